@@ -0,0 +1,171 @@
+//! Describing a compiled program's interface as JSON.
+//!
+//! An [`Abi`] lists what a frontend or script needs to call a program without
+//! reading `build/main.aleo`: the structs and records with their member types
+//! and visibilities, the mappings, and every function's inputs, outputs and
+//! finalize inputs. Closures are left out, since only the program's own
+//! functions can call them.
+//!
+//! Types are written as in Aleo instructions, e.g. `u128` or `Token`, and a
+//! function input or output pairs one with a visibility of `constant`,
+//! `public`, `private` or `record`, so `Token.record` becomes
+//! `{"type": "Token", "visibility": "record"}`. The JSON follows
+//! [`SCHEMA`], whose `version` changes with any incompatible change.
+
+use serde::{Deserialize, Serialize};
+
+use crate::aleo::{Function, Program, ValueType};
+
+/// The version written to, and accepted by, [`SCHEMA`].
+pub const ABI_VERSION: u32 = 1;
+
+/// The JSON Schema an exported ABI validates against.
+pub const SCHEMA: &str = include_str!("../schema/abi.schema.json");
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Abi {
+    pub version: u32,
+    /// The program id, e.g. `token_dsfl348dfl93w1.aleo`.
+    pub program: String,
+    /// The ids of imported programs.
+    pub imports: Vec<String>,
+    pub structs: Vec<StructAbi>,
+    pub records: Vec<RecordAbi>,
+    pub mappings: Vec<MappingAbi>,
+    pub functions: Vec<FunctionAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructAbi {
+    pub name: String,
+    pub members: Vec<MemberAbi>,
+}
+
+/// A struct member, or a record member with its visibility.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberAbi {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordAbi {
+    pub name: String,
+    pub members: Vec<MemberAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingAbi {
+    pub name: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// A function input, output or finalize input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueAbi {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub visibility: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionAbi {
+    pub name: String,
+    pub inputs: Vec<ValueAbi>,
+    pub outputs: Vec<ValueAbi>,
+    /// The inputs of the function's finalize block, which are always public;
+    /// `None` for a function without one.
+    pub finalize: Option<FinalizeAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizeAbi {
+    pub inputs: Vec<ValueAbi>,
+}
+
+impl Abi {
+    pub fn new(program: &Program) -> Self {
+        Self {
+            version: ABI_VERSION,
+            program: program.id.to_string(),
+            imports: program.imports.iter().map(|import| import.program.to_string()).collect(),
+            structs: program
+                .structs()
+                .map(|struct_| StructAbi {
+                    name: struct_.name.to_string(),
+                    members: struct_
+                        .members
+                        .iter()
+                        .map(|member| MemberAbi {
+                            name: member.name.to_string(),
+                            ty: member.ty.to_string(),
+                            visibility: None,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            records: program
+                .records()
+                .map(|record| RecordAbi {
+                    name: record.name.to_string(),
+                    members: record
+                        .members
+                        .iter()
+                        .map(|member| MemberAbi {
+                            name: member.name.to_string(),
+                            ty: member.ty.to_string(),
+                            visibility: Some(member.visibility.to_string()),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            mappings: program
+                .mappings()
+                .map(|mapping| MappingAbi {
+                    name: mapping.name.to_string(),
+                    key: mapping.key.ty.to_string(),
+                    value: mapping.value.ty.to_string(),
+                })
+                .collect(),
+            functions: program.functions().map(function).collect(),
+        }
+    }
+
+    pub fn function(&self, name: &str) -> Option<&FunctionAbi> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("the ABI serializes");
+        json.push('\n');
+        json
+    }
+}
+
+fn function(function: &Function) -> FunctionAbi {
+    FunctionAbi {
+        name: function.name.to_string(),
+        inputs: function.inputs.iter().map(|input| value(&input.ty)).collect(),
+        outputs: function.outputs.iter().map(|output| value(&output.ty)).collect(),
+        finalize: function.finalize.as_ref().map(|(_, finalize)| FinalizeAbi {
+            inputs: finalize
+                .inputs
+                .iter()
+                .map(|input| ValueAbi { ty: input.ty.to_string(), visibility: "public".to_string() })
+                .collect(),
+        }),
+    }
+}
+
+fn value(ty: &ValueType) -> ValueAbi {
+    let (ty, visibility) = match ty {
+        ValueType::Plaintext(ty, visibility) => (ty.to_string(), visibility.to_string()),
+        ValueType::Record(name) => (name.to_string(), "record".to_string()),
+        ValueType::ExternalRecord(locator) => (locator.to_string(), "record".to_string()),
+    };
+    ValueAbi { ty, visibility }
+}
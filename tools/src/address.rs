@@ -0,0 +1,191 @@
+//! Aleo account addresses.
+//!
+//! An address is the bech32m encoding of a 32-byte curve point under the
+//! human-readable part `aleo`: `aleo1` followed by 58 lowercase characters,
+//! the last six of which are a checksum. [`Address::parse`] checks all of it,
+//! and its errors carry the index of the offending character so callers can
+//! point at it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// The human-readable part of every address.
+pub const ADDRESS_HRP: &str = "aleo";
+
+/// `aleo`, the `1` separator, 52 data characters and a 6-character checksum.
+pub const ADDRESS_LENGTH: usize = 63;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const CHECKSUM_LENGTH: usize = 6;
+const BECH32M_CONSTANT: u32 = 0x2bc8_30a3;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum AddressError {
+    #[error("an address starts with `{ADDRESS_HRP}1`")]
+    Prefix,
+    #[error("an address is {ADDRESS_LENGTH} characters long, found {0}")]
+    Length(usize),
+    #[error("`{character}` at character {} is not used in addresses, which contain only `qpzry9x8gf2tvdw0s3jn54khce6mua7l`", index + 1)]
+    Character { index: usize, character: char },
+    #[error("the checksum does not match; character {} is probably a typo for `{expected}`", index + 1)]
+    Typo { index: usize, expected: char },
+    #[error("the checksum does not match; check the address for typos")]
+    Checksum,
+    #[error("the address does not encode 32 bytes")]
+    Padding,
+}
+
+impl AddressError {
+    /// The 0-based index of the character the error is about, if it is about one.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            AddressError::Character { index, .. } | AddressError::Typo { index, .. } => Some(*index),
+            AddressError::Prefix => Some(0),
+            AddressError::Length(_) | AddressError::Checksum | AddressError::Padding => None,
+        }
+    }
+}
+
+/// A well-formed address.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(String);
+
+impl Address {
+    pub fn parse(text: &str) -> Result<Self, AddressError> {
+        let prefix_length = ADDRESS_HRP.len() + 1;
+        if !text.starts_with(ADDRESS_HRP) || text.as_bytes().get(ADDRESS_HRP.len()) != Some(&b'1') {
+            return Err(AddressError::Prefix);
+        }
+        let mut values = Vec::with_capacity(ADDRESS_LENGTH - prefix_length);
+        for (index, character) in text.chars().enumerate().skip(prefix_length) {
+            let value = u8::try_from(character)
+                .ok()
+                .and_then(|byte| CHARSET.iter().position(|&c| c == byte))
+                .ok_or(AddressError::Character { index, character })?;
+            values.push(value as u8);
+        }
+        let length = text.chars().count();
+        if length != ADDRESS_LENGTH {
+            return Err(AddressError::Length(length));
+        }
+
+        if !verify(&values) {
+            return Err(locate_typo(&mut values).map_or(AddressError::Checksum, |(offset, value)| {
+                AddressError::Typo { index: prefix_length + offset, expected: CHARSET[value as usize] as char }
+            }));
+        }
+        let data = &values[..values.len() - CHECKSUM_LENGTH];
+        // 52 groups of five bits hold 32 bytes and four bits of zero padding.
+        if data.last().is_some_and(|last| last & 0b1111 != 0) {
+            return Err(AddressError::Padding);
+        }
+        Ok(Self(text.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The 32 bytes the address encodes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        let (mut accumulator, mut bits, mut index) = (0u32, 0, 0);
+        let data = &self.0.as_bytes()[ADDRESS_HRP.len() + 1..ADDRESS_LENGTH - CHECKSUM_LENGTH];
+        for &c in data {
+            let value = CHARSET.iter().position(|&charset| charset == c).expect("checked when parsed") as u32;
+            accumulator = (accumulator << 5) | value;
+            bits += 5;
+            if bits >= 8 && index < bytes.len() {
+                bits -= 8;
+                bytes[index] = (accumulator >> bits) as u8;
+                index += 1;
+            }
+        }
+        bytes
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Encodes `bytes` under the human-readable part `hrp` with a bech32m
+/// checksum, the way addresses and other Aleo values such as state roots
+/// (`ar1...`) are written.
+pub fn encode_bech32m(hrp: &str, bytes: &[u8]) -> String {
+    let mut values = Vec::with_capacity((bytes.len() * 8).div_ceil(5) + CHECKSUM_LENGTH);
+    let (mut accumulator, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        accumulator = (accumulator << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push(((accumulator >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        values.push(((accumulator << (5 - bits)) & 31) as u8);
+    }
+    let checksum = polymod(expand(hrp).chain(values.iter().copied()).chain([0; CHECKSUM_LENGTH])) ^ BECH32M_CONSTANT;
+    values.extend((0..CHECKSUM_LENGTH).map(|index| ((checksum >> (5 * (CHECKSUM_LENGTH - 1 - index))) & 31) as u8));
+    let data: String = values.iter().map(|&value| CHARSET[value as usize] as char).collect();
+    format!("{hrp}1{data}")
+}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATORS: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ff_ffff) << 5) ^ u32::from(value);
+        for (bit, generator) in GENERATORS.iter().enumerate() {
+            if (top >> bit) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// The human-readable part as it enters the checksum.
+fn expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes().map(|c| c >> 5).chain([0]).chain(hrp.bytes().map(|c| c & 31))
+}
+
+/// Checks the bech32m checksum of the data characters after `aleo1`.
+fn verify(values: &[u8]) -> bool {
+    polymod(expand(ADDRESS_HRP).chain(values.iter().copied())) == BECH32M_CONSTANT
+}
+
+/// Finds the single substituted character that would make the checksum
+/// match, if there is exactly one.
+fn locate_typo(values: &mut [u8]) -> Option<(usize, u8)> {
+    let mut found = None;
+    for offset in 0..values.len() {
+        let original = values[offset];
+        for candidate in (0..32).filter(|&candidate| candidate != original) {
+            values[offset] = candidate;
+            if verify(values) {
+                if found.is_some() {
+                    values[offset] = original;
+                    return None;
+                }
+                found = Some((offset, candidate));
+            }
+        }
+        values[offset] = original;
+    }
+    found
+}
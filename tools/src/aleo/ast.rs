@@ -0,0 +1,455 @@
+//! The typed syntax tree of an Aleo instructions program.
+
+use crate::diagnostic::Span;
+
+use super::opcode::{AssertOp, BinaryOp, CommitOp, HashOp, UnaryOp};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Identifier {
+    pub name: String,
+    pub span: Span,
+}
+
+impl Identifier {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), span: Span::default() }
+    }
+}
+
+/// `name.network`, e.g. `token_dsfl348dfl93w1.aleo`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramId {
+    pub name: Identifier,
+    pub network: Identifier,
+}
+
+/// A resource of another program, e.g. `credits.aleo/credits`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Locator {
+    pub program: ProgramId,
+    pub resource: Identifier,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LiteralType {
+    Address,
+    Boolean,
+    Field,
+    Group,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Scalar,
+    Signature,
+}
+
+impl LiteralType {
+    pub const ALL: &'static [LiteralType] = &[
+        LiteralType::Address,
+        LiteralType::Boolean,
+        LiteralType::Field,
+        LiteralType::Group,
+        LiteralType::I8,
+        LiteralType::I16,
+        LiteralType::I32,
+        LiteralType::I64,
+        LiteralType::I128,
+        LiteralType::U8,
+        LiteralType::U16,
+        LiteralType::U32,
+        LiteralType::U64,
+        LiteralType::U128,
+        LiteralType::Scalar,
+        LiteralType::Signature,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LiteralType::Address => "address",
+            LiteralType::Boolean => "boolean",
+            LiteralType::Field => "field",
+            LiteralType::Group => "group",
+            LiteralType::I8 => "i8",
+            LiteralType::I16 => "i16",
+            LiteralType::I32 => "i32",
+            LiteralType::I64 => "i64",
+            LiteralType::I128 => "i128",
+            LiteralType::U8 => "u8",
+            LiteralType::U16 => "u16",
+            LiteralType::U32 => "u32",
+            LiteralType::U64 => "u64",
+            LiteralType::U128 => "u128",
+            LiteralType::Scalar => "scalar",
+            LiteralType::Signature => "signature",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|ty| ty.name() == name)
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.is_signed() || self.is_unsigned()
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, LiteralType::I8 | LiteralType::I16 | LiteralType::I32 | LiteralType::I64 | LiteralType::I128)
+    }
+
+    pub fn is_unsigned(self) -> bool {
+        matches!(self, LiteralType::U8 | LiteralType::U16 | LiteralType::U32 | LiteralType::U64 | LiteralType::U128)
+    }
+}
+
+/// A literal or struct type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlaintextType {
+    Literal(LiteralType),
+    Struct(Identifier),
+}
+
+/// The type of a closure input or output, or of a `cast` destination.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegisterType {
+    Plaintext(PlaintextType),
+    Record(Identifier),
+    ExternalRecord(Locator),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visibility {
+    Constant,
+    Public,
+    Private,
+}
+
+/// The type of a function input or output, including its visibility.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    Plaintext(PlaintextType, Visibility),
+    Record(Identifier),
+    ExternalRecord(Locator),
+}
+
+/// A literal as written, e.g. `100u32`, `true` or `aleo1...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal {
+    pub ty: LiteralType,
+    pub text: String,
+}
+
+impl Literal {
+    /// Recognizes a literal from its text, inferring the type from its form:
+    /// `true`/`false`, an `aleo1` address, a `sign1` signature, or a number
+    /// with a type suffix such as `100u32`, `-5i8` or `0group`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let ty = match text {
+            "true" | "false" => LiteralType::Boolean,
+            _ if text.starts_with("aleo1") => LiteralType::Address,
+            _ if text.starts_with("sign1") => LiteralType::Signature,
+            _ => {
+                let (negative, unsigned) = match text.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, text),
+                };
+                let split = unsigned.find(|c: char| !(c.is_ascii_digit() || c == '_'))?;
+                let (digits, suffix) = unsigned.split_at(split);
+                let ty = LiteralType::from_name(suffix)?;
+                let numeric =
+                    ty.is_integer() || matches!(ty, LiteralType::Field | LiteralType::Group | LiteralType::Scalar);
+                if !numeric || !digits.starts_with(|c: char| c.is_ascii_digit()) || (negative && ty.is_unsigned()) {
+                    return None;
+                }
+                ty
+            }
+        };
+        Some(Self { ty, text: text.to_string() })
+    }
+
+    /// The literal's text without its type suffix, e.g. `100` for `100u32`.
+    pub fn value_text(&self) -> &str {
+        self.text.strip_suffix(self.ty.name()).unwrap_or(&self.text)
+    }
+}
+
+/// `r2` or `r2.balance`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Register {
+    pub index: u32,
+    pub members: Vec<Identifier>,
+}
+
+impl Register {
+    pub fn new(index: u32) -> Self {
+        Self { index, members: Vec::new() }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Literal(Literal),
+    Register(Register),
+    ProgramId(ProgramId),
+    /// `self.caller`
+    Caller,
+    /// `self.signer`
+    Signer,
+    /// `block.height`, only valid in finalize.
+    BlockHeight,
+}
+
+/// The target of a `call`: a local closure or another program's function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CallTarget {
+    Local(Identifier),
+    External(Locator),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Unary { op: UnaryOp, operand: Operand, destination: Register },
+    Binary { op: BinaryOp, operands: [Operand; 2], destination: Register },
+    Ternary { operands: [Operand; 3], destination: Register },
+    Assert { op: AssertOp, operands: [Operand; 2] },
+    Hash { op: HashOp, operand: Operand, destination: Register, ty: Option<PlaintextType> },
+    Commit { op: CommitOp, operands: [Operand; 2], destination: Register, ty: Option<PlaintextType> },
+    Cast { lossy: bool, operands: Vec<Operand>, destination: Register, ty: RegisterType },
+    Call { target: CallTarget, operands: Vec<Operand>, destinations: Vec<Register> },
+    SignVerify { operands: [Operand; 3], destination: Register },
+}
+
+impl Operation {
+    /// The registers the operation writes.
+    pub fn destinations(&self) -> Vec<&Register> {
+        match self {
+            Operation::Unary { destination, .. }
+            | Operation::Binary { destination, .. }
+            | Operation::Ternary { destination, .. }
+            | Operation::Hash { destination, .. }
+            | Operation::Commit { destination, .. }
+            | Operation::Cast { destination, .. }
+            | Operation::SignVerify { destination, .. } => vec![destination],
+            Operation::Call { destinations, .. } => destinations.iter().collect(),
+            Operation::Assert { .. } => Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub span: Span,
+}
+
+/// `mapping[key]`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingAccess {
+    pub mapping: Identifier,
+    pub key: Operand,
+}
+
+/// A statement of a finalize block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandKind {
+    Instruction(Operation),
+    Get { access: MappingAccess, destination: Register },
+    GetOrUse { access: MappingAccess, default: Operand, destination: Register },
+    Set { value: Operand, access: MappingAccess },
+    Contains { access: MappingAccess, destination: Register },
+    Remove { access: MappingAccess },
+    RandChaCha { operands: Vec<Operand>, destination: Register, ty: LiteralType },
+    Position { label: Identifier },
+    BranchEq { operands: [Operand; 2], label: Identifier },
+    BranchNeq { operands: [Operand; 2], label: Identifier },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Command {
+    pub kind: CommandKind,
+    pub span: Span,
+}
+
+/// `input r0 as <T>;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input<T> {
+    pub register: Register,
+    pub ty: T,
+    pub span: Span,
+}
+
+/// `output <operand> as <T>;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Output<T> {
+    pub operand: Operand,
+    pub ty: T,
+    pub span: Span,
+}
+
+/// `name as type;` inside a struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructMember {
+    pub name: Identifier,
+    pub ty: PlaintextType,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Struct {
+    pub name: Identifier,
+    pub members: Vec<StructMember>,
+    pub span: Span,
+}
+
+/// `name as type.visibility;` inside a record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordMember {
+    pub name: Identifier,
+    pub ty: PlaintextType,
+    pub visibility: Visibility,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub name: Identifier,
+    pub members: Vec<RecordMember>,
+    pub span: Span,
+}
+
+impl Record {
+    pub fn member(&self, name: &str) -> Option<&RecordMember> {
+        self.members.iter().find(|member| member.name.name == name)
+    }
+}
+
+/// The `key` or `value` line of a mapping. Older programs name them
+/// (`key left as ...`), newer ones do not.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingEntry {
+    pub label: Option<Identifier>,
+    pub ty: PlaintextType,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    pub name: Identifier,
+    pub key: MappingEntry,
+    pub value: MappingEntry,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Closure {
+    pub name: Identifier,
+    pub inputs: Vec<Input<RegisterType>>,
+    pub instructions: Vec<Instruction>,
+    pub outputs: Vec<Output<RegisterType>>,
+    pub span: Span,
+}
+
+/// The `finalize r0 r1;` statement ending a function body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalizeCall {
+    pub operands: Vec<Operand>,
+    pub span: Span,
+}
+
+/// The `finalize name:` block that runs on-chain after a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finalize {
+    pub name: Identifier,
+    /// Finalize inputs are always public, `input r0 as u32.public;`.
+    pub inputs: Vec<Input<PlaintextType>>,
+    pub commands: Vec<Command>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    pub name: Identifier,
+    pub inputs: Vec<Input<ValueType>>,
+    pub instructions: Vec<Instruction>,
+    pub outputs: Vec<Output<ValueType>>,
+    pub finalize: Option<(FinalizeCall, Finalize)>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Declaration {
+    Struct(Struct),
+    Record(Record),
+    Mapping(Mapping),
+    Closure(Closure),
+    Function(Function),
+}
+
+impl Declaration {
+    pub fn name(&self) -> &Identifier {
+        match self {
+            Declaration::Struct(struct_) => &struct_.name,
+            Declaration::Record(record) => &record.name,
+            Declaration::Mapping(mapping) => &mapping.name,
+            Declaration::Closure(closure) => &closure.name,
+            Declaration::Function(function) => &function.name,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Declaration::Struct(struct_) => struct_.span,
+            Declaration::Record(record) => record.span,
+            Declaration::Mapping(mapping) => mapping.span,
+            Declaration::Closure(closure) => closure.span,
+            Declaration::Function(function) => function.span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Import {
+    pub program: ProgramId,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Program {
+    pub imports: Vec<Import>,
+    pub id: ProgramId,
+    pub declarations: Vec<Declaration>,
+    pub span: Span,
+}
+
+macro_rules! lookups {
+    ($($all:ident, $one:ident => $variant:ident($ty:ty);)*) => {
+        impl Program {
+            $(
+                pub fn $all(&self) -> impl Iterator<Item = &$ty> {
+                    self.declarations.iter().filter_map(|declaration| match declaration {
+                        Declaration::$variant(item) => Some(item),
+                        _ => None,
+                    })
+                }
+
+                pub fn $one(&self, name: &str) -> Option<&$ty> {
+                    self.$all().find(|item| item.name.name == name)
+                }
+            )*
+        }
+    };
+}
+
+lookups! {
+    structs, struct_ => Struct(Struct);
+    records, record => Record(Record);
+    mappings, mapping => Mapping(Mapping);
+    closures, closure => Closure(Closure);
+    functions, function => Function(Function);
+}
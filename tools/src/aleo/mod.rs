@@ -0,0 +1,32 @@
+//! Aleo instructions: the language of a compiled `build/main.aleo`.
+//!
+//! [`parse`] turns a program into a typed [`Program`] whose nodes carry their
+//! source [`Span`](crate::diagnostic::Span)s, covering imports, structs,
+//! records, mappings, closures, functions with their finalize blocks, and
+//! every instruction and finalize command. Every node implements `Display`,
+//! printing the layout `leo build` emits, so compiler output re-serializes
+//! byte for byte:
+//!
+//! ```
+//! let source = "program token.aleo;\n\nfunction mint:\n    input r0 as u32.private;\n    output r0 as u32.private;\n";
+//! let program = workshop::aleo::parse(source).unwrap();
+//! assert_eq!(program.to_string(), source);
+//! ```
+
+pub mod ast;
+pub mod opcode;
+mod parser;
+mod printer;
+
+pub use ast::*;
+pub use opcode::{AssertOp, BinaryOp, CommitOp, HashOp, UnaryOp};
+pub use parser::{parse, ParseError};
+
+/// Returns the id from the `program <id>;` header and the 1-based line it is
+/// on, without parsing the rest of the program.
+pub fn program_id(source: &str) -> Option<(&str, usize)> {
+    source.lines().enumerate().find_map(|(index, line)| {
+        let id = line.split("//").next()?.trim().strip_prefix("program ")?.trim_end_matches(';').trim();
+        Some((id, index + 1))
+    })
+}
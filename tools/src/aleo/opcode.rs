@@ -0,0 +1,143 @@
+//! The opcodes of Aleo instructions, grouped by the shape of their operands.
+
+use std::fmt;
+
+macro_rules! opcodes {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $mnemonic:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            pub fn mnemonic(self) -> &'static str {
+                match self {
+                    $($name::$variant => $mnemonic,)*
+                }
+            }
+
+            pub fn from_mnemonic(text: &str) -> Option<Self> {
+                match text {
+                    $($mnemonic => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.mnemonic())
+            }
+        }
+    };
+}
+
+opcodes! {
+    /// `op a into r;`
+    UnaryOp {
+        Abs => "abs",
+        AbsWrapped => "abs.w",
+        Double => "double",
+        Inv => "inv",
+        Neg => "neg",
+        Not => "not",
+        Square => "square",
+        SquareRoot => "sqrt",
+    }
+}
+
+opcodes! {
+    /// `op a b into r;`
+    BinaryOp {
+        Add => "add",
+        AddWrapped => "add.w",
+        And => "and",
+        Div => "div",
+        DivWrapped => "div.w",
+        GreaterThan => "gt",
+        GreaterThanOrEqual => "gte",
+        IsEq => "is.eq",
+        IsNeq => "is.neq",
+        LessThan => "lt",
+        LessThanOrEqual => "lte",
+        Modulo => "mod",
+        Mul => "mul",
+        MulWrapped => "mul.w",
+        Nand => "nand",
+        Nor => "nor",
+        Or => "or",
+        Pow => "pow",
+        PowWrapped => "pow.w",
+        Rem => "rem",
+        RemWrapped => "rem.w",
+        Shl => "shl",
+        ShlWrapped => "shl.w",
+        Shr => "shr",
+        ShrWrapped => "shr.w",
+        Sub => "sub",
+        SubWrapped => "sub.w",
+        Xor => "xor",
+    }
+}
+
+impl BinaryOp {
+    /// Whether the operation halts on overflow, underflow or division by
+    /// zero instead of wrapping.
+    pub fn is_checked(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Add
+                | BinaryOp::Div
+                | BinaryOp::Mul
+                | BinaryOp::Pow
+                | BinaryOp::Rem
+                | BinaryOp::Shl
+                | BinaryOp::Shr
+                | BinaryOp::Sub
+        )
+    }
+}
+
+opcodes! {
+    /// `op a b;`
+    AssertOp {
+        Eq => "assert.eq",
+        Neq => "assert.neq",
+    }
+}
+
+opcodes! {
+    /// `op a into r [as type];`
+    HashOp {
+        Bhp256 => "hash.bhp256",
+        Bhp512 => "hash.bhp512",
+        Bhp768 => "hash.bhp768",
+        Bhp1024 => "hash.bhp1024",
+        Keccak256 => "hash.keccak256",
+        Keccak384 => "hash.keccak384",
+        Keccak512 => "hash.keccak512",
+        Ped64 => "hash.ped64",
+        Ped128 => "hash.ped128",
+        Psd2 => "hash.psd2",
+        Psd4 => "hash.psd4",
+        Psd8 => "hash.psd8",
+        Sha3_256 => "hash.sha3_256",
+        Sha3_384 => "hash.sha3_384",
+        Sha3_512 => "hash.sha3_512",
+    }
+}
+
+opcodes! {
+    /// `op a b into r [as type];`
+    CommitOp {
+        Bhp256 => "commit.bhp256",
+        Bhp512 => "commit.bhp512",
+        Bhp768 => "commit.bhp768",
+        Bhp1024 => "commit.bhp1024",
+        Ped64 => "commit.ped64",
+        Ped128 => "commit.ped128",
+    }
+}
@@ -0,0 +1,785 @@
+//! The parser behind [`parse`](super::parse).
+//!
+//! Source is split into tokens, the tokens into statements ending in `;` or
+//! `:`, and the statements into declarations by their header line. Every
+//! error is a [`ParseError`] spanning the offending token or statement;
+//! malformed input never panics.
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::diagnostic::{Diagnostic, Position, Span};
+
+use super::ast::*;
+use super::opcode::{AssertOp, BinaryOp, CommitOp, HashOp, UnaryOp};
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.span.start, self.message)
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        Diagnostic::error(error.span, error.message)
+    }
+}
+
+type Result<T> = std::result::Result<T, ParseError>;
+
+fn error<T>(span: Span, message: impl Into<String>) -> Result<T> {
+    Err(ParseError { span, message: message.into() })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Token {
+    text: String,
+    span: Span,
+}
+
+impl Token {
+    fn is(&self, text: &str) -> bool {
+        self.text == text
+    }
+
+    /// The span of the characters `start..end` of a single-line token.
+    fn sub_span(&self, start: usize, end: usize) -> Span {
+        let line = self.span.start.line;
+        let column = self.span.start.column;
+        Span::new(Position::new(line, column + start), Position::new(line, column + end))
+    }
+}
+
+/// Splits source into words and the punctuation `; : [ ] /`, dropping
+/// whitespace and `//` comments. Words keep their dots, so `r2.balance`,
+/// `u32.private` and `hash.bhp256` are single tokens.
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let position = |column: usize| Position::new(index + 1, column + 1);
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c.is_whitespace() {
+                i += 1;
+            } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+                break;
+            } else if ";:[]/".contains(c) {
+                tokens.push(Token { text: c.to_string(), span: Span::new(position(i), position(i + 1)) });
+                i += 1;
+            } else if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push(Token { text, span: Span::new(position(start), position(i)) });
+            } else {
+                return error(Span::new(position(i), position(i + 1)), format!("unexpected character `{c}`"));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// The tokens of one statement, up to its `;` or `:` terminator.
+#[derive(Clone, Debug)]
+struct Statement {
+    tokens: Vec<Token>,
+    terminator: char,
+    span: Span,
+}
+
+impl Statement {
+    fn first(&self) -> &str {
+        &self.tokens[0].text
+    }
+
+    fn is_header(&self) -> bool {
+        self.terminator == ':'
+    }
+
+    /// The tokens after the statement's leading keyword.
+    fn rest(&self) -> &[Token] {
+        &self.tokens[1..]
+    }
+}
+
+fn statements(tokens: Vec<Token>) -> Result<Vec<Statement>> {
+    let mut statements = Vec::new();
+    let mut current: Vec<Token> = Vec::new();
+    for token in tokens {
+        if token.is(";") || token.is(":") {
+            let Some(first) = current.first() else {
+                return error(token.span, format!("unexpected `{}`", token.text));
+            };
+            let span = first.span.to(token.span);
+            let terminator = token.text.chars().next().expect("punctuation is one character");
+            statements.push(Statement { tokens: std::mem::take(&mut current), terminator, span });
+        } else {
+            current.push(token);
+        }
+    }
+    if let Some(token) = current.first() {
+        return error(token.span, "statement is missing its terminating `;`");
+    }
+    Ok(statements)
+}
+
+const DECLARATION_KEYWORDS: &[&str] = &["struct", "record", "mapping", "closure", "function"];
+
+/// Parses an Aleo instructions program.
+pub fn parse(source: &str) -> Result<Program> {
+    let statements = statements(tokenize(source)?)?;
+    let mut parser = Parser { statements, index: 0 };
+    parser.program(source)
+}
+
+struct Parser {
+    statements: Vec<Statement>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Statement> {
+        self.statements.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Statement> {
+        let statement = self.statements.get(self.index).cloned();
+        self.index += 1;
+        statement
+    }
+
+    /// Whether the next statement starts a new top-level declaration.
+    fn at_declaration(&self) -> bool {
+        self.peek().is_none_or(|statement| statement.is_header() && DECLARATION_KEYWORDS.contains(&statement.first()))
+    }
+
+    fn program(&mut self, source: &str) -> Result<Program> {
+        let mut imports = Vec::new();
+        let header = loop {
+            let Some(statement) = self.next() else {
+                return error(end_span(source), "missing `program <name>.aleo;` header");
+            };
+            match (statement.first(), statement.rest(), statement.terminator) {
+                ("import", [id], ';') => imports.push(Import { program: program_id(id)?, span: statement.span }),
+                ("program", [id], ';') => break program_id(id)?,
+                _ => return error(statement.span, "expected `import <program>;` or `program <name>.aleo;`"),
+            }
+        };
+
+        let mut declarations = Vec::new();
+        while let Some(statement) = self.next() {
+            if !statement.is_header() {
+                return error(statement.span, "expected a declaration such as `function <name>:`");
+            }
+            let name = match statement.rest() {
+                [name] => identifier(name)?,
+                _ => return error(statement.span, format!("expected `{} <name>:`", statement.first())),
+            };
+            let declaration = match statement.first() {
+                "struct" => Declaration::Struct(self.struct_(name, statement.span)?),
+                "record" => Declaration::Record(self.record(name, statement.span)?),
+                "mapping" => Declaration::Mapping(self.mapping(name, statement.span)?),
+                "closure" => Declaration::Closure(self.closure(name, statement.span)?),
+                "function" => Declaration::Function(self.function(name, statement.span)?),
+                "finalize" => return error(statement.span, "`finalize` block must directly follow its function"),
+                other => return error(statement.tokens[0].span, format!("unknown declaration `{other}`")),
+            };
+            if let Some(previous) =
+                declarations.iter().find(|d: &&Declaration| d.name().name == declaration.name().name)
+            {
+                return error(
+                    declaration.name().span,
+                    format!("`{}` is already declared at {}", declaration.name().name, previous.span().start),
+                );
+            }
+            declarations.push(declaration);
+        }
+
+        let span = Span::new(Position::new(1, 1), end_span(source).end);
+        Ok(Program { imports, id: header, declarations, span })
+    }
+
+    /// Collects the statements of a declaration body.
+    fn body(&mut self) -> Vec<Statement> {
+        let mut body = Vec::new();
+        while !self.at_declaration() {
+            if self.peek().is_some_and(|statement| statement.is_header() && statement.first() == "finalize") {
+                break;
+            }
+            body.extend(self.next());
+        }
+        body
+    }
+
+    fn struct_(&mut self, name: Identifier, header: Span) -> Result<Struct> {
+        let mut members = Vec::new();
+        let mut span = header;
+        for statement in self.body() {
+            span = span.to(statement.span);
+            match statement.tokens.as_slice() {
+                [member, as_, ty @ ..] if as_.is("as") && !ty.is_empty() => members.push(StructMember {
+                    name: identifier(member)?,
+                    ty: plaintext_type(ty, statement.span)?,
+                    span: statement.span,
+                }),
+                _ => return error(statement.span, "expected `<member> as <type>;`"),
+            }
+        }
+        Ok(Struct { name, members, span })
+    }
+
+    fn record(&mut self, name: Identifier, header: Span) -> Result<Record> {
+        let mut members = Vec::new();
+        let mut span = header;
+        for statement in self.body() {
+            span = span.to(statement.span);
+            match statement.tokens.as_slice() {
+                [member, as_, ty @ ..] if as_.is("as") && !ty.is_empty() => {
+                    let ValueType::Plaintext(ty, visibility) = value_type(ty, statement.span)? else {
+                        return error(statement.span, "record members must be plaintext types");
+                    };
+                    members.push(RecordMember { name: identifier(member)?, ty, visibility, span: statement.span });
+                }
+                _ => return error(statement.span, "expected `<member> as <type>.<visibility>;`"),
+            }
+        }
+        Ok(Record { name, members, span })
+    }
+
+    fn mapping(&mut self, name: Identifier, header: Span) -> Result<Mapping> {
+        let mut entries = Vec::new();
+        let mut span = header;
+        for statement in self.body() {
+            span = span.to(statement.span);
+            let (label, ty) = match statement.rest() {
+                [as_, ty @ ..] if as_.is("as") => (None, ty),
+                [label, as_, ty @ ..] if as_.is("as") => (Some(identifier(label)?), ty),
+                _ => return error(statement.span, format!("expected `{} as <type>.public;`", statement.first())),
+            };
+            let ty = public_type(ty, statement.span)?;
+            entries.push((statement.first().to_string(), MappingEntry { label, ty, span: statement.span }));
+        }
+        match <[_; 2]>::try_from(entries) {
+            Ok([(key_word, key), (value_word, value)]) if key_word == "key" && value_word == "value" => {
+                Ok(Mapping { name, key, value, span })
+            }
+            _ => error(span, "a mapping declares exactly one `key` line followed by one `value` line"),
+        }
+    }
+
+    fn closure(&mut self, name: Identifier, header: Span) -> Result<Closure> {
+        let mut closure =
+            Closure { name, inputs: Vec::new(), instructions: Vec::new(), outputs: Vec::new(), span: header };
+        for statement in self.body() {
+            closure.span = closure.span.to(statement.span);
+            match statement.first() {
+                "input" => {
+                    let (register, ty) = typed_register(&statement)?;
+                    closure.inputs.push(Input {
+                        register,
+                        ty: register_type(ty, statement.span)?,
+                        span: statement.span,
+                    });
+                }
+                "output" => {
+                    let (operand, ty) = typed_operand(&statement)?;
+                    closure.outputs.push(Output {
+                        operand,
+                        ty: register_type(ty, statement.span)?,
+                        span: statement.span,
+                    });
+                }
+                _ => closure.instructions.push(instruction(&statement)?),
+            }
+        }
+        Ok(closure)
+    }
+
+    fn function(&mut self, name: Identifier, header: Span) -> Result<Function> {
+        let mut function = Function {
+            name,
+            inputs: Vec::new(),
+            instructions: Vec::new(),
+            outputs: Vec::new(),
+            finalize: None,
+            span: header,
+        };
+        let mut call = None;
+        for statement in self.body() {
+            function.span = function.span.to(statement.span);
+            if call.is_some() {
+                return error(statement.span, "`finalize` must be the last statement of a function");
+            }
+            match statement.first() {
+                "input" => {
+                    let (register, ty) = typed_register(&statement)?;
+                    function.inputs.push(Input { register, ty: value_type(ty, statement.span)?, span: statement.span });
+                }
+                "output" => {
+                    let (operand, ty) = typed_operand(&statement)?;
+                    function.outputs.push(Output {
+                        operand,
+                        ty: value_type(ty, statement.span)?,
+                        span: statement.span,
+                    });
+                }
+                "finalize" => {
+                    let operands = statement.rest().iter().map(operand).collect::<Result<_>>()?;
+                    call = Some(FinalizeCall { operands, span: statement.span });
+                }
+                _ => function.instructions.push(instruction(&statement)?),
+            }
+        }
+
+        let has_block = self.peek().is_some_and(|statement| statement.is_header() && statement.first() == "finalize");
+        match (call, has_block) {
+            (Some(call), true) => {
+                let header = self.next().expect("peeked");
+                let finalize = self.finalize(&function.name, &header)?;
+                function.span = function.span.to(finalize.span);
+                function.finalize = Some((call, finalize));
+            }
+            (Some(call), false) => {
+                return error(call.span, format!("`finalize` is missing its `finalize {}:` block", function.name.name))
+            }
+            (None, true) => {
+                let header = self.peek().expect("peeked");
+                return error(header.span, format!("function `{}` has no `finalize` statement", function.name.name));
+            }
+            (None, false) => {}
+        }
+        Ok(function)
+    }
+
+    fn finalize(&mut self, function: &Identifier, header: &Statement) -> Result<Finalize> {
+        let name = match header.rest() {
+            [name] => identifier(name)?,
+            _ => return error(header.span, "expected `finalize <name>:`"),
+        };
+        if name.name != function.name {
+            return error(name.span, format!("finalize block must be named `{}`", function.name));
+        }
+        let mut finalize = Finalize { name, inputs: Vec::new(), commands: Vec::new(), span: header.span };
+        for statement in self.body() {
+            finalize.span = finalize.span.to(statement.span);
+            match statement.first() {
+                "input" => {
+                    let (register, ty) = typed_register(&statement)?;
+                    let ty = public_type(ty, statement.span)?;
+                    finalize.inputs.push(Input { register, ty, span: statement.span });
+                }
+                _ => finalize.commands.push(command(&statement)?),
+            }
+        }
+        Ok(finalize)
+    }
+}
+
+fn end_span(source: &str) -> Span {
+    let line = source.lines().count().max(1);
+    let column = source.lines().last().map_or(0, |line| line.chars().count()) + 1;
+    Span::new(Position::new(line, column), Position::new(line, column))
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn identifier(token: &Token) -> Result<Identifier> {
+    if !is_identifier(&token.text) {
+        return error(token.span, format!("`{}` is not a valid identifier", token.text));
+    }
+    Ok(Identifier { name: token.text.clone(), span: token.span })
+}
+
+fn identifier_at(token: &Token, text: &str, start: usize) -> Result<Identifier> {
+    let span = token.sub_span(start, start + text.chars().count());
+    if !is_identifier(text) {
+        return error(span, format!("`{text}` is not a valid identifier"));
+    }
+    Ok(Identifier { name: text.to_string(), span })
+}
+
+fn program_id(token: &Token) -> Result<ProgramId> {
+    match token.text.split_once('.') {
+        Some((name, network)) if !network.contains('.') => Ok(ProgramId {
+            name: identifier_at(token, name, 0)?,
+            network: identifier_at(token, network, name.len() + 1)?,
+        }),
+        _ => error(token.span, format!("expected a program id like `name.aleo`, found `{}`", token.text)),
+    }
+}
+
+/// The text and span of a type written across several tokens, e.g.
+/// `credits.aleo` `/` `credits.record`. An empty type is an error at `at`.
+fn joined(tokens: &[Token], at: Span) -> Result<(String, Span)> {
+    let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+        return error(at, "expected a type after `as`");
+    };
+    let text = tokens.iter().map(|token| token.text.as_str()).collect();
+    Ok((text, first.span.to(last.span)))
+}
+
+fn plaintext_type_text(text: &str, span: Span) -> Result<PlaintextType> {
+    if let Some(ty) = LiteralType::from_name(text) {
+        return Ok(PlaintextType::Literal(ty));
+    }
+    if is_identifier(text) {
+        return Ok(PlaintextType::Struct(Identifier { name: text.to_string(), span }));
+    }
+    error(span, format!("`{text}` is not a valid type"))
+}
+
+fn plaintext_type(tokens: &[Token], at: Span) -> Result<PlaintextType> {
+    let (text, span) = joined(tokens, at)?;
+    plaintext_type_text(&text, span)
+}
+
+fn locator_text(text: &str, span: Span) -> Result<Locator> {
+    let Some((program, resource)) = text.split_once('/') else {
+        return error(span, format!("expected `program.aleo/name`, found `{text}`"));
+    };
+    let Some((name, network)) = program.split_once('.') else {
+        return error(span, format!("expected a program id like `name.aleo`, found `{program}`"));
+    };
+    let ident = |text: &str| {
+        if is_identifier(text) {
+            Ok(Identifier { name: text.to_string(), span })
+        } else {
+            error(span, format!("`{text}` is not a valid identifier"))
+        }
+    };
+    Ok(Locator { program: ProgramId { name: ident(name)?, network: ident(network)? }, resource: ident(resource)? })
+}
+
+/// Parses `<type>.record`, returning `None` for any other type.
+fn record_type(text: &str, span: Span) -> Result<Option<RegisterType>> {
+    let Some(name) = text.strip_suffix(".record") else {
+        return Ok(None);
+    };
+    if name.contains('/') {
+        return Ok(Some(RegisterType::ExternalRecord(locator_text(name, span)?)));
+    }
+    match plaintext_type_text(name, span)? {
+        PlaintextType::Struct(name) => Ok(Some(RegisterType::Record(name))),
+        PlaintextType::Literal(_) => error(span, format!("`{name}` is not a record")),
+    }
+}
+
+fn register_type(tokens: &[Token], at: Span) -> Result<RegisterType> {
+    let (text, span) = joined(tokens, at)?;
+    match record_type(&text, span)? {
+        Some(ty) => Ok(ty),
+        None => Ok(RegisterType::Plaintext(plaintext_type_text(&text, span)?)),
+    }
+}
+
+fn value_type(tokens: &[Token], at: Span) -> Result<ValueType> {
+    let (text, span) = joined(tokens, at)?;
+    match record_type(&text, span)? {
+        Some(RegisterType::Record(name)) => return Ok(ValueType::Record(name)),
+        Some(RegisterType::ExternalRecord(locator)) => return Ok(ValueType::ExternalRecord(locator)),
+        _ => {}
+    }
+    let Some((ty, visibility)) = text.rsplit_once('.') else {
+        return error(span, format!("`{text}` needs a visibility: `.constant`, `.public` or `.private`"));
+    };
+    let visibility = match visibility {
+        "constant" => Visibility::Constant,
+        "public" => Visibility::Public,
+        "private" => Visibility::Private,
+        other => return error(span, format!("unknown visibility `{other}`")),
+    };
+    Ok(ValueType::Plaintext(plaintext_type_text(ty, span)?, visibility))
+}
+
+/// Parses a `<type>.public` as used by mappings and finalize inputs.
+fn public_type(tokens: &[Token], span: Span) -> Result<PlaintextType> {
+    match value_type(tokens, span)? {
+        ValueType::Plaintext(ty, Visibility::Public) => Ok(ty),
+        _ => error(span, "expected a `.public` plaintext type"),
+    }
+}
+
+fn register(token: &Token) -> Result<Register> {
+    let mut parts = token.text.split('.');
+    let head = parts.next().unwrap_or_default();
+    let Some(index) = head.strip_prefix('r').and_then(|index| index.parse::<u32>().ok()) else {
+        return error(token.span, format!("expected a register like `r0`, found `{}`", token.text));
+    };
+    let mut members = Vec::new();
+    let mut offset = head.len() + 1;
+    for part in parts {
+        members.push(identifier_at(token, part, offset)?);
+        offset += part.len() + 1;
+    }
+    Ok(Register { index, members })
+}
+
+fn destination(token: &Token) -> Result<Register> {
+    let register = register(token)?;
+    if !register.members.is_empty() {
+        return error(token.span, "a destination must be a plain register like `r3`");
+    }
+    Ok(register)
+}
+
+fn operand(token: &Token) -> Result<Operand> {
+    match token.text.as_str() {
+        "self.caller" => return Ok(Operand::Caller),
+        "self.signer" => return Ok(Operand::Signer),
+        "block.height" => return Ok(Operand::BlockHeight),
+        _ => {}
+    }
+    if let Some(literal) = Literal::parse(&token.text) {
+        return Ok(Operand::Literal(literal));
+    }
+    let is_register = token
+        .text
+        .strip_prefix('r')
+        .is_some_and(|rest| rest.split('.').next().is_some_and(|index| index.parse::<u32>().is_ok()));
+    if is_register {
+        return Ok(Operand::Register(register(token)?));
+    }
+    if token.text.ends_with(".aleo") {
+        return Ok(Operand::ProgramId(program_id(token)?));
+    }
+    error(token.span, format!("`{}` is not a valid operand", token.text))
+}
+
+/// Parses `input r0 as <type>`, returning the register and the type tokens.
+fn typed_register(statement: &Statement) -> Result<(Register, &[Token])> {
+    match statement.rest() {
+        [register_token, as_, ty @ ..] if as_.is("as") && !ty.is_empty() => Ok((register(register_token)?, ty)),
+        _ => error(statement.span, format!("expected `{} r<N> as <type>;`", statement.first())),
+    }
+}
+
+/// Parses `output <operand> as <type>`, returning the operand and the type tokens.
+fn typed_operand(statement: &Statement) -> Result<(Operand, &[Token])> {
+    match statement.rest() {
+        [operand_token, as_, ty @ ..] if as_.is("as") && !ty.is_empty() => Ok((operand(operand_token)?, ty)),
+        _ => error(statement.span, format!("expected `{} <operand> as <type>;`", statement.first())),
+    }
+}
+
+/// The parts of `<op> <operands> [into <destinations>] [as <type>]`; `ty` is
+/// `None` without an `as`, and empty for an `as` with nothing after it.
+struct Parts<'a> {
+    operands: &'a [Token],
+    destinations: &'a [Token],
+    ty: Option<&'a [Token]>,
+}
+
+fn parts(statement: &Statement) -> Parts<'_> {
+    let rest = statement.rest();
+    let into = rest.iter().position(|token| token.is("into")).unwrap_or(rest.len());
+    let as_ = rest.iter().position(|token| token.is("as")).map(|as_| as_.max(into));
+    Parts {
+        operands: &rest[..into],
+        destinations: rest.get(into + 1..as_.unwrap_or(rest.len())).unwrap_or_default(),
+        ty: as_.map(|as_| rest.get(as_ + 1..).unwrap_or_default()),
+    }
+}
+
+fn operands<const N: usize>(statement: &Statement, tokens: &[Token]) -> Result<[Operand; N]> {
+    let operands = tokens.iter().map(operand).collect::<Result<Vec<_>>>()?;
+    operands.try_into().or_else(|operands: Vec<_>| {
+        error(statement.span, format!("`{}` takes {N} operand(s), found {}", statement.first(), operands.len()))
+    })
+}
+
+fn single_destination(statement: &Statement, tokens: &[Token]) -> Result<Register> {
+    match tokens {
+        [token] => destination(token),
+        _ => error(statement.span, format!("`{}` writes exactly one destination: `into r<N>`", statement.first())),
+    }
+}
+
+fn no_type(statement: &Statement, ty: Option<&[Token]>) -> Result<()> {
+    match ty {
+        Some(ty) => {
+            let span = ty.first().map_or(statement.span, |token| token.span);
+            error(span, format!("`{}` does not take an `as` type", statement.first()))
+        }
+        None => Ok(()),
+    }
+}
+
+fn optional_type(statement: &Statement, ty: Option<&[Token]>) -> Result<Option<PlaintextType>> {
+    ty.map(|ty| plaintext_type(ty, statement.span)).transpose()
+}
+
+fn instruction(statement: &Statement) -> Result<Instruction> {
+    Ok(Instruction { operation: operation(statement)?, span: statement.span })
+}
+
+fn operation(statement: &Statement) -> Result<Operation> {
+    let opcode = statement.first();
+    let parts = parts(statement);
+
+    if let Some(op) = UnaryOp::from_mnemonic(opcode) {
+        no_type(statement, parts.ty)?;
+        let [operand] = operands(statement, parts.operands)?;
+        return Ok(Operation::Unary { op, operand, destination: single_destination(statement, parts.destinations)? });
+    }
+    if let Some(op) = BinaryOp::from_mnemonic(opcode) {
+        no_type(statement, parts.ty)?;
+        let operands = operands(statement, parts.operands)?;
+        return Ok(Operation::Binary { op, operands, destination: single_destination(statement, parts.destinations)? });
+    }
+    if let Some(op) = AssertOp::from_mnemonic(opcode) {
+        if let Some(token) = statement.rest().iter().find(|token| token.is("into")) {
+            return error(token.span, format!("`{opcode}` has no destination"));
+        }
+        return Ok(Operation::Assert { op, operands: operands(statement, parts.operands)? });
+    }
+    if let Some(op) = HashOp::from_mnemonic(opcode) {
+        let [operand] = operands(statement, parts.operands)?;
+        let destination = single_destination(statement, parts.destinations)?;
+        return Ok(Operation::Hash { op, operand, destination, ty: optional_type(statement, parts.ty)? });
+    }
+    if let Some(op) = CommitOp::from_mnemonic(opcode) {
+        let operands = operands(statement, parts.operands)?;
+        let destination = single_destination(statement, parts.destinations)?;
+        return Ok(Operation::Commit { op, operands, destination, ty: optional_type(statement, parts.ty)? });
+    }
+    match opcode {
+        "ternary" => {
+            no_type(statement, parts.ty)?;
+            let operands = operands(statement, parts.operands)?;
+            Ok(Operation::Ternary { operands, destination: single_destination(statement, parts.destinations)? })
+        }
+        "sign.verify" => {
+            no_type(statement, parts.ty)?;
+            let operands = operands(statement, parts.operands)?;
+            Ok(Operation::SignVerify { operands, destination: single_destination(statement, parts.destinations)? })
+        }
+        "cast" | "cast.lossy" => {
+            let Some(ty) = parts.ty.filter(|ty| !ty.is_empty()) else {
+                return error(statement.span, format!("`{opcode}` needs a destination type: `as <type>`"));
+            };
+            let operands = parts.operands.iter().map(operand).collect::<Result<Vec<_>>>()?;
+            if operands.is_empty() {
+                return error(statement.span, format!("`{opcode}` needs at least one operand"));
+            }
+            Ok(Operation::Cast {
+                lossy: opcode == "cast.lossy",
+                operands,
+                destination: single_destination(statement, parts.destinations)?,
+                ty: register_type(ty, statement.span)?,
+            })
+        }
+        "call" => {
+            no_type(statement, parts.ty)?;
+            let (target, rest) = match parts.operands {
+                [program, slash, name, rest @ ..] if slash.is("/") => {
+                    let (text, span) = joined(&[program.clone(), slash.clone(), name.clone()], statement.span)?;
+                    (CallTarget::External(locator_text(&text, span)?), rest)
+                }
+                [name, rest @ ..] => (CallTarget::Local(identifier(name)?), rest),
+                [] => return error(statement.span, "`call` needs a target"),
+            };
+            let operands = rest.iter().map(operand).collect::<Result<_>>()?;
+            let destinations = parts.destinations.iter().map(destination).collect::<Result<_>>()?;
+            Ok(Operation::Call { target, operands, destinations })
+        }
+        _ => error(statement.tokens[0].span, format!("unknown opcode `{opcode}`")),
+    }
+}
+
+/// Parses `name[key]` from the start of `tokens`, returning the access and
+/// the remaining tokens.
+fn mapping_access<'a>(statement: &Statement, tokens: &'a [Token]) -> Result<(MappingAccess, &'a [Token])> {
+    match tokens {
+        [mapping, open, key, close, rest @ ..] if open.is("[") && close.is("]") => {
+            Ok((MappingAccess { mapping: identifier(mapping)?, key: operand(key)? }, rest))
+        }
+        _ => error(statement.span, "expected a mapping access like `account[r0]`"),
+    }
+}
+
+fn command(statement: &Statement) -> Result<Command> {
+    let rest = statement.rest();
+    let into = |tokens: &[Token]| -> Result<Register> {
+        match tokens {
+            [into, target] if into.is("into") => destination(target),
+            _ => error(statement.span, format!("expected `into r<N>` after `{}`", statement.first())),
+        }
+    };
+    let kind = match statement.first() {
+        "get" => {
+            let (access, rest) = mapping_access(statement, rest)?;
+            CommandKind::Get { access, destination: into(rest)? }
+        }
+        "get.or_use" => {
+            let (access, rest) = mapping_access(statement, rest)?;
+            let Some((default, rest)) = rest.split_first() else {
+                return error(statement.span, "`get.or_use` needs a default value");
+            };
+            CommandKind::GetOrUse { access, default: operand(default)?, destination: into(rest)? }
+        }
+        "set" => match rest {
+            [value, into, access @ ..] if into.is("into") => {
+                let (access, rest) = mapping_access(statement, access)?;
+                if let Some(token) = rest.first() {
+                    return error(token.span, "unexpected token after the mapping access");
+                }
+                CommandKind::Set { value: operand(value)?, access }
+            }
+            _ => return error(statement.span, "expected `set <value> into <mapping>[<key>];`"),
+        },
+        "contains" => {
+            let (access, rest) = mapping_access(statement, rest)?;
+            CommandKind::Contains { access, destination: into(rest)? }
+        }
+        "remove" => {
+            let (access, rest) = mapping_access(statement, rest)?;
+            if let Some(token) = rest.first() {
+                return error(token.span, "unexpected token after the mapping access");
+            }
+            CommandKind::Remove { access }
+        }
+        "rand.chacha" => {
+            let parts = parts(statement);
+            let operands = parts.operands.iter().map(operand).collect::<Result<_>>()?;
+            let destination = single_destination(statement, parts.destinations)?;
+            let ty = match parts.ty.filter(|ty| !ty.is_empty()).map(|ty| plaintext_type(ty, statement.span)) {
+                Some(Ok(PlaintextType::Literal(ty))) => ty,
+                _ => return error(statement.span, "`rand.chacha` needs a literal type: `as <type>`"),
+            };
+            CommandKind::RandChaCha { operands, destination, ty }
+        }
+        "position" => match rest {
+            [label] => CommandKind::Position { label: identifier(label)? },
+            _ => return error(statement.span, "expected `position <label>;`"),
+        },
+        "branch.eq" | "branch.neq" => match rest {
+            [first, second, to, label] if to.is("to") => {
+                let operands = [operand(first)?, operand(second)?];
+                let label = identifier(label)?;
+                if statement.first() == "branch.eq" {
+                    CommandKind::BranchEq { operands, label }
+                } else {
+                    CommandKind::BranchNeq { operands, label }
+                }
+            }
+            _ => return error(statement.span, format!("expected `{} <a> <b> to <label>;`", statement.first())),
+        },
+        _ => CommandKind::Instruction(operation(statement)?),
+    };
+    Ok(Command { kind, span: statement.span })
+}
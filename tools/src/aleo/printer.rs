@@ -0,0 +1,336 @@
+//! Prints the syntax tree back to Aleo instructions.
+//!
+//! The layout is the one `leo build` emits: four-space indentation, one blank
+//! line after the `program` header and two between declarations, so a
+//! compiled `build/main.aleo` re-serializes byte for byte.
+
+use std::fmt::{self, Display, Formatter};
+
+use super::ast::*;
+
+const INDENT: &str = "    ";
+
+fn join<T: Display>(f: &mut Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(" ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Display for ProgramId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.name, self.network)
+    }
+}
+
+impl Display for Locator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.program, self.resource)
+    }
+}
+
+impl Display for LiteralType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for PlaintextType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaintextType::Literal(ty) => write!(f, "{ty}"),
+            PlaintextType::Struct(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl Display for RegisterType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterType::Plaintext(ty) => write!(f, "{ty}"),
+            RegisterType::Record(name) => write!(f, "{name}.record"),
+            RegisterType::ExternalRecord(locator) => write!(f, "{locator}.record"),
+        }
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Visibility::Constant => "constant",
+            Visibility::Public => "public",
+            Visibility::Private => "private",
+        })
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueType::Plaintext(ty, visibility) => write!(f, "{ty}.{visibility}"),
+            ValueType::Record(name) => write!(f, "{name}.record"),
+            ValueType::ExternalRecord(locator) => write!(f, "{locator}.record"),
+        }
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "r{}", self.index)?;
+        for member in &self.members {
+            write!(f, ".{member}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Literal(literal) => write!(f, "{literal}"),
+            Operand::Register(register) => write!(f, "{register}"),
+            Operand::ProgramId(id) => write!(f, "{id}"),
+            Operand::Caller => f.write_str("self.caller"),
+            Operand::Signer => f.write_str("self.signer"),
+            Operand::BlockHeight => f.write_str("block.height"),
+        }
+    }
+}
+
+impl Display for CallTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CallTarget::Local(name) => write!(f, "{name}"),
+            CallTarget::External(locator) => write!(f, "{locator}"),
+        }
+    }
+}
+
+impl Display for Operation {
+    /// Prints the operation without its terminating `;`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Unary { op, operand, destination } => write!(f, "{op} {operand} into {destination}"),
+            Operation::Binary { op, operands: [a, b], destination } => write!(f, "{op} {a} {b} into {destination}"),
+            Operation::Ternary { operands: [a, b, c], destination } => {
+                write!(f, "ternary {a} {b} {c} into {destination}")
+            }
+            Operation::Assert { op, operands: [a, b] } => write!(f, "{op} {a} {b}"),
+            Operation::Hash { op, operand, destination, ty } => {
+                write!(f, "{op} {operand} into {destination}")?;
+                if let Some(ty) = ty {
+                    write!(f, " as {ty}")?;
+                }
+                Ok(())
+            }
+            Operation::Commit { op, operands: [a, b], destination, ty } => {
+                write!(f, "{op} {a} {b} into {destination}")?;
+                if let Some(ty) = ty {
+                    write!(f, " as {ty}")?;
+                }
+                Ok(())
+            }
+            Operation::Cast { lossy, operands, destination, ty } => {
+                f.write_str(if *lossy { "cast.lossy " } else { "cast " })?;
+                join(f, operands)?;
+                write!(f, " into {destination} as {ty}")
+            }
+            Operation::Call { target, operands, destinations } => {
+                write!(f, "call {target}")?;
+                for operand in operands {
+                    write!(f, " {operand}")?;
+                }
+                if !destinations.is_empty() {
+                    f.write_str(" into ")?;
+                    join(f, destinations)?;
+                }
+                Ok(())
+            }
+            Operation::SignVerify { operands: [a, b, c], destination } => {
+                write!(f, "sign.verify {a} {b} {c} into {destination}")
+            }
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{};", self.operation)
+    }
+}
+
+impl Display for MappingAccess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.mapping, self.key)
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CommandKind::Instruction(operation) => write!(f, "{operation}"),
+            CommandKind::Get { access, destination } => write!(f, "get {access} into {destination}"),
+            CommandKind::GetOrUse { access, default, destination } => {
+                write!(f, "get.or_use {access} {default} into {destination}")
+            }
+            CommandKind::Set { value, access } => write!(f, "set {value} into {access}"),
+            CommandKind::Contains { access, destination } => write!(f, "contains {access} into {destination}"),
+            CommandKind::Remove { access } => write!(f, "remove {access}"),
+            CommandKind::RandChaCha { operands, destination, ty } => {
+                f.write_str("rand.chacha")?;
+                for operand in operands {
+                    write!(f, " {operand}")?;
+                }
+                write!(f, " into {destination} as {ty}")
+            }
+            CommandKind::Position { label } => write!(f, "position {label}"),
+            CommandKind::BranchEq { operands: [a, b], label } => write!(f, "branch.eq {a} {b} to {label}"),
+            CommandKind::BranchNeq { operands: [a, b], label } => write!(f, "branch.neq {a} {b} to {label}"),
+        }?;
+        f.write_str(";")
+    }
+}
+
+impl<T: Display> Display for Input<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "input {} as {};", self.register, self.ty)
+    }
+}
+
+impl<T: Display> Display for Output<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "output {} as {};", self.operand, self.ty)
+    }
+}
+
+impl Display for Struct {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "struct {}:", self.name)?;
+        for member in &self.members {
+            writeln!(f, "{INDENT}{} as {};", member.name, member.ty)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "record {}:", self.name)?;
+        for member in &self.members {
+            writeln!(f, "{INDENT}{} as {}.{};", member.name, member.ty, member.visibility)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Mapping {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "mapping {}:", self.name)?;
+        for (keyword, entry) in [("key", &self.key), ("value", &self.value)] {
+            write!(f, "{INDENT}{keyword}")?;
+            if let Some(label) = &entry.label {
+                write!(f, " {label}")?;
+            }
+            writeln!(f, " as {}.public;", entry.ty)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Closure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "closure {}:", self.name)?;
+        for input in &self.inputs {
+            writeln!(f, "{INDENT}{input}")?;
+        }
+        for instruction in &self.instructions {
+            writeln!(f, "{INDENT}{instruction}")?;
+        }
+        for output in &self.outputs {
+            writeln!(f, "{INDENT}{output}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Finalize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "finalize {}:", self.name)?;
+        for input in &self.inputs {
+            writeln!(f, "{INDENT}input {} as {}.public;", input.register, input.ty)?;
+        }
+        for command in &self.commands {
+            writeln!(f, "{INDENT}{command}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "function {}:", self.name)?;
+        for input in &self.inputs {
+            writeln!(f, "{INDENT}{input}")?;
+        }
+        for instruction in &self.instructions {
+            writeln!(f, "{INDENT}{instruction}")?;
+        }
+        for output in &self.outputs {
+            writeln!(f, "{INDENT}{output}")?;
+        }
+        if let Some((call, finalize)) = &self.finalize {
+            f.write_str(INDENT)?;
+            f.write_str("finalize")?;
+            for operand in &call.operands {
+                write!(f, " {operand}")?;
+            }
+            writeln!(f, ";")?;
+            write!(f, "\n{finalize}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Declaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Declaration::Struct(struct_) => write!(f, "{struct_}"),
+            Declaration::Record(record) => write!(f, "{record}"),
+            Declaration::Mapping(mapping) => write!(f, "{mapping}"),
+            Declaration::Closure(closure) => write!(f, "{closure}"),
+            Declaration::Function(function) => write!(f, "{function}"),
+        }
+    }
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for import in &self.imports {
+            writeln!(f, "import {};", import.program)?;
+        }
+        if !self.imports.is_empty() {
+            writeln!(f)?;
+        }
+        writeln!(f, "program {};", self.id)?;
+        for (index, declaration) in self.declarations.iter().enumerate() {
+            f.write_str(if index == 0 { "\n" } else { "\n\n" })?;
+            write!(f, "{declaration}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Human-readable token amounts.
+//!
+//! Balances are integers of base units; the program's `metadata` function
+//! says how many of its digits are decimals. With six, `12500000u128` is
+//! `12.5`:
+//!
+//! ```
+//! use workshop::amount::{format_amount, parse_amount};
+//!
+//! assert_eq!(format_amount(12_500_000, 6), "12.5");
+//! assert_eq!(parse_amount("12.5", 6), Ok(12_500_000));
+//! ```
+
+use thiserror::Error;
+
+use crate::aleo::Program;
+use crate::interpreter::{Interpreter, Literal, Plaintext, Value, ZERO_ADDRESS};
+
+/// The function returning a program's [`TokenMetadata`].
+pub const METADATA_FUNCTION: &str = "metadata";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("the amount is empty")]
+    Empty,
+    #[error("`{character}` at character {} is not a digit", index + 1)]
+    Character { index: usize, character: char },
+    #[error("`{text}` has {found} decimal places, but the token has {decimals}")]
+    Precision { text: String, found: usize, decimals: u8 },
+    #[error("`{0}` does not fit in a u128 of base units")]
+    Overflow(String),
+    #[error("`{text}` is in {found}, not {expected}")]
+    Symbol { text: String, found: String, expected: String },
+    #[error("the program has no `{METADATA_FUNCTION}` function")]
+    NoMetadata,
+    #[error("invalid token metadata: {0}")]
+    Metadata(String),
+}
+
+/// The name, symbol and decimals of a token, as returned by its program's
+/// `metadata` function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl TokenMetadata {
+    /// Runs the program's `metadata` function and decodes its output.
+    pub fn from_program(program: &Program) -> Result<Self, AmountError> {
+        program.function(METADATA_FUNCTION).ok_or(AmountError::NoMetadata)?;
+        let mut interpreter = Interpreter::new(program, ZERO_ADDRESS);
+        let outputs = interpreter
+            .execute(METADATA_FUNCTION, Vec::new())
+            .map_err(|error| AmountError::Metadata(error.to_string()))?;
+        match outputs.as_slice() {
+            [Value::Plaintext(plaintext)] => Self::from_plaintext(plaintext),
+            _ => Err(AmountError::Metadata(format!("`{METADATA_FUNCTION}` must return one struct"))),
+        }
+    }
+
+    /// Decodes a `TokenMetadata { name, symbol, decimals }` struct.
+    pub fn from_plaintext(plaintext: &Plaintext) -> Result<Self, AmountError> {
+        let member = |name: &str| match plaintext.member(name) {
+            Some(Plaintext::Literal(Literal::Integer(integer))) => Ok(integer.unsigned()),
+            _ => Err(AmountError::Metadata(format!("no integer member `{name}`"))),
+        };
+        let text = |name: &str| {
+            unpack_ascii(member(name)?).ok_or_else(|| AmountError::Metadata(format!("`{name}` is not packed ASCII")))
+        };
+        let decimals = member("decimals")?;
+        Ok(Self {
+            name: text("name")?,
+            symbol: text("symbol")?,
+            decimals: u8::try_from(decimals)
+                .map_err(|_| AmountError::Metadata(format!("{decimals} decimals is too many")))?,
+        })
+    }
+
+    /// Formats base units with the symbol, e.g. `12.5 WST`.
+    pub fn format(&self, units: u128) -> String {
+        format!("{} {}", format_amount(units, self.decimals), self.symbol)
+    }
+
+    /// Parses `12.5` or `12.5 WST` into base units.
+    pub fn parse(&self, text: &str) -> Result<u128, AmountError> {
+        let trimmed = text.trim();
+        let number = match trimmed.rsplit_once(char::is_whitespace) {
+            Some((number, symbol)) if symbol == self.symbol => number,
+            Some((_, symbol)) => {
+                return Err(AmountError::Symbol {
+                    text: trimmed.to_string(),
+                    found: symbol.to_string(),
+                    expected: self.symbol.clone(),
+                })
+            }
+            None => trimmed,
+        };
+        parse_amount(number.trim_end(), self.decimals)
+    }
+}
+
+/// Formats base units as a decimal number without trailing zeros, e.g.
+/// `12500000` with 6 decimals as `12.5`.
+pub fn format_amount(units: u128, decimals: u8) -> String {
+    let digits = format!("{units:0>width$}", width = usize::from(decimals) + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - usize::from(decimals));
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// Parses a decimal number such as `12.5`, `0.000001` or `1_000` into base
+/// units. More decimal places than the token has is an error rather than
+/// silently rounding.
+pub fn parse_amount(text: &str, decimals: u8) -> Result<u128, AmountError> {
+    if text.is_empty() {
+        return Err(AmountError::Empty);
+    }
+    let mut units: u128 = 0;
+    let mut places = None;
+    for (index, character) in text.chars().enumerate() {
+        match character {
+            '0'..='9' => {
+                units = units
+                    .checked_mul(10)
+                    .and_then(|units| units.checked_add(u128::from(character as u8 - b'0')))
+                    .ok_or_else(|| AmountError::Overflow(text.to_string()))?;
+                if let Some(places) = places.as_mut() {
+                    *places += 1;
+                }
+            }
+            '.' if places.is_none() && index > 0 => places = Some(0),
+            '_' if index > 0 && places.is_none() => {}
+            character => return Err(AmountError::Character { index, character }),
+        }
+    }
+    let places = places.unwrap_or(0);
+    if places > usize::from(decimals) {
+        return Err(AmountError::Precision { text: text.to_string(), found: places, decimals });
+    }
+    10u128
+        .checked_pow(u32::from(decimals) - places as u32)
+        .and_then(|scale| units.checked_mul(scale))
+        .ok_or_else(|| AmountError::Overflow(text.to_string()))
+}
+
+/// Packs up to 16 ASCII characters into a u128, big-endian, the way the
+/// program stores its `name` and `symbol`.
+pub fn pack_ascii(text: &str) -> Option<u128> {
+    if text.len() > 16 || !text.is_ascii() {
+        return None;
+    }
+    Some(text.bytes().fold(0, |packed, byte| (packed << 8) | u128::from(byte)))
+}
+
+/// The inverse of [`pack_ascii`].
+pub fn unpack_ascii(packed: u128) -> Option<String> {
+    let bytes: Vec<u8> = packed.to_be_bytes().into_iter().skip_while(|&byte| byte == 0).collect();
+    String::from_utf8(bytes).ok().filter(|text| text.is_ascii())
+}
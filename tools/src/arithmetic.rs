@@ -0,0 +1,399 @@
+//! Finding integer arithmetic that can halt a program.
+//!
+//! Checked opcodes such as `add` and `sub` halt instead of wrapping, and a
+//! halt while proving surfaces as an opaque VM error. [`lint`] lists every
+//! such instruction that no earlier assertion rules out, and [`explain`] turns
+//! a halt found by the [`interpreter`](crate::interpreter) into a diagnostic
+//! phrased in terms of the values involved, e.g. `insufficient balance:
+//! record holds 100, requested 250`.
+
+use std::collections::BTreeMap;
+
+use crate::aleo::{
+    self, AssertOp, BinaryOp, CallTarget, CommandKind, LiteralType, Operand, Operation, PlaintextType, Program,
+    RegisterType, UnaryOp, ValueType,
+};
+use crate::diagnostic::{Diagnostic, Span};
+use crate::inputs;
+use crate::interpreter::{InterpretError, Literal, Value};
+
+/// Warns about every checked integer operation that can halt and is not
+/// guarded by an earlier assertion, such as `sub a b` after `gte a b into r;
+/// assert.eq r true;`.
+pub fn lint(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    walk(program, |scope, operation, span| {
+        if let Some((message, help)) = scope.hazard(operation) {
+            let diagnostic = Diagnostic::warning(span, message);
+            diagnostics.push(match help {
+                Some(help) => diagnostic.with_help(help),
+                None => diagnostic,
+            });
+        }
+    });
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+    diagnostics
+}
+
+/// Describes an interpreter error. Arithmetic halts are explained in terms of
+/// their operands, and an underflowing `sub` from a record member reads as an
+/// insufficient balance; other errors are passed through.
+pub fn explain(program: &Program, error: &InterpretError) -> Diagnostic {
+    let InterpretError::Arithmetic { values, message, span } = error else {
+        return error.clone().into();
+    };
+    let mut explained = None;
+    walk(program, |scope, operation, at| {
+        if at == *span {
+            explained = Some(scope.explain(operation, values, message));
+        }
+    });
+    match explained {
+        Some((message, Some(help))) => Diagnostic::error(*span, message).with_help(help),
+        Some((message, None)) => Diagnostic::error(*span, message),
+        None => error.clone().into(),
+    }
+}
+
+/// The values of an input file section, in order.
+pub fn section_inputs(section: &inputs::Section) -> Result<Vec<Value>, Diagnostic> {
+    section
+        .inputs
+        .iter()
+        .map(|input| {
+            Value::from_input(&input.value)
+                .map_err(|message| Diagnostic::error(input.value.span(), format!("`{}`: {message}", input.name.name)))
+        })
+        .collect()
+}
+
+/// The caller to run with: the owner of the first record input, so the
+/// records can be spent, or `default` if there are none.
+pub fn caller_for<'a>(inputs: &'a [Value], default: &'a str) -> &'a str {
+    inputs
+        .iter()
+        .find_map(|value| match value {
+            Value::Record(record) => record.owner(),
+            Value::Plaintext(_) => None,
+        })
+        .unwrap_or(default)
+}
+
+/// The address the program's access checks compare `self.caller` with, as in
+/// `assert.eq self.caller aleo1...;`, or `None` if no function has one or
+/// they name different addresses.
+pub fn admin(program: &Program) -> Option<String> {
+    let mut admins = program.functions().flat_map(|function| &function.instructions).filter_map(|instruction| {
+        match &instruction.operation {
+            Operation::Assert {
+                op: AssertOp::Eq,
+                operands: [Operand::Caller, Operand::Literal(literal)] | [Operand::Literal(literal), Operand::Caller],
+            } if literal.ty == LiteralType::Address => Some(literal.text.as_str()),
+            _ => None,
+        }
+    });
+    let first = admins.next()?;
+    admins.all(|admin| admin == first).then(|| first.to_string())
+}
+
+/// The static type of a register.
+#[derive(Clone, Debug)]
+enum Ty {
+    Plaintext(PlaintextType),
+    Record(String),
+}
+
+impl Ty {
+    fn literal(ty: LiteralType) -> Self {
+        Ty::Plaintext(PlaintextType::Literal(ty))
+    }
+
+    fn register(ty: &RegisterType) -> Option<Self> {
+        match ty {
+            RegisterType::Plaintext(ty) => Some(Ty::Plaintext(ty.clone())),
+            RegisterType::Record(name) => Some(Ty::Record(name.name.clone())),
+            RegisterType::ExternalRecord(_) => None,
+        }
+    }
+}
+
+/// What is known just before an instruction of one body: the types of the
+/// registers and the comparisons that assertions have established.
+struct Scope<'a> {
+    program: &'a Program,
+    types: BTreeMap<u32, Ty>,
+    /// The comparison each boolean register holds, e.g. `r4` for `gte r2 r1 into r4`.
+    comparisons: BTreeMap<u32, (BinaryOp, String, String)>,
+    /// Pairs `(a, b)` for which `a >= b` has been asserted.
+    at_least: Vec<(String, String)>,
+    /// Operands asserted to be non-zero.
+    nonzero: Vec<String>,
+}
+
+/// Calls `visit` with every instruction of every closure, function and
+/// finalize block, together with the scope just before it.
+fn walk(program: &Program, mut visit: impl FnMut(&Scope, &Operation, Span)) {
+    let new_scope = |types: BTreeMap<u32, Ty>| Scope {
+        program,
+        types,
+        comparisons: BTreeMap::new(),
+        at_least: Vec::new(),
+        nonzero: Vec::new(),
+    };
+
+    for closure in program.closures() {
+        let types = closure.inputs.iter().filter_map(|input| Some((input.register.index, Ty::register(&input.ty)?)));
+        let mut scope = new_scope(types.collect());
+        for instruction in &closure.instructions {
+            visit(&scope, &instruction.operation, instruction.span);
+            scope.assign(&instruction.operation);
+        }
+    }
+
+    for function in program.functions() {
+        let types = function.inputs.iter().filter_map(|input| {
+            let ty = match &input.ty {
+                ValueType::Plaintext(ty, _) => Ty::Plaintext(ty.clone()),
+                ValueType::Record(name) => Ty::Record(name.name.clone()),
+                ValueType::ExternalRecord(_) => return None,
+            };
+            Some((input.register.index, ty))
+        });
+        let mut scope = new_scope(types.collect());
+        for instruction in &function.instructions {
+            visit(&scope, &instruction.operation, instruction.span);
+            scope.assign(&instruction.operation);
+        }
+
+        let Some((_, finalize)) = &function.finalize else { continue };
+        let types = finalize.inputs.iter().map(|input| (input.register.index, Ty::Plaintext(input.ty.clone())));
+        let mut scope = new_scope(types.collect());
+        for command in &finalize.commands {
+            match &command.kind {
+                CommandKind::Instruction(operation) => {
+                    visit(&scope, operation, command.span);
+                    scope.assign(operation);
+                }
+                CommandKind::Get { access, destination } | CommandKind::GetOrUse { access, destination, .. } => {
+                    if let Some(mapping) = program.mapping(&access.mapping.name) {
+                        scope.types.insert(destination.index, Ty::Plaintext(mapping.value.ty.clone()));
+                    }
+                }
+                CommandKind::Contains { destination, .. } => {
+                    scope.types.insert(destination.index, Ty::literal(LiteralType::Boolean));
+                }
+                CommandKind::RandChaCha { destination, ty, .. } => {
+                    scope.types.insert(destination.index, Ty::literal(*ty));
+                }
+                CommandKind::Set { .. }
+                | CommandKind::Remove { .. }
+                | CommandKind::Position { .. }
+                | CommandKind::BranchEq { .. }
+                | CommandKind::BranchNeq { .. } => {}
+            }
+        }
+    }
+}
+
+fn is_comparison(op: BinaryOp) -> bool {
+    matches!(op, BinaryOp::GreaterThan | BinaryOp::GreaterThanOrEqual | BinaryOp::LessThan | BinaryOp::LessThanOrEqual)
+}
+
+/// Whether an operand, as written, is an integer zero such as `0u32`.
+fn is_zero(operand: &str) -> bool {
+    aleo::Literal::parse(operand)
+        .is_some_and(|literal| literal.ty.is_integer() && literal.value_text().chars().all(|c| c == '0' || c == '_'))
+}
+
+/// A number without its type suffix, e.g. `100` for `100u32`.
+fn number(literal: &Literal) -> String {
+    match literal {
+        Literal::Integer(integer) if integer.ty().is_signed() => integer.signed().to_string(),
+        Literal::Integer(integer) => integer.unsigned().to_string(),
+        other => other.to_string(),
+    }
+}
+
+impl Scope<'_> {
+    fn operand_type(&self, operand: &Operand) -> Option<Ty> {
+        match operand {
+            Operand::Literal(literal) => Some(Ty::literal(literal.ty)),
+            Operand::Caller | Operand::Signer | Operand::ProgramId(_) => Some(Ty::literal(LiteralType::Address)),
+            Operand::BlockHeight => Some(Ty::literal(LiteralType::U32)),
+            Operand::Register(register) => {
+                let mut ty = self.types.get(&register.index)?.clone();
+                for member in &register.members {
+                    let member_ty = match &ty {
+                        Ty::Record(name) => self.program.record(name)?.member(&member.name)?.ty.clone(),
+                        Ty::Plaintext(PlaintextType::Struct(name)) => {
+                            let declaration = self.program.struct_(&name.name)?;
+                            declaration.members.iter().find(|m| m.name.name == member.name)?.ty.clone()
+                        }
+                        Ty::Plaintext(PlaintextType::Literal(_)) => return None,
+                    };
+                    ty = Ty::Plaintext(member_ty);
+                }
+                Some(ty)
+            }
+        }
+    }
+
+    fn integer_type(&self, operand: &Operand) -> Option<LiteralType> {
+        match self.operand_type(operand)? {
+            Ty::Plaintext(PlaintextType::Literal(ty)) if ty.is_integer() => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Records the effect of an operation on register types and facts.
+    fn assign(&mut self, operation: &Operation) {
+        let ty = match operation {
+            Operation::Unary { operand, .. } => self.operand_type(operand),
+            Operation::Binary { op, operands: [a, b], destination } => {
+                if is_comparison(*op) {
+                    self.comparisons.insert(destination.index, (*op, a.to_string(), b.to_string()));
+                }
+                match op {
+                    _ if is_comparison(*op) => Some(Ty::literal(LiteralType::Boolean)),
+                    BinaryOp::IsEq | BinaryOp::IsNeq => Some(Ty::literal(LiteralType::Boolean)),
+                    _ => self.operand_type(a),
+                }
+            }
+            Operation::Ternary { operands: [_, a, _], .. } => self.operand_type(a),
+            Operation::Assert { op, operands: [a, b] } => {
+                self.assume(*op, a, b);
+                None
+            }
+            Operation::Hash { ty, .. } | Operation::Commit { ty, .. } => {
+                Some(Ty::Plaintext(ty.clone().unwrap_or(PlaintextType::Literal(LiteralType::Field))))
+            }
+            Operation::Cast { ty, .. } => Ty::register(ty),
+            Operation::Call { target: CallTarget::Local(name), destinations, .. } => {
+                if let Some(closure) = self.program.closure(&name.name) {
+                    for (output, destination) in closure.outputs.iter().zip(destinations) {
+                        if let Some(ty) = Ty::register(&output.ty) {
+                            self.types.insert(destination.index, ty);
+                        }
+                    }
+                }
+                None
+            }
+            Operation::Call { target: CallTarget::External(_), .. } => None,
+            Operation::SignVerify { .. } => Some(Ty::literal(LiteralType::Boolean)),
+        };
+        if let (Some(ty), [destination]) = (ty, operation.destinations().as_slice()) {
+            self.types.insert(destination.index, ty);
+        }
+    }
+
+    /// Learns from `assert.eq`/`assert.neq`: either that a comparison holds
+    /// or fails, or that an operand is non-zero.
+    fn assume(&mut self, op: AssertOp, a: &Operand, b: &Operand) {
+        if op == AssertOp::Neq {
+            for (operand, other) in [(a, b), (b, a)] {
+                if is_zero(&other.to_string()) {
+                    self.nonzero.push(operand.to_string());
+                }
+            }
+        }
+        let (register, expected) = match (a, b) {
+            (Operand::Register(register), Operand::Literal(literal))
+            | (Operand::Literal(literal), Operand::Register(register))
+                if literal.ty == LiteralType::Boolean && register.members.is_empty() =>
+            {
+                (register.index, literal.text == "true")
+            }
+            _ => return,
+        };
+        let Some((comparison, x, y)) = self.comparisons.get(&register).cloned() else { return };
+        let holds = expected == (op == AssertOp::Eq);
+        // Normalize to `larger >= smaller`; a failed strict comparison still
+        // gives the non-strict converse.
+        let (larger, smaller) = match (comparison, holds) {
+            (BinaryOp::GreaterThanOrEqual | BinaryOp::GreaterThan, true)
+            | (BinaryOp::LessThanOrEqual | BinaryOp::LessThan, false) => (x, y),
+            _ => (y, x),
+        };
+        let strict = matches!((comparison, holds), (BinaryOp::GreaterThan | BinaryOp::LessThan, true));
+        if strict && is_zero(&smaller) {
+            self.nonzero.push(larger.clone());
+        }
+        self.at_least.push((larger, smaller));
+    }
+
+    /// The ways a checked operation can halt, unless assertions rule them
+    /// out, with advice on avoiding them.
+    fn hazard(&self, operation: &Operation) -> Option<(String, Option<String>)> {
+        match operation {
+            Operation::Binary { op, operands: [a, b], .. } if op.is_checked() => {
+                let ty = self.integer_type(a)?;
+                let (a, b) = (a.to_string(), b.to_string());
+                let range = if ty.is_signed() {
+                    format!("outside the range of {ty}")
+                } else {
+                    format!("above the largest {ty}")
+                };
+                let condition = match op {
+                    BinaryOp::Add => format!("the sum is {range}"),
+                    BinaryOp::Sub if self.at_least.contains(&(a.clone(), b.clone())) => return None,
+                    BinaryOp::Sub if ty.is_signed() => format!("the difference is {range}"),
+                    BinaryOp::Sub => format!("{b} is greater than {a}"),
+                    BinaryOp::Mul => format!("the product is {range}"),
+                    BinaryOp::Pow => format!("the power is {range}"),
+                    BinaryOp::Div | BinaryOp::Rem if ty.is_signed() => {
+                        format!("{b} is zero, or {a} is the smallest {ty} and {b} is -1")
+                    }
+                    BinaryOp::Div | BinaryOp::Rem if self.nonzero.contains(&b) => return None,
+                    BinaryOp::Div | BinaryOp::Rem => format!("{b} is zero"),
+                    _ => {
+                        let width = ty.name().trim_start_matches(['i', 'u']);
+                        format!("{b} is {width} or more")
+                    }
+                };
+                Some((format!("`{op} {a} {b}` halts if {condition}"), self.advice(operation)))
+            }
+            Operation::Unary { op: op @ (UnaryOp::Abs | UnaryOp::Neg), operand, .. } => {
+                let ty = self.integer_type(operand).filter(|ty| ty.is_signed())?;
+                Some((format!("`{op} {operand}` halts if {operand} is the smallest {ty}"), self.advice(operation)))
+            }
+            _ => None,
+        }
+    }
+
+    fn advice(&self, operation: &Operation) -> Option<String> {
+        match operation {
+            Operation::Binary { op: BinaryOp::Sub, operands: [a, b], .. } => Some(format!(
+                "assert `{a} >= {b}` first (`assert(x >= y);` in Leo) to fail with a clear error, or use `sub.w` if wrapping is intended"
+            )),
+            Operation::Binary { op: BinaryOp::Div | BinaryOp::Rem, operands: [_, b], .. } => {
+                Some(format!("assert `{b} != 0` first (`assert_neq(y, 0u32);` in Leo) to fail with a clear error"))
+            }
+            Operation::Binary { op, .. } => Some(format!("use `{op}.w` if wrapping is intended")),
+            Operation::Unary { op: UnaryOp::Abs, .. } => Some("use `abs.w` if wrapping is intended".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Phrases a halt of `operation` on `values` for the person who supplied
+    /// the inputs.
+    fn explain(&self, operation: &Operation, values: &[Literal], message: &str) -> (String, Option<String>) {
+        if let (
+            Operation::Binary { op: BinaryOp::Sub, operands: [Operand::Register(register), _], .. },
+            [held, requested],
+        ) = (operation, values)
+        {
+            let base = self.types.get(&register.index);
+            if let (Some(Ty::Record(record)), Some(member)) = (base, register.members.last()) {
+                let message =
+                    format!("insufficient {member}: record holds {}, requested {}", number(held), number(requested));
+                let help = format!(
+                    "`{operation}` would underflow; spend a {record} record holding at least {}, or request at most {}",
+                    number(requested),
+                    number(held)
+                );
+                return (message, Some(help));
+            }
+        }
+        (message.to_string(), self.advice(operation))
+    }
+}
@@ -0,0 +1,50 @@
+//! Exports the ABI of a package's compiled program as JSON, or prints the
+//! schema the ABI follows.
+//!
+//! ```text
+//! abi [PACKAGE] [--out FILE]
+//! abi --schema
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::abi::{Abi, SCHEMA};
+use workshop::package::{read, Package};
+
+#[derive(Parser)]
+#[command(about = "Export the records, structs, mappings and function signatures of build/main.aleo as JSON")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// Write the ABI to this file instead of standard output.
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+    /// Print the JSON Schema of the ABI instead.
+    #[arg(long, conflicts_with = "out")]
+    schema: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    if args.schema {
+        print!("{SCHEMA}");
+        return Ok(());
+    }
+
+    let package = Package::open(&args.package)?;
+    let build = package.build_program_path();
+    let source = read(&build)?;
+    let program = workshop::aleo::parse(&source).with_context(|| format!("failed to parse `{}`", build.display()))?;
+    let json = Abi::new(&program).to_json();
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, json).with_context(|| format!("failed to write `{}`", path.display()))?;
+            eprintln!("Wrote the ABI of `{}` to {}", program.id, path.display());
+        }
+        None => print!("{json}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! Runs `leo build` and records the hashes of a package's sources and
+//! `build/` outputs, or checks that `build/` is still what the sources
+//! compile to.
+//!
+//! ```text
+//! build-manifest [PACKAGE] --build
+//! build-manifest [PACKAGE] [--check]
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use workshop::build::{check, leo_build, BuildManifest};
+use workshop::package::Package;
+
+#[derive(Parser)]
+#[command(about = "Run `leo build` and record build/checksums.json, or check build/ for staleness")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// Run `leo build` in the package first, so the recorded outputs are
+    /// built from the current sources.
+    #[arg(long, conflicts_with = "check")]
+    build: bool,
+    /// Compare with the recorded manifest instead of writing it.
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let path = package.checksums_path();
+
+    if args.build {
+        let status = leo_build(&package).status().context("failed to run `leo build`; is Leo installed?")?;
+        if !status.success() {
+            bail!("`leo build` failed ({status}); the manifest was not recorded");
+        }
+    }
+    if !args.check {
+        let manifest = BuildManifest::record(&package)?;
+        manifest.save(&path)?;
+        println!(
+            "Recorded {} source(s) and {} output(s) in {}",
+            manifest.sources.len(),
+            manifest.outputs.len(),
+            path.display()
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let stale = check(&package)?;
+    for reason in &stale {
+        println!("stale: {reason}");
+    }
+    if !stale.is_empty() {
+        println!("Rebuild and record it with `build-manifest --build`");
+        return Ok(ExitCode::FAILURE);
+    }
+    println!("`build/` is up to date with `{}`", package.source_path().display());
+    Ok(ExitCode::SUCCESS)
+}
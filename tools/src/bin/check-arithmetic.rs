@@ -0,0 +1,96 @@
+//! Finds arithmetic in a package's compiled program that can halt, both
+//! statically and by running each function on its input file values, section
+//! by section with the mappings carried over.
+//!
+//! ```text
+//! check-arithmetic [PACKAGE] [--input FILE]... [--caller ADDRESS]
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::address::Address;
+use workshop::arithmetic::{admin, caller_for, explain, lint, section_inputs};
+use workshop::inputs;
+use workshop::interpreter::{Interpreter, ZERO_ADDRESS};
+use workshop::package::{read, Package};
+
+#[derive(Parser)]
+#[command(about = "Report arithmetic in build/main.aleo that can overflow or underflow")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// Run only the sections of these input files instead of every file in `inputs/`.
+    #[arg(long = "input", value_name = "FILE")]
+    inputs: Vec<PathBuf>,
+    /// The caller for sections without a record input. Defaults to the address
+    /// the program's access checks compare `self.caller` with, so admin-gated
+    /// functions such as `mint` run.
+    #[arg(long)]
+    caller: Option<Address>,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let build = package.build_program_path();
+    let source = read(&build)?;
+    let program = workshop::aleo::parse(&source).with_context(|| format!("failed to parse `{}`", build.display()))?;
+
+    let caller = match &args.caller {
+        Some(caller) => caller.to_string(),
+        None => admin(&program).unwrap_or_else(|| ZERO_ADDRESS.to_string()),
+    };
+
+    let warnings = lint(&program);
+    for warning in &warnings {
+        eprintln!("{}", warning.render(&build, &source));
+    }
+
+    let files = if args.inputs.is_empty() { package.input_files()? } else { args.inputs };
+    let (mut runs, mut errors) = (0, 0);
+    for path in &files {
+        let input_source = read(path)?;
+        let (file, diagnostics) = inputs::parse(&input_source);
+        if diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+            eprintln!("skipping `{}`: it does not parse; run `check-inputs` for details\n", path.display());
+            continue;
+        }
+        // Sections run in file order and share mapping state, so a `mint`
+        // section can fund the sections after it.
+        let mut interpreter = Interpreter::new(&program, &caller);
+        for section in &file.sections {
+            if program.function(&section.name.name).is_none() {
+                continue;
+            }
+            let values = match section_inputs(section) {
+                Ok(values) => values,
+                Err(diagnostic) => {
+                    eprintln!("{}", diagnostic.render(path, &input_source));
+                    errors += 1;
+                    continue;
+                }
+            };
+            runs += 1;
+            interpreter.set_caller(caller_for(&values, &caller));
+            if let Err(error) = interpreter.execute(&section.name.name, values) {
+                let diagnostic = explain(&program, &error);
+                eprintln!("running `[{}]` from `{}`:", section.name.name, path.display());
+                eprintln!("{}", diagnostic.render(&build, &source));
+                errors += 1;
+            }
+        }
+    }
+
+    let summary = format!("{} unchecked operation(s), ran {runs} input section(s)", warnings.len());
+    if errors == 0 {
+        println!("{summary}: ok");
+        Ok(ExitCode::SUCCESS)
+    } else {
+        eprintln!("{summary}: {errors} error(s)");
+        Ok(ExitCode::FAILURE)
+    }
+}
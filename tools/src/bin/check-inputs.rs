@@ -0,0 +1,61 @@
+//! Checks a package's `inputs/*.in` files against its compiled program.
+//!
+//! ```text
+//! check-inputs [PACKAGE] [--input FILE]...
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::package::{read, Package};
+use workshop::{inputs, leo};
+
+#[derive(Parser)]
+#[command(about = "Check inputs/*.in files against build/main.aleo")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// Check only these input files instead of every file in `inputs/`.
+    #[arg(long = "input", value_name = "FILE")]
+    inputs: Vec<PathBuf>,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+
+    let build = package.build_program_path();
+    let program =
+        workshop::aleo::parse(&read(&build)?).with_context(|| format!("failed to parse `{}`", build.display()))?;
+    // The Leo source is only needed for parameter names, so checking still
+    // works on a package that ships just its build output.
+    let transitions = read(&package.source_path()).map(|source| leo::transitions(&source)).unwrap_or_default();
+
+    let files = if args.inputs.is_empty() { package.input_files()? } else { args.inputs };
+    let (mut errors, mut warnings) = (0, 0);
+    for path in &files {
+        let source = read(path)?;
+        let (file, mut diagnostics) = inputs::parse(&source);
+        diagnostics.extend(inputs::check(&file, &program, &transitions));
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic.render(path, &source));
+            if diagnostic.is_error() {
+                errors += 1;
+            } else {
+                warnings += 1;
+            }
+        }
+    }
+
+    if errors == 0 {
+        println!("checked {} input file(s) against `{}`: ok ({warnings} warning(s))", files.len(), program.id);
+        Ok(ExitCode::SUCCESS)
+    } else {
+        eprintln!("checked {} input file(s): {errors} error(s), {warnings} warning(s)", files.len());
+        Ok(ExitCode::FAILURE)
+    }
+}
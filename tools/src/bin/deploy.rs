@@ -0,0 +1,105 @@
+//! Deploys a package with `snarkos developer deploy`, replacing `deploy.sh`.
+//! Refuses to deploy a `build/` that its build manifest shows to be stale.
+//!
+//! ```text
+//! deploy [PACKAGE] [--account NAME] [--network NAME] [--config FILE] [--fee MICROCREDITS] [--record RECORD] [--dry-run]
+//! ```
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use workshop::build::check;
+use workshop::deploy::{load_private_key, Deployment, ENV_FILE};
+use workshop::keystore::{read_passphrase, Keystore};
+use workshop::network::{NetworksConfig, CONFIG_FILE};
+use workshop::package::Package;
+
+#[derive(Parser)]
+#[command(about = "Deploy a package's build/ to an Aleo network")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// Sign with this keystore account instead of `PRIVATE_KEY` (see `keystore`).
+    #[arg(long, value_name = "NAME")]
+    account: Option<String>,
+    /// The network profile to deploy to; defaults to the config's `default`.
+    #[arg(long)]
+    network: Option<String>,
+    /// The network profile file; defaults to `networks.toml` in the package.
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Override the profile's fee, in microcredits.
+    #[arg(long, value_name = "MICROCREDITS")]
+    fee: Option<u64>,
+    /// The fee record to spend.
+    #[arg(long)]
+    record: Option<String>,
+    /// Build and print the deployment transaction without broadcasting it.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let stale = check(&package)?;
+    if !stale.is_empty() {
+        for reason in &stale {
+            eprintln!("stale build: {reason}");
+        }
+        bail!(
+            "refusing to deploy a stale build; rebuild it with `cargo run --bin build-manifest -- {} --build`",
+            package.root().display()
+        );
+    }
+
+    let config_path = args.config.unwrap_or_else(|| package.root().join(CONFIG_FILE));
+    let config = NetworksConfig::load_or_default(&config_path)?;
+    let (network, profile) = config.select(args.network.as_deref())?;
+    let mut profile = profile.clone();
+    if let Some(fee) = args.fee {
+        profile.fee = fee;
+    }
+
+    let private_key = match &args.account {
+        Some(name) => {
+            let keystore = Keystore::open_default()?;
+            keystore.account(name)?;
+            keystore.unlock(name, &read_passphrase(&format!("Passphrase for `{name}`: "), false)?)?
+        }
+        None => load_private_key(&package.root().join(ENV_FILE))?,
+    };
+    let mut deployment = Deployment::new(&package, network, profile, private_key);
+    deployment.fee_record = args.record;
+    deployment.dry_run = args.dry_run;
+
+    eprintln!("📦 Deploying `{}` to `{network}`", deployment.program_id);
+    if let Some(name) = &args.account {
+        eprintln!("   account:   {name}");
+    }
+    eprintln!("   query:     {}", deployment.profile.query);
+    if deployment.dry_run {
+        eprintln!("   broadcast: (dry run, not sent)");
+    } else {
+        eprintln!("   broadcast: {}", deployment.profile.broadcast);
+    }
+    eprintln!("   fee:       {} microcredits", deployment.profile.fee);
+    eprintln!("   command:   {}", deployment.display_command());
+
+    let status = match deployment.command().status() {
+        Ok(status) => status,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            bail!("`snarkos` was not found on PATH; install it first (see FAQ.md)")
+        }
+        Err(error) => return Err(error).context("failed to run `snarkos`"),
+    };
+    if !status.success() {
+        eprintln!("snarkos exited with {status}");
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,32 @@
+//! Checks that every artifact of a package names the same program.
+//!
+//! ```text
+//! doctor [PACKAGE]
+//! ```
+//!
+//! Exits non-zero when anything disagrees, so it can gate a deployment.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use workshop::doctor::examine;
+
+#[derive(Parser)]
+#[command(about = "Check that a package's artifacts agree on the program id")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let report = examine(&args.package);
+    print!("{report}");
+    if report.is_healthy() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
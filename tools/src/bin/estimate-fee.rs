@@ -0,0 +1,92 @@
+//! Estimates the fee for deploying a package's compiled program, and checks
+//! the fee its network profile pays against it.
+//!
+//! ```text
+//! estimate-fee [PACKAGE] [--network NAME] [--config FILE] [--margin PERCENT]
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::amount::format_amount;
+use workshop::fee::{
+    FeeEstimate, CONSTRAINTS_PER_FUNCTION, CONSTRAINTS_PER_INSTRUCTION, CONSTRAINTS_PER_RECORD, DEFAULT_MARGIN_PERCENT,
+    KEY_BYTES_PER_FUNCTION, NAMESPACE_FREE_LENGTH, STORAGE_COST_PER_BYTE, SYNTHESIS_COST_PER_CONSTRAINT,
+};
+use workshop::network::{NetworksConfig, CONFIG_FILE};
+use workshop::package::{read, Package};
+
+#[derive(Parser)]
+#[command(about = "Estimate the deployment fee of build/main.aleo and check the network profile's fee against it")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// The network profile whose fee to check; defaults to the config's `default`.
+    #[arg(long)]
+    network: Option<String>,
+    /// The network profile file; defaults to `networks.toml` in the package.
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// How much to add to the estimate for the suggested fee, in percent.
+    #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_MARGIN_PERCENT)]
+    margin: u64,
+}
+
+/// Microcredits as credits, e.g. `12.5 credits`.
+fn credits(microcredits: u64) -> String {
+    match format_amount(microcredits.into(), 6) {
+        one if one == "1" => "1 credit".to_string(),
+        amount => format!("{amount} credits"),
+    }
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let build = package.build_program_path();
+    let source = read(&build)?;
+    let program = workshop::aleo::parse(&source).with_context(|| format!("failed to parse `{}`", build.display()))?;
+
+    let config_path = args.config.unwrap_or_else(|| package.root().join(CONFIG_FILE));
+    let config = NetworksConfig::load_or_default(&config_path)?;
+    let (network, profile) = config.select(args.network.as_deref())?;
+
+    let estimate = FeeEstimate::new(&program, &source);
+    println!("Deployment fee estimate for `{}`\n", program.id);
+    println!("  storage    {:>20}", credits(estimate.storage_cost()));
+    println!("    {} bytes × {STORAGE_COST_PER_BYTE} microcredits", estimate.storage_bytes());
+    println!(
+        "    program {} bytes, {} function(s) × ~{KEY_BYTES_PER_FUNCTION} bytes of verifying key and certificate",
+        estimate.program_bytes, estimate.functions
+    );
+    println!("  synthesis  {:>20}", credits(estimate.synthesis_cost()));
+    println!("    ~{} constraints × {SYNTHESIS_COST_PER_CONSTRAINT} microcredits", estimate.constraints());
+    println!(
+        "    {} function(s) × {CONSTRAINTS_PER_FUNCTION}, {} record input(s) and output(s) × {CONSTRAINTS_PER_RECORD}, {} instruction(s) × {CONSTRAINTS_PER_INSTRUCTION}",
+        estimate.functions, estimate.records, estimate.instructions
+    );
+    println!("  namespace  {:>20}", credits(estimate.namespace_cost()));
+    println!(
+        "    `{}` is {} characters; names under {NAMESPACE_FREE_LENGTH} cost ten times more per missing character",
+        estimate.name,
+        estimate.name.chars().count()
+    );
+    println!("  total      {:>20}\n", credits(estimate.total()));
+
+    let suggested = estimate.suggested_fee(args.margin);
+    println!("Suggested fee with a {}% margin: {suggested} microcredits ({})", args.margin, credits(suggested));
+    if profile.fee < estimate.total() {
+        println!(
+            "`{network}` pays {} microcredits ({}), less than the estimate; set `fee = {suggested}` in `{}` or pass `deploy --fee {suggested}`",
+            profile.fee,
+            credits(profile.fee),
+            config_path.display()
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+    println!("`{network}` pays {} microcredits ({}), enough for the estimate", profile.fee, credits(profile.fee));
+    Ok(ExitCode::SUCCESS)
+}
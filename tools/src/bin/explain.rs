@@ -0,0 +1,55 @@
+//! Explains captured `leo run` or `snarkos developer deploy` output: what
+//! went wrong, why, and where in the package to fix it.
+//!
+//! ```text
+//! explain [PACKAGE] [--log FILE]
+//! leo run mint 2>&1 | explain
+//! ```
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::explain::{locate, recognize};
+use workshop::package::{read, Package};
+
+#[derive(Parser)]
+#[command(about = "Explain a failed `leo run` or `snarkos developer deploy` and point at the fix")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// The captured output; read from standard input if not given.
+    #[arg(long, value_name = "FILE")]
+    log: Option<PathBuf>,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let output = match &args.log {
+        Some(path) => read(path)?,
+        None => {
+            let mut output = String::new();
+            std::io::stdin().read_to_string(&mut output).context("failed to read standard input")?;
+            output
+        }
+    };
+
+    let recognized = recognize(&output);
+    if recognized.is_empty() {
+        eprintln!("no known failure in the output; see FAQ.md for the ones `explain` does not cover");
+        return Ok(ExitCode::FAILURE);
+    }
+    for found in &recognized {
+        println!("error (output line {}): {}", found.line, found.failure);
+        println!("cause: {}\n", found.failure.cause());
+        for location in locate(&found.failure, &package) {
+            let source = read(&location.path).unwrap_or_default();
+            println!("{}", location.diagnostic.render(&location.path, &source));
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,79 @@
+//! Runs a function of a package's compiled program without proving.
+//!
+//! ```text
+//! interpret <FUNCTION> [INPUT]... [--package DIR] [--caller ADDRESS] [--block-height N]
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use workshop::address::Address;
+use workshop::arithmetic::explain;
+use workshop::interpreter::{Interpreter, Value, ZERO_ADDRESS};
+use workshop::memo::record_memo;
+use workshop::package::{read, Package};
+
+#[derive(Parser)]
+#[command(about = "Execute a function of build/main.aleo on plaintext inputs, without proving")]
+struct Args {
+    /// The function to run.
+    function: String,
+    /// Its inputs, e.g. `100u128`, an address, or a record as printed by a
+    /// previous run: `"{ owner: aleo1....private, balance: 100u128.private, _nonce: 0group.public }"`.
+    inputs: Vec<String>,
+    /// The package directory.
+    #[arg(long, default_value = ".")]
+    package: PathBuf,
+    /// The address `self.caller` evaluates to.
+    #[arg(long, default_value = ZERO_ADDRESS)]
+    caller: Address,
+    /// The value of `block.height` in finalize blocks.
+    #[arg(long, default_value_t = 0)]
+    block_height: u32,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let build = package.build_program_path();
+    let source = read(&build)?;
+    let program = workshop::aleo::parse(&source).with_context(|| format!("failed to parse `{}`", build.display()))?;
+
+    let inputs = args
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, text)| Value::parse(text).map_err(|error| anyhow!("input {}: {error}", index + 1)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut interpreter = Interpreter::new(&program, args.caller.as_str());
+    interpreter.set_block_height(args.block_height);
+    match interpreter.execute(&args.function, inputs) {
+        Ok(outputs) => {
+            println!("`{}/{}` as {}: {} output(s)", program.id, args.function, args.caller, outputs.len());
+            for output in outputs {
+                println!("\n • {output}");
+                if let Value::Record(record) = &output {
+                    match record_memo(record) {
+                        Some(Ok(memo)) => println!("   memo: {memo:?}"),
+                        Some(Err(error)) => println!("   memo: {error}"),
+                        None => {}
+                    }
+                }
+            }
+            for mapping in program.mappings() {
+                for (key, value) in interpreter.mapping_entries(&mapping.name.name) {
+                    println!("\n{}[{key}] = {value}", mapping.name);
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(error) if error.span().is_some() => {
+            eprintln!("{}", explain(&program, &error).render(&build, &source));
+            Ok(ExitCode::FAILURE)
+        }
+        Err(error) => Err(error.into()),
+    }
+}
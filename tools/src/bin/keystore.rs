@@ -0,0 +1,107 @@
+//! Manages the encrypted keystore `deploy --account` reads private keys from.
+//!
+//! ```text
+//! keystore new <NAME>
+//! keystore import <NAME> [--address ADDRESS]
+//! keystore set-address <NAME> <ADDRESS>
+//! keystore list
+//! keystore export <NAME>
+//! keystore remove <NAME>
+//! ```
+
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use workshop::address::Address;
+use workshop::deploy::PrivateKey;
+use workshop::keystore::{check_private_key, generate_private_key, read_passphrase, Keystore};
+
+#[derive(Parser)]
+#[command(about = "Create, import and list the encrypted accounts used by `deploy --account`")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a new private key and store it encrypted.
+    New {
+        /// The account name, e.g. `workshop`.
+        name: String,
+    },
+    /// Store an existing private key, read from the terminal or standard input, encrypted.
+    Import {
+        /// The account name, e.g. `workshop`.
+        name: String,
+        /// The account's address, to show in `list`.
+        #[arg(long)]
+        address: Option<Address>,
+    },
+    /// Record an account's address, to show in `list`.
+    SetAddress { name: String, address: Address },
+    /// List the accounts.
+    List,
+    /// Decrypt an account and print its private key.
+    Export { name: String },
+    /// Delete an account.
+    Remove { name: String },
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let keystore = Keystore::open_default()?;
+    match args.command {
+        Command::New { name } => {
+            let passphrase = read_passphrase(&format!("Passphrase for `{name}`: "), true)?;
+            let account = keystore.add(&name, &generate_private_key(), None, &passphrase)?;
+            println!("Created `{name}` in {}", account.path.display());
+            // Deriving the address from the key needs snarkVM, which `leo` bundles.
+            println!("Show its address with `leo account import \"$(cargo run -q --bin keystore -- export {name})\"`,");
+            println!("then record it with `cargo run --bin keystore -- set-address {name} <ADDRESS>`");
+            println!("Deploy with it using `deploy --account {name}`");
+        }
+        Command::Import { name, address } => {
+            let text = if std::io::stdin().is_terminal() {
+                rpassword::prompt_password("Private key: ").context("failed to read the private key")?
+            } else {
+                let mut text = String::new();
+                std::io::stdin().read_line(&mut text).context("failed to read the private key")?;
+                text
+            };
+            let key = PrivateKey::parse(&text, "the input")?;
+            check_private_key(&key)?;
+            let passphrase = read_passphrase(&format!("Passphrase for `{name}`: "), true)?;
+            let account = keystore.add(&name, &key, address, &passphrase)?;
+            println!("Imported `{name}` into {}", account.path.display());
+            println!("Deploy with it using `deploy --account {name}`");
+        }
+        Command::SetAddress { name, address } => {
+            keystore.set_address(&name, address)?;
+            println!("Recorded the address of `{name}`");
+        }
+        Command::List => {
+            let accounts = keystore.accounts()?;
+            if accounts.is_empty() {
+                println!("No accounts in {}; add one with `keystore new NAME`", keystore.dir().display());
+            }
+            for account in accounts {
+                match &account.address {
+                    Some(address) => println!("{}  {address}", account.name),
+                    None => println!("{}", account.name),
+                }
+            }
+        }
+        Command::Export { name } => {
+            keystore.account(&name)?;
+            let passphrase = read_passphrase(&format!("Passphrase for `{name}`: "), false)?;
+            println!("{}", keystore.unlock(&name, &passphrase)?.expose());
+        }
+        Command::Remove { name } => {
+            let account = keystore.remove(&name)?;
+            println!("Removed `{name}` ({})", account.path.display());
+        }
+    }
+    Ok(())
+}
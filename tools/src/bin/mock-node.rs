@@ -0,0 +1,51 @@
+//! Serves an offline stand-in for the node API `deploy` queries and
+//! broadcasts to.
+//!
+//! ```text
+//! mock-node [--dir DIR] [--port PORT] [--program FILE]...
+//! ```
+
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::mock_node::{MockNode, DEFAULT_PORT, NETWORK};
+use workshop::package::read;
+
+#[derive(Parser)]
+#[command(about = "Serve the query and broadcast endpoints of a node locally, keeping accepted transactions on disk")]
+struct Args {
+    /// Where the node keeps `state.json` and accepted transactions.
+    #[arg(long, default_value = "target/mock-node")]
+    dir: PathBuf,
+    /// The port to listen on, on localhost.
+    #[arg(long, default_value_t = DEFAULT_PORT)]
+    port: u16,
+    /// A compiled program to serve as already deployed, e.g. an import.
+    #[arg(long = "program")]
+    programs: Vec<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let mut node = MockNode::open(&args.dir)?;
+    for path in &args.programs {
+        let id = node.add_program(&read(path)?)?;
+        println!("Serving `{id}` from {}", path.display());
+    }
+    let listener = TcpListener::bind(("127.0.0.1", args.port))
+        .with_context(|| format!("failed to listen on port {}", args.port))?;
+
+    let state = node.state();
+    println!(
+        "Mock node at height {} with {} program(s), state in {}",
+        state.height,
+        state.programs.len(),
+        args.dir.display()
+    );
+    println!("  --query \"http://localhost:{}\"", args.port);
+    println!("  --broadcast \"http://localhost:{}/{NETWORK}/transaction/broadcast\"", args.port);
+    node.serve(&listener)?;
+    Ok(())
+}
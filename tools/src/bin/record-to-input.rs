@@ -0,0 +1,71 @@
+//! Converts a record printed by `leo run` into an input file assignment.
+//!
+//! ```text
+//! leo run mint 100u128 | record-to-input [PACKAGE] [--function NAME] [--name NAME] [--write]
+//! record-to-input [PACKAGE] --from output.txt --write
+//! ```
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use workshop::inputs::{self, record};
+use workshop::leo;
+use workshop::package::{read, Package};
+
+#[derive(Parser)]
+#[command(about = "Turn a record printed by `leo run` into a ready-to-paste input file literal")]
+struct Args {
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// Read the record from this file instead of standard input.
+    #[arg(long, value_name = "FILE")]
+    from: Option<PathBuf>,
+    /// The function whose record input to fill; needed if several take records.
+    #[arg(long)]
+    function: Option<String>,
+    /// The input to fill; needed if the function takes several records.
+    #[arg(long)]
+    name: Option<String>,
+    /// Write the assignment into the package's input file instead of printing it.
+    #[arg(long)]
+    write: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let build = package.build_program_path();
+    let program =
+        workshop::aleo::parse(&read(&build)?).with_context(|| format!("failed to parse `{}`", build.display()))?;
+    let transitions = read(&package.source_path()).map(|source| leo::transitions(&source)).unwrap_or_default();
+
+    let text = match &args.from {
+        Some(path) => read(path)?,
+        None => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text).context("failed to read standard input")?;
+            text
+        }
+    };
+    let found = record::find_record(&text)?;
+    let target = record::target(&program, &transitions, args.function.as_deref(), args.name.as_deref())?;
+    let assignment = record::assignment(&target, &record::record_literal(&program, &target.record, &found)?);
+
+    if !args.write {
+        println!("{assignment}");
+        return Ok(());
+    }
+    let path = package.input_path();
+    let source = if path.exists() { read(&path)? } else { String::new() };
+    let (file, diagnostics) = inputs::parse(&source);
+    if diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+        anyhow::bail!("`{}` does not parse; fix it first (see `check-inputs`)", path.display());
+    }
+    let updated = record::set_input(&source, &file, &target.function, &target.name, &assignment);
+    std::fs::write(&path, updated).with_context(|| format!("failed to write `{}`", path.display()))?;
+    println!("Set `{}` in the [{}] section of {}", target.name, target.function, path.display());
+    Ok(())
+}
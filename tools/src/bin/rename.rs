@@ -0,0 +1,47 @@
+//! Renames a package in all the places its program name appears.
+//!
+//! ```text
+//! rename <NEW_NAME> [PACKAGE] [--dry-run]
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use workshop::package::Package;
+use workshop::rename::plan;
+
+#[derive(Parser)]
+#[command(about = "Rename a package's program everywhere it appears")]
+struct Args {
+    /// The new program name, with or without `.aleo`.
+    new_name: String,
+    /// The package directory.
+    #[arg(default_value = ".")]
+    package: PathBuf,
+    /// List the changes without making them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let package = Package::open(&args.package)?;
+    let plan = plan(&package, &args.new_name)?;
+
+    println!("Renaming `{}.aleo` to `{}.aleo`:", plan.old_name, plan.new_name);
+    for edit in &plan.edits {
+        println!("  edit   {}", edit.path.display());
+    }
+    for (from, to) in plan.input_move.iter().chain(&plan.root_move) {
+        println!("  move   {} -> {}", from.display(), to.display());
+    }
+    if args.dry_run {
+        println!("(dry run, nothing changed)");
+        return Ok(());
+    }
+
+    let root = plan.apply()?;
+    println!("Done; the package is now at {}", root.display());
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Recording what `build/` was compiled from, to catch stale artifacts.
+//!
+//! `leo build` overwrites `build/main.aleo` but leaves nothing behind to say
+//! which `src/main.leo` it came from, and the package's `.history` shows the
+//! source changing between builds. The build manifest, `build/checksums.json`,
+//! holds a SHA-256 hash of every source (the files in `src/` and
+//! `program.json`) and every output (`build/main.aleo` and
+//! `build/program.json`). [`check`] compares it with the files on disk and
+//! names whatever changed since it was recorded. Recording trusts `build/` to
+//! be what the sources compile to, so record right after [`leo_build`] runs.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::package::{Package, MANIFEST_FILE};
+
+/// The build manifest inside `build/`.
+pub const CHECKSUMS_FILE: &str = "checksums.json";
+
+/// The format version written to, and required in, the build manifest.
+pub const BUILD_MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum BuildError {
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("failed to write `{}`: {source}", path.display())]
+    Write { path: PathBuf, source: std::io::Error },
+    #[error("`{}` is not a valid build manifest: {source}", path.display())]
+    Parse { path: PathBuf, source: serde_json::Error },
+    #[error("`{}` has version {found}; only version {BUILD_MANIFEST_VERSION} is supported", path.display())]
+    Version { path: PathBuf, found: u32 },
+}
+
+/// The contents of `build/checksums.json`: hex SHA-256 hashes keyed by the
+/// path relative to the package root, with `/` separators.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildManifest {
+    pub version: u32,
+    pub sources: BTreeMap<String, String>,
+    pub outputs: BTreeMap<String, String>,
+}
+
+impl BuildManifest {
+    /// Hashes the package's sources and outputs as they are on disk.
+    pub fn record(package: &Package) -> Result<Self, BuildError> {
+        Self::record_with(package, |path| fs::read(path).map_err(|source| io_error(path, source)))
+    }
+
+    /// Hashes the package's sources and outputs with `read` supplying the
+    /// contents, so a manifest can be computed for changes not yet written.
+    pub fn record_with(
+        package: &Package,
+        read: impl Fn(&Path) -> Result<Vec<u8>, BuildError>,
+    ) -> Result<Self, BuildError> {
+        let hash_all = |paths: Vec<PathBuf>| -> Result<BTreeMap<String, String>, BuildError> {
+            paths.iter().map(|path| Ok((relative(package, path), hash(&read(path)?)))).collect()
+        };
+        Ok(Self {
+            version: BUILD_MANIFEST_VERSION,
+            sources: hash_all(sources(package)?)?,
+            outputs: hash_all(outputs(package))?,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BuildError> {
+        let text = fs::read_to_string(path).map_err(|source| io_error(path, source))?;
+        let manifest: Self =
+            serde_json::from_str(&text).map_err(|source| BuildError::Parse { path: path.to_path_buf(), source })?;
+        if manifest.version != BUILD_MANIFEST_VERSION {
+            return Err(BuildError::Version { path: path.to_path_buf(), found: manifest.version });
+        }
+        Ok(manifest)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("the manifest serializes");
+        json.push('\n');
+        json
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BuildError> {
+        fs::write(path, self.to_json()).map_err(|source| BuildError::Write { path: path.to_path_buf(), source })
+    }
+}
+
+/// The `leo build` command that compiles the package into `build/`.
+pub fn leo_build(package: &Package) -> Command {
+    let mut command = Command::new("leo");
+    command.arg("build").current_dir(package.root());
+    command
+}
+
+/// Something that makes `build/` stale, or unknown to be current.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Stale {
+    /// There is no build manifest to compare against.
+    Unrecorded,
+    SourceChanged(String),
+    SourceAdded(String),
+    SourceRemoved(String),
+    /// An output was edited or replaced without recording the build.
+    OutputChanged(String),
+    OutputMissing(String),
+}
+
+impl fmt::Display for Stale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stale::Unrecorded => write!(f, "`build/{CHECKSUMS_FILE}` is missing, so the build cannot be checked"),
+            Stale::SourceChanged(path) => write!(f, "`{path}` changed since the last build"),
+            Stale::SourceAdded(path) => write!(f, "`{path}` was added since the last build"),
+            Stale::SourceRemoved(path) => write!(f, "`{path}` was removed since the last build"),
+            Stale::OutputChanged(path) => write!(f, "`{path}` changed since the build was recorded"),
+            Stale::OutputMissing(path) => write!(f, "`{path}` is missing"),
+        }
+    }
+}
+
+/// Compares the package with its build manifest. An empty result means
+/// `build/` is what the current sources compiled to.
+pub fn check(package: &Package) -> Result<Vec<Stale>, BuildError> {
+    let path = package.checksums_path();
+    if !path.exists() {
+        return Ok(vec![Stale::Unrecorded]);
+    }
+    let recorded = BuildManifest::load(&path)?;
+    let current = BuildManifest::record_with(package, |path| match fs::read(path) {
+        Ok(bytes) => Ok(bytes),
+        // A missing output is reported below rather than failing the check.
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(source) => Err(io_error(path, source)),
+    })?;
+
+    let mut stale = Vec::new();
+    for (path, hash) in &current.sources {
+        match recorded.sources.get(path) {
+            None => stale.push(Stale::SourceAdded(path.clone())),
+            Some(recorded) if recorded != hash => stale.push(Stale::SourceChanged(path.clone())),
+            Some(_) => {}
+        }
+    }
+    for path in recorded.sources.keys().filter(|path| !current.sources.contains_key(*path)) {
+        stale.push(Stale::SourceRemoved(path.clone()));
+    }
+    for (path, hash) in &recorded.outputs {
+        if !package.root().join(path).exists() {
+            stale.push(Stale::OutputMissing(path.clone()));
+        } else if current.outputs.get(path) != Some(hash) {
+            stale.push(Stale::OutputChanged(path.clone()));
+        }
+    }
+    Ok(stale)
+}
+
+/// `src/*` and `program.json`, sorted.
+fn sources(package: &Package) -> Result<Vec<PathBuf>, BuildError> {
+    let dir = package.root().join("src");
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|source| io_error(&dir, source))? {
+        let path = entry.map_err(|source| io_error(&dir, source))?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.push(package.root().join(MANIFEST_FILE));
+    paths.sort();
+    Ok(paths)
+}
+
+fn outputs(package: &Package) -> Vec<PathBuf> {
+    vec![package.build_program_path(), package.build_manifest_path()]
+}
+
+fn relative(package: &Package, path: &Path) -> String {
+    let relative = path.strip_prefix(package.root()).unwrap_or(path);
+    relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+fn hash(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> BuildError {
+    BuildError::Io { path: path.to_path_buf(), source }
+}
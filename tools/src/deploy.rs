@@ -0,0 +1,185 @@
+//! Assembling the `snarkos developer deploy` invocation for a package.
+//!
+//! This replaces `deploy.sh`: the program id comes from `program.json`, the
+//! endpoints and fee from a [`NetworkProfile`], and the private key from the
+//! [keystore](crate::keystore), the environment or the package's `.env` file
+//! rather than the script itself.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use thiserror::Error;
+
+use crate::network::NetworkProfile;
+use crate::package::Package;
+
+/// The variable the private key is read from.
+pub const PRIVATE_KEY_VAR: &str = "PRIVATE_KEY";
+
+/// The dotenv file looked up in the package directory; it is gitignored.
+pub const ENV_FILE: &str = ".env";
+
+const PRIVATE_KEY_PREFIX: &str = "APrivateKey1";
+const PRIVATE_KEY_LENGTH: usize = 59;
+
+#[derive(Debug, Error)]
+pub enum DeployError {
+    #[error("no private key: set `{PRIVATE_KEY_VAR}` in the environment or in `{}`", env_file.display())]
+    MissingKey { env_file: PathBuf },
+    #[error("the private key from {source_name} is malformed: expected {PRIVATE_KEY_LENGTH} characters starting with `{PRIVATE_KEY_PREFIX}`")]
+    MalformedKey { source_name: String },
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("`{}` line {line}: expected `NAME=value`", path.display())]
+    EnvSyntax { path: PathBuf, line: usize },
+}
+
+/// An Aleo private key. Never printed in full.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PrivateKey(String);
+
+impl PrivateKey {
+    /// Checks the shape of a key; `source_name` describes where it came from
+    /// for error messages.
+    pub fn parse(text: &str, source_name: &str) -> Result<Self, DeployError> {
+        let text = text.trim();
+        if text.len() != PRIVATE_KEY_LENGTH
+            || !text.starts_with(PRIVATE_KEY_PREFIX)
+            || !text.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(DeployError::MalformedKey { source_name: source_name.to_string() });
+        }
+        Ok(Self(text.to_string()))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(<redacted>)")
+    }
+}
+
+/// Reads `NAME=value` pairs from a dotenv file.
+///
+/// Blank lines, `#` comments and a leading `export ` are allowed, and values
+/// may be wrapped in single or double quotes.
+pub fn parse_env_file(path: &Path, text: &str) -> Result<Vec<(String, String)>, DeployError> {
+    let mut pairs = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) =
+            line.split_once('=').ok_or(DeployError::EnvSyntax { path: path.to_path_buf(), line: index + 1 })?;
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| value.strip_prefix(*quote).and_then(|value| value.strip_suffix(*quote)))
+            .unwrap_or(value);
+        pairs.push((name.trim().to_string(), value.to_string()));
+    }
+    Ok(pairs)
+}
+
+/// Finds the private key, preferring the environment over `env_file`.
+pub fn load_private_key(env_file: &Path) -> Result<PrivateKey, DeployError> {
+    if let Ok(value) = std::env::var(PRIVATE_KEY_VAR) {
+        if !value.trim().is_empty() {
+            return PrivateKey::parse(&value, &format!("`${PRIVATE_KEY_VAR}`"));
+        }
+    }
+    if env_file.exists() {
+        let text = std::fs::read_to_string(env_file)
+            .map_err(|source| DeployError::Io { path: env_file.to_path_buf(), source })?;
+        let value = parse_env_file(env_file, &text)?.into_iter().rev().find(|(name, _)| name == PRIVATE_KEY_VAR);
+        if let Some((_, value)) = value.filter(|(_, value)| !value.is_empty()) {
+            return PrivateKey::parse(&value, &format!("`{PRIVATE_KEY_VAR}` in `{}`", env_file.display()));
+        }
+    }
+    Err(DeployError::MissingKey { env_file: env_file.to_path_buf() })
+}
+
+/// Everything needed to deploy one package to one network.
+#[derive(Clone, Debug)]
+pub struct Deployment {
+    pub program_id: String,
+    pub build_dir: PathBuf,
+    pub network: String,
+    pub profile: NetworkProfile,
+    pub private_key: PrivateKey,
+    /// A fee record to spend instead of letting snarkos pick one.
+    pub fee_record: Option<String>,
+    /// Build the transaction and print it instead of broadcasting it.
+    pub dry_run: bool,
+}
+
+impl Deployment {
+    pub fn new(package: &Package, network: &str, profile: NetworkProfile, private_key: PrivateKey) -> Self {
+        Self {
+            program_id: package.program_id().to_string(),
+            build_dir: package.build_dir(),
+            network: network.to_string(),
+            profile,
+            private_key,
+            fee_record: None,
+            dry_run: false,
+        }
+    }
+
+    /// The arguments passed to `snarkos`, with the private key replaced by
+    /// `redacted` when given.
+    fn arguments(&self, redacted: Option<&str>) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![
+            "developer".into(),
+            "deploy".into(),
+            self.program_id.clone().into(),
+            "--private-key".into(),
+            redacted.unwrap_or(self.private_key.expose()).into(),
+            "--query".into(),
+            self.profile.query.clone().into(),
+            "--path".into(),
+            self.build_dir.clone().into(),
+            "--fee".into(),
+            self.profile.fee.to_string().into(),
+        ];
+        if let Some(record) = &self.fee_record {
+            args.extend(["--record".into(), record.into()]);
+        }
+        if self.dry_run {
+            args.push("--dry-run".into());
+        } else {
+            args.extend(["--broadcast".into(), self.profile.broadcast.clone().into()]);
+        }
+        args
+    }
+
+    /// The `snarkos` command that performs the deployment.
+    pub fn command(&self) -> Command {
+        let mut command = Command::new("snarkos");
+        command.args(self.arguments(None));
+        command
+    }
+
+    /// The command as it would be typed, with the private key redacted.
+    pub fn display_command(&self) -> String {
+        let mut text = String::from("snarkos");
+        for arg in self.arguments(Some("<redacted>")) {
+            let arg = arg.to_string_lossy();
+            text.push(' ');
+            if arg.contains(|c: char| c.is_whitespace() || c == '{' || c == '"') {
+                text.push_str(&format!("{arg:?}"));
+            } else {
+                text.push_str(&arg);
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,246 @@
+//! Checking that every artifact of a package agrees on the program id.
+//!
+//! `program.json` is taken as the source of truth, since it is what both
+//! `leo` and the `deploy` tool read; every other artifact is compared with it.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::package::{self, Manifest, MANIFEST_FILE, PROGRAM_SUFFIX};
+
+/// The places a package names its program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Artifact {
+    Manifest,
+    BuildManifest,
+    Source,
+    BuildProgram,
+    InputFile,
+    DeployScript,
+    Directory,
+}
+
+impl fmt::Display for Artifact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Artifact::Manifest => "program.json",
+            Artifact::BuildManifest => "build/program.json",
+            Artifact::Source => "src/main.leo",
+            Artifact::BuildProgram => "build/main.aleo",
+            Artifact::InputFile => "input file",
+            Artifact::DeployScript => "deploy.sh APPNAME",
+            Artifact::Directory => "package directory",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The artifact names a different program.
+    Mismatch {
+        fix: String,
+    },
+    /// The artifact does not exist or does not name a program at all.
+    Missing {
+        fix: String,
+    },
+}
+
+/// What one artifact says the program id is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub artifact: Artifact,
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub found: Option<String>,
+    pub status: Status,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// The program id from `program.json`, if it could be read.
+    pub expected: Option<String>,
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    pub fn is_healthy(&self) -> bool {
+        self.findings.iter().all(|finding| finding.status == Status::Ok)
+    }
+
+    pub fn problems(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(|finding| finding.status != Status::Ok)
+    }
+}
+
+/// Extracts the program id from every artifact of the package at `root`.
+pub fn examine(root: &Path) -> Report {
+    let manifest_path = root.join(MANIFEST_FILE);
+    let manifest = Manifest::load(&manifest_path);
+    let expected = manifest.as_ref().ok().map(|manifest| manifest.program.clone());
+    let mut findings = Vec::new();
+
+    let push = |findings: &mut Vec<Finding>, artifact, path, found, fix: &dyn Fn(&str) -> String| {
+        findings.push(compare(expected.as_deref(), artifact, path, found, fix));
+    };
+
+    match &manifest {
+        Ok(manifest) => {
+            let status = match manifest.program.strip_suffix(PROGRAM_SUFFIX).map(package::validate_program_name) {
+                Some(Ok(())) => Status::Ok,
+                Some(Err(error)) => Status::Mismatch { fix: format!("{error}; choose a valid name with `rename`") },
+                None => Status::Mismatch { fix: format!("append `{PROGRAM_SUFFIX}` to `\"program\"`") },
+            };
+            findings.push(Finding {
+                artifact: Artifact::Manifest,
+                path: manifest_path.clone(),
+                line: None,
+                found: Some(manifest.program.clone()),
+                status,
+            });
+        }
+        Err(error) => findings.push(Finding {
+            artifact: Artifact::Manifest,
+            path: manifest_path.clone(),
+            line: None,
+            found: None,
+            status: Status::Missing { fix: error.to_string() },
+        }),
+    }
+
+    let build_manifest = root.join("build").join(MANIFEST_FILE);
+    let found = Manifest::load(&build_manifest).ok().map(|manifest| (manifest.program, None));
+    push(&mut findings, Artifact::BuildManifest, build_manifest, found, &|expected| {
+        format!("rebuild with `leo build`, or set `\"program\": \"{expected}\"`")
+    });
+
+    let source = root.join("src").join("main.leo");
+    let found = package::read(&source).ok().and_then(|text| {
+        let (id, span) = crate::leo::program_id(&text)?;
+        Some((id, Some(span.start.line)))
+    });
+    push(&mut findings, Artifact::Source, source, found, &|expected| {
+        format!("change the header to `program {expected} {{`")
+    });
+
+    let build = root.join("build").join("main.aleo");
+    let found = package::read(&build)
+        .ok()
+        .and_then(|text| crate::aleo::program_id(&text).map(|(id, line)| (id.to_string(), Some(line))));
+    push(&mut findings, Artifact::BuildProgram, build, found, &|_| "rebuild with `leo build`".to_string());
+
+    let inputs = root.join("inputs");
+    let mut input_files: Vec<PathBuf> = std::fs::read_dir(&inputs)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+                .collect()
+        })
+        .unwrap_or_default();
+    input_files.sort();
+    let stem = |path: &Path| path.file_stem().map(|stem| format!("{}{PROGRAM_SUFFIX}", stem.to_string_lossy()));
+    let matching = input_files.iter().find(|path| stem(path) == expected);
+    let (path, found) = match matching.or(input_files.first()) {
+        Some(path) => (path.clone(), stem(path).map(|id| (id, None))),
+        None => (inputs.join("<name>.in"), None),
+    };
+    push(&mut findings, Artifact::InputFile, path.clone(), found, &|expected| {
+        let name = expected.trim_end_matches(PROGRAM_SUFFIX);
+        match path.file_name().filter(|_| path.exists()) {
+            Some(file) => format!("rename `inputs/{}` to `inputs/{name}.in`", file.to_string_lossy()),
+            None => format!("create `inputs/{name}.in`"),
+        }
+    });
+
+    let script = root.join("deploy.sh");
+    if script.exists() {
+        // deploy.sh appends `.aleo` itself, so a suffixed APPNAME deploys `x.aleo.aleo`.
+        let found = package::read(&script).ok().and_then(|text| {
+            package::deploy_script_appname(&text).map(|(name, line)| (format!("{name}{PROGRAM_SUFFIX}"), Some(line)))
+        });
+        push(&mut findings, Artifact::DeployScript, script, found, &|expected| {
+            format!("set `APPNAME=\"{}\"` (without `.aleo`)", expected.trim_end_matches(PROGRAM_SUFFIX))
+        });
+    }
+
+    let directory = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let found = directory.file_name().map(|name| (format!("{}{PROGRAM_SUFFIX}", name.to_string_lossy()), None));
+    push(&mut findings, Artifact::Directory, directory, found, &|expected| {
+        format!("rename the directory to `{}`", expected.trim_end_matches(PROGRAM_SUFFIX))
+    });
+
+    Report { expected, findings }
+}
+
+/// Compares what one artifact names against `expected`.
+fn compare(
+    expected: Option<&str>,
+    artifact: Artifact,
+    path: PathBuf,
+    found: Option<(String, Option<usize>)>,
+    fix: &dyn Fn(&str) -> String,
+) -> Finding {
+    let (found, line) = found.map_or((None, None), |(found, line)| (Some(found), line));
+    let status = match (expected, &found) {
+        (Some(expected), Some(found)) if expected == found => Status::Ok,
+        (Some(expected), Some(_)) => Status::Mismatch { fix: fix(expected) },
+        (Some(expected), None) => Status::Missing { fix: fix(expected) },
+        (None, _) => Status::Missing { fix: format!("fix `{MANIFEST_FILE}` first") },
+    };
+    Finding { artifact, path, line, found, status }
+}
+
+impl fmt::Display for Report {
+    /// Renders the findings as a table followed by the suggested fixes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<[String; 4]> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let location = match finding.line {
+                    Some(line) => format!("{}:{line}", finding.path.display()),
+                    None => finding.path.display().to_string(),
+                };
+                let status = match finding.status {
+                    Status::Ok => "ok",
+                    Status::Mismatch { .. } => "MISMATCH",
+                    Status::Missing { .. } => "MISSING",
+                };
+                [
+                    finding.artifact.to_string(),
+                    finding.found.clone().unwrap_or_else(|| "-".to_string()),
+                    status.to_string(),
+                    location,
+                ]
+            })
+            .collect();
+        let header = ["ARTIFACT", "PROGRAM ID", "STATUS", "LOCATION"].map(String::from);
+        let mut widths = [0; 4];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for row in std::iter::once(&header).chain(&rows) {
+            let line = row.iter().zip(widths).map(|(cell, width)| format!("{cell:width$}")).collect::<Vec<_>>();
+            writeln!(f, "{}", line.join("  ").trim_end())?;
+        }
+
+        let problems: Vec<&Finding> = self.problems().collect();
+        if !problems.is_empty() {
+            writeln!(f)?;
+            match &self.expected {
+                Some(expected) => writeln!(f, "expected every artifact to name `{expected}`; suggested fixes:")?,
+                None => writeln!(f, "suggested fixes:")?,
+            }
+            for finding in problems {
+                if let Status::Mismatch { fix } | Status::Missing { fix } = &finding.status {
+                    writeln!(f, "  - {}: {fix}", finding.artifact)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+//! Explaining what `leo` and `snarkos` print when they fail.
+//!
+//! [`recognize`] matches captured output against a catalog of failures the
+//! workshop runs into, the ones `FAQ.md` describes among them, and [`locate`]
+//! finds what to change in the package: the input that is missing its `;`,
+//! the program name to make unique, the fee to raise, or the instruction that
+//! halted.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::aleo::{parse, Program};
+use crate::arithmetic::{admin, caller_for, explain as explain_halt, section_inputs};
+use crate::diagnostic::{Diagnostic, Position, Span};
+use crate::fee::{FeeEstimate, DEFAULT_MARGIN_PERCENT};
+use crate::inputs;
+use crate::interpreter::{InterpretError, Interpreter, ZERO_ADDRESS};
+use crate::network::{NetworksConfig, CONFIG_FILE};
+use crate::package::{read, Package, MANIFEST_FILE};
+
+/// A known failure, with what the output said about it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// `leo run` read fewer or more inputs than the function takes, most
+    /// often because an input in the file is missing its semicolon.
+    InputCount { function: String, expected: usize, found: usize },
+    /// Leo could not parse a file, e.g. `expected ; -- found 'amount'`.
+    Syntax { message: String, file: String, position: Option<Position> },
+    /// The fee record cannot pay the deployment fee.
+    InsufficientFee,
+    /// A program with the same id is already deployed.
+    ProgramExists { program: String },
+    /// A checked integer instruction halted.
+    Arithmetic { instruction: String, underflow: bool },
+}
+
+impl Failure {
+    /// Why it happens, in the FAQ's terms.
+    pub fn cause(&self) -> String {
+        match self {
+            Failure::InputCount { function, .. } => format!(
+                "the `[{function}]` section of the input file does not give `{function}` its inputs; an input without \
+                 its closing `;` (including struct and record literals) stops Leo reading the rest"
+            ),
+            Failure::Syntax { file, .. } if file.ends_with(".in") => {
+                "the input file does not parse; every input, including struct and record literals, ends with `;`"
+                    .to_string()
+            }
+            Failure::Syntax { .. } => "the Leo source does not parse".to_string(),
+            Failure::InsufficientFee => "either the program name is already taken, since deployed names must be \
+                                         globally unique, or the fee is lower than the deployment costs"
+                .to_string(),
+            Failure::ProgramExists { program } => {
+                format!("`{program}` is already deployed; program names must be globally unique")
+            }
+            Failure::Arithmetic { underflow: true, .. } => {
+                "a `sub` went below zero, e.g. spending more than a record or balance holds".to_string()
+            }
+            Failure::Arithmetic { .. } => "an integer instruction went past the largest value of its type".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::InputCount { function, expected, found } => {
+                write!(f, "`{function}` expects {expected} input(s), but {found} were found")
+            }
+            Failure::Syntax { message, file, .. } => write!(f, "`{file}` does not parse: {message}"),
+            Failure::InsufficientFee => f.write_str("the fee record does not have enough balance to pay the fee"),
+            Failure::ProgramExists { program } => write!(f, "`{program}` already exists"),
+            Failure::Arithmetic { instruction, underflow } => {
+                let what = if *underflow { "underflowed" } else { "overflowed" };
+                write!(f, "`{instruction}` {what}")
+            }
+        }
+    }
+}
+
+/// A failure recognized in the output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recognized {
+    pub failure: Failure,
+    /// The 1-based line of the output it was recognized on.
+    pub line: usize,
+}
+
+/// A place in the package to fix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub diagnostic: Diagnostic,
+}
+
+/// Finds every known failure in captured output, in order. Colors are
+/// ignored, and a failure repeated on consecutive lines is reported once.
+pub fn recognize(output: &str) -> Vec<Recognized> {
+    let lines: Vec<String> = output.lines().map(strip_ansi).collect();
+    let mut found: Vec<Recognized> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let failure = input_count(line)
+            .or_else(|| syntax(line, &lines[index + 1..]))
+            .or_else(|| line.contains("does not have enough balance").then_some(Failure::InsufficientFee))
+            .or_else(|| program_exists(line))
+            .or_else(|| arithmetic(line));
+        if let Some(failure) = failure {
+            if found.last().is_none_or(|last| last.failure != failure) {
+                found.push(Recognized { failure, line: index + 1 });
+            }
+        }
+    }
+    found
+}
+
+/// `Function 'mint' in the program 'token.aleo' expects 1 inputs, but 0 inputs were found.`
+fn input_count(line: &str) -> Option<Failure> {
+    let function = between(line, "Function '", "'")?;
+    let expected = between(line, "expects ", " input")?.trim().parse().ok()?;
+    let found = between(line, "but ", " input")?.trim().parse().ok()?;
+    Some(Failure::InputCount { function: function.to_string(), expected, found })
+}
+
+/// `Error [EPAR0370005]: expected ; -- found 'amount'` followed by
+/// `--> path/to/file.in:3:1`.
+fn syntax(line: &str, rest: &[String]) -> Option<Failure> {
+    let (_, message) = line.split_once("Error [EPAR")?.1.split_once("]: ")?;
+    let location = rest.iter().take(3).find_map(|line| line.trim().strip_prefix("--> ").map(str::trim));
+    let (file, position) = match location {
+        Some(location) => {
+            let mut parts = location.rsplitn(3, ':');
+            let column = parts.next().and_then(|column| column.parse().ok());
+            let line = parts.next().and_then(|line| line.parse().ok());
+            match (parts.next(), line, column) {
+                (Some(file), Some(line), Some(column)) => (file.to_string(), Some(Position::new(line, column))),
+                _ => (location.to_string(), None),
+            }
+        }
+        None => (String::new(), None),
+    };
+    Some(Failure::Syntax { message: message.trim().to_string(), file, position })
+}
+
+/// `Program 'token.aleo' already exists`, as snarkOS and the explorer API
+/// word it.
+fn program_exists(line: &str) -> Option<Failure> {
+    if !line.contains("already exists") {
+        return None;
+    }
+    let program = line.split('\'').find(|part| part.ends_with(".aleo") && !part.contains(' '))?;
+    Some(Failure::ProgramExists { program: program.to_string() })
+}
+
+/// `Failed to evaluate instruction (sub r0.balance r1 into r2;): Integer underflow ...`
+fn arithmetic(line: &str) -> Option<Failure> {
+    let lower = line.to_lowercase();
+    let underflow = lower.contains("underflow");
+    if !underflow && !lower.contains("overflow") {
+        return None;
+    }
+    let instruction = between(line, "instruction (", ")")?.trim_end_matches(';').trim();
+    Some(Failure::Arithmetic { instruction: instruction.to_string(), underflow })
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(start)?;
+    Some(rest.split_once(end)?.0)
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip to the end of the escape sequence, a letter.
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Where in `package` to fix `failure`. Files that cannot be read are
+/// skipped, so the result may be empty.
+pub fn locate(failure: &Failure, package: &Package) -> Vec<Location> {
+    match failure {
+        Failure::InputCount { function, expected, .. } => locate_input_section(package, function, *expected),
+        Failure::Syntax { message, file, position } => {
+            let name = file.rsplit(['/', '\\']).next().unwrap_or(file);
+            let path = if name.ends_with(".in") { package.inputs_dir().join(name) } else { package.source_path() };
+            let Some(position) = position else { return Vec::new() };
+            let mut diagnostic = Diagnostic::error(Span::new(*position, *position), message.clone());
+            if message.starts_with("expected ;") {
+                diagnostic = diagnostic.with_help("add the missing `;` at the end of the statement before this one");
+            }
+            vec![Location { path, diagnostic }]
+        }
+        Failure::InsufficientFee => {
+            let mut locations = locate_program_name(package);
+            locations.extend(locate_fee(package));
+            locations
+        }
+        Failure::ProgramExists { .. } => locate_program_name(package),
+        Failure::Arithmetic { instruction, .. } => locate_instruction(package, instruction),
+    }
+}
+
+/// A span covering `needle` on the first line containing it.
+fn find(source: &str, needle: &str) -> Option<Span> {
+    source.lines().enumerate().find_map(|(index, line)| {
+        let column = line.find(needle)? + 1;
+        let start = Position::new(index + 1, column);
+        Some(Span::new(start, Position::new(index + 1, column + needle.len())))
+    })
+}
+
+fn locate_input_section(package: &Package, function: &str, expected: usize) -> Vec<Location> {
+    let path = package.input_path();
+    let Ok(source) = read(&path) else { return Vec::new() };
+    let (file, diagnostics) = inputs::parse(&source);
+    let Some(section) = file.section(function) else {
+        let diagnostic = Diagnostic::error(Span::new(Position::new(1, 1), Position::new(1, 1)), "no input section")
+            .with_help(format!("add a `[{function}]` section with {expected} input(s)"));
+        return vec![Location { path, diagnostic }];
+    };
+    let start = section.name.span.start.line;
+    let end = file
+        .sections
+        .iter()
+        .map(|other| other.name.span.start.line)
+        .filter(|&line| line > start)
+        .min()
+        .unwrap_or(usize::MAX);
+    let inside: Vec<Location> = diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.is_error() && (start..end).contains(&diagnostic.span.start.line))
+        .map(|diagnostic| Location { path: path.clone(), diagnostic })
+        .collect();
+    if !inside.is_empty() {
+        return inside;
+    }
+    let diagnostic = Diagnostic::error(
+        section.name.span,
+        format!("`[{function}]` has {} input(s); the function takes {expected}", section.inputs.len()),
+    )
+    .with_help("check that every input in the section ends with `;`");
+    vec![Location { path, diagnostic }]
+}
+
+fn locate_program_name(package: &Package) -> Vec<Location> {
+    let path = package.root().join(MANIFEST_FILE);
+    let Some(span) = read(&path).ok().and_then(|source| find(&source, package.program_id())) else {
+        return Vec::new();
+    };
+    let diagnostic = Diagnostic::error(span, format!("`{}` must not be deployed already", package.program_id()))
+        .with_help(
+            "pick a unique name and apply it to every file with `cargo run --bin rename -- <package> <new-name>`",
+        );
+    vec![Location { path, diagnostic }]
+}
+
+fn locate_fee(package: &Package) -> Vec<Location> {
+    let build = package.build_program_path();
+    let Some((program, source)) = read(&build).ok().and_then(|source| Some((parse(&source).ok()?, source))) else {
+        return Vec::new();
+    };
+    let estimate = FeeEstimate::new(&program, &source);
+    let suggested = estimate.suggested_fee(DEFAULT_MARGIN_PERCENT);
+    let help =
+        format!("`estimate-fee` puts the deployment at about {} microcredits; pay {suggested}", estimate.total());
+
+    let mut locations = Vec::new();
+    let script = package.deploy_script_path();
+    let source = read(&script).unwrap_or_default();
+    if let Some(span) = find(&source, "--fee ") {
+        let line = source.lines().nth(span.start.line - 1).unwrap_or_default();
+        let digits = &line[span.end.column - 1..];
+        let digits = &digits[..digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len())];
+        if let Some(fee) = digits.parse::<u64>().ok().filter(|&fee| fee < estimate.total()) {
+            let end = Position::new(span.start.line, span.end.column + digits.len());
+            let diagnostic =
+                Diagnostic::error(Span::new(span.start, end), format!("`deploy.sh` pays {fee} microcredits"))
+                    .with_help(help.clone());
+            locations.push(Location { path: script, diagnostic });
+        }
+    }
+    let config_path = package.root().join(CONFIG_FILE);
+    if let Ok(config) = NetworksConfig::load(&config_path) {
+        if let Ok((network, profile)) = config.select(None) {
+            let source = read(&config_path).unwrap_or_default();
+            let span = find(&source, &format!("fee = {}", profile.fee));
+            if let (Some(span), true) = (span, profile.fee < estimate.total()) {
+                let diagnostic =
+                    Diagnostic::error(span, format!("`{network}` pays {} microcredits", profile.fee)).with_help(help);
+                locations.push(Location { path: config_path, diagnostic });
+            }
+        }
+    }
+    locations
+}
+
+fn locate_instruction(package: &Package, instruction: &str) -> Vec<Location> {
+    let build = package.build_program_path();
+    let Ok(source) = read(&build) else { return Vec::new() };
+    let Ok(program) = parse(&source) else { return Vec::new() };
+    let mut locations = Vec::new();
+    let mut function = None;
+    for (index, line) in source.lines().enumerate() {
+        if let Some(name) = line.strip_prefix("function ").and_then(|rest| rest.strip_suffix(':')) {
+            function = Some(name.trim().to_string());
+        }
+        if line.trim().trim_end_matches(';') != instruction {
+            continue;
+        }
+        let column = line.len() - line.trim_start().len() + 1;
+        let span = Span::new(Position::new(index + 1, column), Position::new(index + 1, line.trim_end().len() + 1));
+        let diagnostic =
+            function.as_deref().and_then(|function| replay(package, &program, function, span)).unwrap_or_else(|| {
+                Diagnostic::error(span, format!("`{instruction}` halts here")).with_help(
+                    "assert the operands first to fail with a clear error; `check-arithmetic` shows which inputs halt",
+                )
+            });
+        locations.push(Location { path: build.clone(), diagnostic });
+    }
+    locations
+}
+
+/// Runs the input file's section for `function` and, if it halts on the line
+/// of `span`, explains the halt in terms of the input values.
+fn replay(package: &Package, program: &Program, function: &str, span: Span) -> Option<Diagnostic> {
+    let (file, _) = inputs::parse(&read(&package.input_path()).ok()?);
+    let values = section_inputs(file.section(function)?).ok()?;
+    let admin = admin(program);
+    let mut interpreter = Interpreter::new(program, caller_for(&values, admin.as_deref().unwrap_or(ZERO_ADDRESS)));
+    match interpreter.execute(function, values) {
+        Err(error @ InterpretError::Arithmetic { span: at, .. }) if at.start.line == span.start.line => {
+            Some(explain_halt(program, &error))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,119 @@
+//! Estimating what deploying a compiled program costs.
+//!
+//! snarkVM charges a deployment for three things, in microcredits:
+//!
+//! - storage: 1,000 per byte of the deployment, which is the program plus a
+//!   verifying key and certificate for every function;
+//! - synthesis: 25 per constraint of the functions' circuits;
+//! - namespace: `10^(10 - length)` credits for a program name shorter than
+//!   ten characters, and one credit otherwise.
+//!
+//! Storage and namespace follow from the program text. Constraint counts
+//! need the circuits synthesized, so [`FeeEstimate`] approximates them from
+//! the functions, their record inputs and outputs and their instructions,
+//! and the fee to pay should include a margin on top.
+
+use crate::aleo::{Program, ValueType};
+
+/// Microcredits in one credit.
+pub const MICROCREDITS_PER_CREDIT: u64 = 1_000_000;
+
+/// Storage cost per byte of the deployment.
+pub const STORAGE_COST_PER_BYTE: u64 = 1_000;
+
+/// Synthesis cost per constraint.
+pub const SYNTHESIS_COST_PER_CONSTRAINT: u64 = 25;
+
+/// Program names at least this long pay the smallest namespace cost.
+pub const NAMESPACE_FREE_LENGTH: u32 = 10;
+
+/// Approximate bytes of a verifying key and certificate, added per function.
+pub const KEY_BYTES_PER_FUNCTION: u64 = 1_000;
+
+/// Approximate constraints every function has, for the request it checks.
+pub const CONSTRAINTS_PER_FUNCTION: u64 = 5_000;
+
+/// Approximate constraints per record input or output, which is committed to
+/// and encrypted.
+pub const CONSTRAINTS_PER_RECORD: u64 = 10_000;
+
+/// Approximate constraints per instruction; integer arithmetic on wide types
+/// costs more and moves less.
+pub const CONSTRAINTS_PER_INSTRUCTION: u64 = 200;
+
+/// The margin [`FeeEstimate::suggested_fee`] adds, in percent.
+pub const DEFAULT_MARGIN_PERCENT: u64 = 20;
+
+/// What a program's deployment is charged for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The program name without `.aleo`.
+    pub name: String,
+    /// The compiled program text.
+    pub program_bytes: u64,
+    pub functions: u64,
+    /// Record inputs and outputs across all functions.
+    pub records: u64,
+    /// Instructions across all functions, not counting finalize blocks,
+    /// which run on-chain rather than in a circuit.
+    pub instructions: u64,
+}
+
+impl FeeEstimate {
+    /// Counts what `program`, compiled to `source`, is charged for.
+    pub fn new(program: &Program, source: &str) -> Self {
+        let functions: Vec<_> = program.functions().collect();
+        let is_record = |ty: &ValueType| matches!(ty, ValueType::Record(_) | ValueType::ExternalRecord(_));
+        let records = functions
+            .iter()
+            .map(|function| {
+                function.inputs.iter().filter(|input| is_record(&input.ty)).count()
+                    + function.outputs.iter().filter(|output| is_record(&output.ty)).count()
+            })
+            .sum::<usize>();
+        Self {
+            name: program.id.name.name.clone(),
+            program_bytes: source.len() as u64,
+            functions: functions.len() as u64,
+            records: records as u64,
+            instructions: functions.iter().map(|function| function.instructions.len() as u64).sum(),
+        }
+    }
+
+    /// The program and the functions' keys and certificates.
+    pub fn storage_bytes(&self) -> u64 {
+        self.program_bytes + self.functions * KEY_BYTES_PER_FUNCTION
+    }
+
+    pub fn storage_cost(&self) -> u64 {
+        self.storage_bytes() * STORAGE_COST_PER_BYTE
+    }
+
+    /// The approximate constraints of all functions together.
+    pub fn constraints(&self) -> u64 {
+        self.functions * CONSTRAINTS_PER_FUNCTION
+            + self.records * CONSTRAINTS_PER_RECORD
+            + self.instructions * CONSTRAINTS_PER_INSTRUCTION
+    }
+
+    pub fn synthesis_cost(&self) -> u64 {
+        self.constraints() * SYNTHESIS_COST_PER_CONSTRAINT
+    }
+
+    /// The surcharge for short program names: 1 credit from ten characters
+    /// up, ten times more for every character fewer.
+    pub fn namespace_cost(&self) -> u64 {
+        let length = u32::try_from(self.name.chars().count()).unwrap_or(u32::MAX);
+        10u64.pow(NAMESPACE_FREE_LENGTH.saturating_sub(length)) * MICROCREDITS_PER_CREDIT
+    }
+
+    pub fn total(&self) -> u64 {
+        self.storage_cost() + self.synthesis_cost() + self.namespace_cost()
+    }
+
+    /// The total plus `margin_percent`, rounded up to a whole credit.
+    pub fn suggested_fee(&self, margin_percent: u64) -> u64 {
+        let with_margin = self.total() + (self.total() * margin_percent).div_ceil(100);
+        with_margin.div_ceil(MICROCREDITS_PER_CREDIT) * MICROCREDITS_PER_CREDIT
+    }
+}
@@ -0,0 +1,296 @@
+use crate::address::Address;
+use crate::aleo::{Program, ValueType};
+use crate::diagnostic::{Diagnostic, Position, Span};
+use crate::leo::Transition;
+
+use super::{aleo_type_name, Input, InputFile, Literal, Section, StructLiteral, Value};
+
+/// The member every record literal carries besides its declared members.
+const NONCE: &str = "_nonce";
+
+/// Cross-checks every section of `file` against the function of the same
+/// name in `program`.
+///
+/// `transitions` are the Leo signatures from `src/main.leo`; when given, the
+/// input names are checked against the parameter names as well, which is how
+/// `leo run` matches them up.
+pub fn check(file: &InputFile, program: &Program, transitions: &[Transition]) -> Vec<Diagnostic> {
+    let mut checker = Checker { program, diagnostics: Vec::new() };
+    let mut seen: Vec<&str> = Vec::new();
+    for section in &file.sections {
+        if seen.contains(&section.name.name.as_str()) {
+            checker.diagnostics.push(
+                Diagnostic::error(
+                    section.name.span,
+                    format!("section `[{}]` is declared more than once", section.name.name),
+                )
+                .with_help("leo only reads the first one; merge or remove the duplicate"),
+            );
+            continue;
+        }
+        seen.push(&section.name.name);
+        let transition = transitions.iter().find(|transition| transition.name == section.name.name);
+        checker.section(section, transition);
+    }
+    checker.diagnostics
+}
+
+/// The members of a struct or record, by name and type.
+struct Shape<'a> {
+    name: &'a str,
+    members: Vec<(&'a str, String)>,
+}
+
+struct Checker<'a> {
+    program: &'a Program,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    fn shape(&self, name: &str, is_record: bool) -> Option<Shape<'a>> {
+        let program: &'a Program = self.program;
+        if is_record {
+            let record = program.record(name)?;
+            let members = record.members.iter().map(|member| (member.name.name.as_str(), member.ty.to_string()));
+            Some(Shape { name: &record.name.name, members: members.collect() })
+        } else {
+            let struct_ = program.struct_(name)?;
+            let members = struct_.members.iter().map(|member| (member.name.name.as_str(), member.ty.to_string()));
+            Some(Shape { name: &struct_.name.name, members: members.collect() })
+        }
+    }
+
+    fn section(&mut self, section: &Section, transition: Option<&Transition>) {
+        let Some(function) = self.program.function(&section.name.name) else {
+            let names: Vec<&str> = self.program.functions().map(|function| function.name.name.as_str()).collect();
+            self.diagnostics.push(
+                Diagnostic::error(
+                    section.name.span,
+                    format!("`{}` has no function named `{}`", self.program.id, section.name.name),
+                )
+                .with_help(format!("the program defines: {}", names.join(", "))),
+            );
+            return;
+        };
+
+        let expected = function.inputs.len();
+        let found = section.inputs.len();
+        if expected != found {
+            let mut diagnostic = Diagnostic::error(
+                section.name.span,
+                format!("function `{}` expects {expected} input(s), but {found} are declared", function.name.name),
+            );
+            if let Some(transition) = transition.filter(|_| found < expected) {
+                let missing: Vec<String> = transition.parameters[found.min(transition.parameters.len())..]
+                    .iter()
+                    .map(|parameter| format!("`{}: {}`", parameter.name, parameter.ty))
+                    .collect();
+                diagnostic = diagnostic.with_help(format!("add {}", missing.join(", ")));
+            }
+            self.diagnostics.push(diagnostic);
+        }
+
+        let mut seen: Vec<&str> = Vec::new();
+        for (position, input) in section.inputs.iter().enumerate() {
+            if seen.contains(&input.name.name.as_str()) {
+                self.diagnostics.push(Diagnostic::error(
+                    input.name.span,
+                    format!("input `{}` is declared more than once", input.name.name),
+                ));
+            }
+            seen.push(&input.name.name);
+
+            if let Some(transition) = transition {
+                self.name(input, position, transition);
+            }
+            if let Some(expected) = function.inputs.get(position) {
+                self.input(input, &expected.ty);
+            }
+        }
+    }
+
+    fn name(&mut self, input: &Input, position: usize, transition: &Transition) {
+        let Some(parameter) = transition.parameters.get(position) else {
+            return;
+        };
+        if parameter.name == input.name.name {
+            return;
+        }
+        let message = match transition.parameters.iter().position(|parameter| parameter.name == input.name.name) {
+            Some(index) => format!(
+                "input `{}` is parameter {} of `{}`, but is declared at position {}",
+                input.name.name,
+                index + 1,
+                transition.name,
+                position + 1
+            ),
+            None => format!("`{}` has no parameter named `{}`", transition.name, input.name.name),
+        };
+        self.diagnostics.push(Diagnostic::error(input.name.span, message).with_help(format!(
+            "parameter {} is `{}: {}`",
+            position + 1,
+            parameter.name,
+            parameter.ty
+        )));
+    }
+
+    fn input(&mut self, input: &Input, expected: &ValueType) {
+        let (expected_ty, is_record) = match expected {
+            ValueType::Plaintext(ty, _) => (ty.to_string(), false),
+            ValueType::Record(name) => (name.name.clone(), true),
+            // Records of imported programs cannot be checked without the import.
+            ValueType::ExternalRecord(_) => return,
+        };
+        let declared = aleo_type_name(&input.ty.name);
+        if declared != expected_ty {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    input.ty.span,
+                    format!(
+                        "`{}` is declared as `{}`, but the function takes `{expected}`",
+                        input.name.name, input.ty.name
+                    ),
+                )
+                .with_help(format!("change the type to `{expected_ty}`")),
+            );
+            return;
+        }
+        self.value(&input.value, &expected_ty, is_record);
+    }
+
+    fn value(&mut self, value: &Value, expected: &str, is_record: bool) {
+        match value {
+            Value::Literal(literal) => self.literal(literal, expected),
+            Value::Struct(literal) => match self.shape(expected, is_record) {
+                Some(composite) => self.struct_literal(literal, composite, is_record),
+                None => self.diagnostics.push(Diagnostic::error(
+                    literal.span,
+                    format!("expected a `{expected}` value, found a `{}` literal", literal.name.name),
+                )),
+            },
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal, expected: &str) {
+        let Some(found) = literal.ty() else {
+            let help = match self.shape(expected, false).or_else(|| self.shape(expected, true)) {
+                Some(composite) => format!("write a `{} {{ ... }}` literal", composite.name),
+                None => format!("write a `{expected}` literal"),
+            };
+            self.diagnostics.push(
+                Diagnostic::error(literal.span, format!("`{}` is not a valid literal", literal.text)).with_help(help),
+            );
+            return;
+        };
+        if found != expected {
+            self.diagnostics.push(Diagnostic::error(
+                literal.span,
+                format!("expected a `{expected}` value, found `{}` of type `{found}`", literal.text),
+            ));
+            return;
+        }
+        if let Some(message) = out_of_range(literal, found) {
+            self.diagnostics.push(Diagnostic::error(literal.span, message));
+        }
+        if found == "address" {
+            if let Err(error) = Address::parse(&literal.text) {
+                // Point at the offending character when the error is about one.
+                let span = match error.index() {
+                    Some(index) => {
+                        let start = Position { column: literal.span.start.column + index, ..literal.span.start };
+                        Span { start, end: Position { column: start.column + 1, ..start } }
+                    }
+                    None => literal.span,
+                };
+                self.diagnostics.push(Diagnostic::error(span, format!("invalid address: {error}")));
+            }
+        }
+    }
+
+    fn struct_literal(&mut self, literal: &StructLiteral, composite: Shape<'_>, is_record: bool) {
+        if literal.name.name != composite.name {
+            self.diagnostics.push(Diagnostic::error(
+                literal.name.span,
+                format!("expected a `{}` literal, found `{}`", composite.name, literal.name.name),
+            ));
+            return;
+        }
+
+        for (name, value) in &literal.members {
+            if is_record && name.name == NONCE {
+                self.literal_of(value, "group");
+                continue;
+            }
+            match composite.members.iter().find(|(member, _)| *member == name.name) {
+                // Records cannot nest, so members are always plaintext.
+                Some((_, ty)) => self.value(value, ty, false),
+                None => self.diagnostics.push(Diagnostic::error(
+                    name.span,
+                    format!("`{}` has no member named `{}`", composite.name, name.name),
+                )),
+            }
+        }
+
+        let present = |name: &str| literal.members.iter().any(|(member, _)| member.name == name);
+        let mut missing: Vec<&str> =
+            composite.members.iter().map(|(name, _)| *name).filter(|name| !present(name)).collect();
+        if is_record && !present(NONCE) {
+            missing.push(NONCE);
+        }
+        if !missing.is_empty() {
+            let mut diagnostic = Diagnostic::error(
+                closing(literal.span),
+                format!("`{}` literal is missing {}", composite.name, quote_all(&missing)),
+            );
+            if missing.contains(&NONCE) {
+                diagnostic = diagnostic
+                    .with_help("copy the `_nonce` of the record printed by `leo run`, e.g. `_nonce: 123...group`");
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn literal_of(&mut self, value: &Value, expected: &str) {
+        match value {
+            Value::Literal(literal) => self.literal(literal, expected),
+            Value::Struct(literal) => self.diagnostics.push(Diagnostic::error(
+                literal.span,
+                format!("expected a `{expected}` value, found a `{}` literal", literal.name.name),
+            )),
+        }
+    }
+}
+
+/// Checks an integer literal against the bounds of its type.
+fn out_of_range(literal: &Literal, ty: &str) -> Option<String> {
+    let (min, max): (i128, u128) = match ty {
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" => (0, u64::MAX.into()),
+        "u128" => (0, u128::MAX),
+        "i8" => (i8::MIN.into(), i8::MAX as u128),
+        "i16" => (i16::MIN.into(), i16::MAX as u128),
+        "i32" => (i32::MIN.into(), i32::MAX as u128),
+        "i64" => (i64::MIN.into(), i64::MAX as u128),
+        "i128" => (i128::MIN, i128::MAX as u128),
+        _ => return None,
+    };
+    let digits: String = literal.text.trim_end_matches(ty).chars().filter(|c| *c != '_').collect();
+    let in_range = match digits.strip_prefix('-') {
+        Some(magnitude) => magnitude.parse::<u128>().is_ok_and(|magnitude| magnitude <= min.unsigned_abs()),
+        None => digits.parse::<u128>().is_ok_and(|value| value <= max),
+    };
+    (!in_range).then(|| format!("`{}` does not fit in `{ty}` (range {min}..={max})", literal.text))
+}
+
+/// The position of the closing brace of a struct literal.
+fn closing(span: Span) -> Span {
+    let mut start = span.end;
+    start.column = start.column.saturating_sub(1).max(1);
+    Span::new(start, span.end)
+}
+
+fn quote_all(names: &[&str]) -> String {
+    names.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,112 @@
+//! Leo program input files (`inputs/*.in`).
+//!
+//! An input file is a list of `[function]` sections, each holding typed
+//! assignments such as `amount: u32 = 10u32;` or record literals like
+//! `input: Token = Token { owner: aleo1..., balance: 100u32, _nonce: ...group };`.
+//! [`parse`] reads one into an [`InputFile`], and [`check`] cross-checks it
+//! against the compiled program and the Leo transition signatures.
+//! [`record`] converts records printed by `leo run` into input literals.
+
+mod check;
+mod parser;
+pub mod record;
+
+pub use check::check;
+pub use parser::parse;
+
+use crate::diagnostic::Span;
+
+/// An identifier with its location.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputFile {
+    pub sections: Vec<Section>,
+}
+
+impl InputFile {
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name.name == name)
+    }
+}
+
+/// A `[name]` header and the assignments that follow it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section {
+    pub name: Ident,
+    pub inputs: Vec<Input>,
+}
+
+/// `[visibility] name: type = value;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    pub visibility: Option<Ident>,
+    pub name: Ident,
+    pub ty: Ident,
+    pub value: Value,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Literal(Literal),
+    Struct(StructLiteral),
+}
+
+impl Value {
+    pub fn span(&self) -> Span {
+        match self {
+            Value::Literal(literal) => literal.span,
+            Value::Struct(literal) => literal.span,
+        }
+    }
+}
+
+/// A single literal token such as `10u32`, `true` or `aleo1...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal {
+    pub text: String,
+    pub span: Span,
+}
+
+/// The suffixes a numeric literal may carry, which double as type names.
+pub const LITERAL_SUFFIXES: &[&str] =
+    &["u128", "u64", "u32", "u16", "u8", "i128", "i64", "i32", "i16", "i8", "field", "group", "scalar"];
+
+impl Literal {
+    /// Infers the Aleo type of the literal from its text, if it is one.
+    pub fn ty(&self) -> Option<&'static str> {
+        let text = self.text.as_str();
+        match text {
+            "true" | "false" => return Some("boolean"),
+            _ if text.starts_with("aleo1") => return Some("address"),
+            _ => {}
+        }
+        let digits = text.strip_prefix('-').unwrap_or(text);
+        LITERAL_SUFFIXES.iter().copied().find(|suffix| {
+            digits
+                .strip_suffix(suffix)
+                .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit() || b == b'_'))
+        })
+    }
+}
+
+/// `Name { member: value, ... }`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructLiteral {
+    pub name: Ident,
+    pub members: Vec<(Ident, Value)>,
+    pub span: Span,
+}
+
+/// Maps a Leo type name onto the Aleo instructions spelling.
+pub fn aleo_type_name(leo: &str) -> &str {
+    match leo {
+        "bool" => "boolean",
+        other => other,
+    }
+}
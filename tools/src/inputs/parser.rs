@@ -0,0 +1,345 @@
+use crate::diagnostic::{Diagnostic, Position, Span};
+
+use super::{Ident, Input, InputFile, Literal, Section, StructLiteral, Value};
+
+const VISIBILITIES: &[&str] = &["public", "private", "constant", "const"];
+
+const MISSING_SEMICOLON_HELP: &str =
+    "every input, including struct and record literals, must end with `;`; otherwise leo reports \
+     \"expects N inputs, but 0 inputs were found\"";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Word,
+    Punct(char),
+    Eof,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: Kind,
+    text: String,
+    span: Span,
+    /// Whether this is the first token on its line.
+    line_start: bool,
+}
+
+fn tokenize(source: &str, diagnostics: &mut Vec<Diagnostic>) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line, mut column) = (0, 1, 1);
+    let mut line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = Position::new(line, column);
+        if c == '\n' {
+            i += 1;
+            line += 1;
+            column = 1;
+            line_start = true;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            column += 1;
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+                column += 1;
+            }
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            column += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+            column += 2;
+            continue;
+        }
+
+        let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let kind = if is_word(c) || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) {
+            let begin = i;
+            i += 1;
+            while i < chars.len() && is_word(chars[i]) {
+                i += 1;
+            }
+            column += i - begin;
+            tokens.push(Token {
+                kind: Kind::Word,
+                text: chars[begin..i].iter().collect(),
+                span: Span::new(start, Position::new(line, column)),
+                line_start,
+            });
+            line_start = false;
+            continue;
+        } else if "[]:=;,{}.".contains(c) {
+            Kind::Punct(c)
+        } else {
+            i += 1;
+            column += 1;
+            diagnostics.push(Diagnostic::error(
+                Span::new(start, Position::new(line, column)),
+                format!("unexpected character `{c}`"),
+            ));
+            continue;
+        };
+        i += 1;
+        column += 1;
+        tokens.push(Token {
+            kind,
+            text: c.to_string(),
+            span: Span::new(start, Position::new(line, column)),
+            line_start,
+        });
+        line_start = false;
+    }
+
+    let end = Position::new(line, column);
+    tokens.push(Token { kind: Kind::Eof, text: String::new(), span: Span::new(end, end), line_start: true });
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses an input file, recovering from errors where possible.
+///
+/// The returned file holds every section and input that could be read; the
+/// diagnostics describe everything that could not.
+pub fn parse(source: &str) -> (InputFile, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let tokens = tokenize(source, &mut diagnostics);
+    let mut parser = Parser { tokens, index: 0, diagnostics };
+    let file = parser.file();
+    (file, parser.diagnostics)
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        &self.tokens[(self.index + n).min(self.tokens.len() - 1)]
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.tokens[self.index].clone();
+        if token.kind != Kind::Eof {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek().kind == Kind::Punct(c)
+    }
+
+    fn eat(&mut self, c: char) -> Option<Token> {
+        self.is_punct(c).then(|| self.bump())
+    }
+
+    fn describe(token: &Token) -> String {
+        match token.kind {
+            Kind::Eof => "end of file".to_string(),
+            _ => format!("`{}`", token.text),
+        }
+    }
+
+    fn expect(&mut self, c: char, context: &str) -> Option<Token> {
+        if let Some(token) = self.eat(c) {
+            return Some(token);
+        }
+        let found = Self::describe(self.peek());
+        self.diagnostics.push(Diagnostic::error(self.peek().span, format!("expected `{c}` {context}, found {found}")));
+        None
+    }
+
+    fn ident(&mut self, what: &str) -> Option<Ident> {
+        if self.peek().kind == Kind::Word {
+            let token = self.bump();
+            return Some(Ident { name: token.text, span: token.span });
+        }
+        let found = Self::describe(self.peek());
+        self.diagnostics.push(Diagnostic::error(self.peek().span, format!("expected {what}, found {found}")));
+        None
+    }
+
+    /// Whether the next token starts a new section or assignment.
+    fn at_item_start(&self) -> bool {
+        let token = self.peek();
+        match token.kind {
+            Kind::Eof => true,
+            Kind::Punct('[') => token.line_start,
+            Kind::Word if token.line_start => {
+                self.peek_nth(1).kind == Kind::Punct(':')
+                    || (VISIBILITIES.contains(&token.text.as_str()) && self.peek_nth(2).kind == Kind::Punct(':'))
+            }
+            _ => false,
+        }
+    }
+
+    /// Skips past the item that began at token `start` after an error,
+    /// stopping after its `;` or before the next section or input.
+    fn recover(&mut self, start: usize) {
+        if self.index == start {
+            self.bump();
+        }
+        while !self.at_item_start() {
+            if self.bump().kind == Kind::Punct(';') {
+                return;
+            }
+        }
+    }
+
+    /// Skips a brace-delimited block, including nested blocks.
+    fn skip_braces(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.bump().kind {
+                Kind::Punct('{') => depth += 1,
+                Kind::Punct('}') if depth <= 1 => return,
+                Kind::Punct('}') => depth -= 1,
+                Kind::Eof => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn file(&mut self) -> InputFile {
+        let mut file = InputFile::default();
+        loop {
+            let start = self.index;
+            match self.peek().kind {
+                Kind::Eof => return file,
+                Kind::Punct('[') => match self.section_header() {
+                    Some(name) => file.sections.push(Section { name, inputs: Vec::new() }),
+                    None => self.recover(start),
+                },
+                Kind::Punct('{') => {
+                    let span = self.peek().span;
+                    self.diagnostics.push(
+                        Diagnostic::error(span, "struct literal is not assigned to an input")
+                            .with_help("write it as `name: Type = Type { ... };` inside a section"),
+                    );
+                    self.skip_braces();
+                    self.eat(';');
+                }
+                _ => match self.input() {
+                    Some(input) => match file.sections.last_mut() {
+                        Some(section) => section.inputs.push(input),
+                        None => self.diagnostics.push(
+                            Diagnostic::error(
+                                input.name.span,
+                                format!("input `{}` is outside of any section", input.name.name),
+                            )
+                            .with_help("add a `[function_name]` header above it"),
+                        ),
+                    },
+                    None => self.recover(start),
+                },
+            }
+        }
+    }
+
+    fn section_header(&mut self) -> Option<Ident> {
+        self.expect('[', "to open a section")?;
+        let name = self.ident("a function name")?;
+        self.expect(']', "to close the section header")?;
+        Some(name)
+    }
+
+    fn input(&mut self) -> Option<Input> {
+        let start = self.peek().span;
+        let visibility = match self.peek() {
+            token
+                if token.kind == Kind::Word
+                    && VISIBILITIES.contains(&token.text.as_str())
+                    && self.peek_nth(1).kind == Kind::Word =>
+            {
+                self.ident("a visibility")
+            }
+            _ => None,
+        };
+        let name = self.ident("an input name")?;
+        self.expect(':', &format!("after input name `{}`", name.name))?;
+        let ty = self.ident("a type")?;
+        self.expect('=', &format!("after the type of `{}`", name.name))?;
+        let value = self.value()?;
+        let end = value.span();
+        if self.eat(';').is_none() {
+            let at = Span::new(end.end, end.end);
+            let diagnostic = Diagnostic::error(at, format!("missing `;` after the value of `{}`", name.name))
+                .with_help(MISSING_SEMICOLON_HELP);
+            self.diagnostics.push(diagnostic);
+            if !self.at_item_start() {
+                self.recover(self.index);
+            }
+        }
+        Some(Input { visibility, name, ty, value, span: start.to(end) })
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        if self.is_punct('{') {
+            let span = self.peek().span;
+            self.diagnostics.push(
+                Diagnostic::error(span, "struct literal is missing its type name")
+                    .with_help("write `Name { ... }`, e.g. `Token { owner: ..., balance: ..., _nonce: ... }`"),
+            );
+            self.skip_braces();
+            return None;
+        }
+        let head = self.ident("a value")?;
+        if self.is_punct('{') {
+            return self.struct_literal(head).map(Value::Struct);
+        }
+        let mut span = head.span;
+        if self.is_punct('.') && self.peek_nth(1).kind == Kind::Word {
+            self.bump();
+            let suffix = self.bump();
+            span = span.to(suffix.span);
+            self.diagnostics.push(
+                Diagnostic::error(span, format!("`.{}` visibility suffix is not allowed in input files", suffix.text))
+                    .with_help(format!("write just `{}`", head.name)),
+            );
+        }
+        Some(Value::Literal(Literal { text: head.name, span }))
+    }
+
+    fn struct_literal(&mut self, name: Ident) -> Option<StructLiteral> {
+        self.expect('{', "to open the struct literal")?;
+        let mut members = Vec::new();
+        loop {
+            if let Some(close) = self.eat('}') {
+                return Some(StructLiteral { span: name.span.to(close.span), name, members });
+            }
+            let member = self.ident("a member name or `}`")?;
+            self.expect(':', &format!("after member `{}`", member.name))?;
+            let value = self.value()?;
+            members.push((member, value));
+            if self.eat(',').is_none() && !self.is_punct('}') {
+                let found = Self::describe(self.peek());
+                let span = self.peek().span;
+                self.diagnostics
+                    .push(Diagnostic::error(span, format!("expected `,` or `}}` in struct literal, found {found}")));
+                return None;
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Turning a record printed by `leo run` into an input file assignment.
+//!
+//! `leo run` prints records as plaintext, with a visibility on every member
+//! (`balance: 100u32.private`), while an input file needs a typed struct
+//! literal without them (`input: Token = Token { balance: 100u32, ... };`).
+
+use thiserror::Error;
+
+use crate::aleo::{PlaintextType, Program, ValueType};
+use crate::diagnostic::Position;
+use crate::interpreter::{Plaintext, Record, Value};
+use crate::leo::Transition;
+
+use super::InputFile;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RecordError {
+    #[error("no `{{ ... }}` record found in the text")]
+    NotFound,
+    #[error("failed to parse the record: {0}")]
+    Parse(String),
+    #[error("the value has no `_nonce`, so it is not a record")]
+    NotARecord,
+    #[error("the program has no function `{0}`")]
+    UnknownFunction(String),
+    #[error("`{function}` takes no record input")]
+    NoRecordInput { function: String },
+    #[error("no function of the program takes a record")]
+    NoRecordFunction,
+    #[error("{candidates}; choose one with `{flag}`")]
+    Ambiguous { candidates: String, flag: &'static str },
+    #[error("the program declares no record `{0}`")]
+    UnknownRecord(String),
+    #[error("`{function}` has no record input named `{name}`")]
+    UnknownInput { function: String, name: String },
+    #[error("the record has {found}, but `{record}` declares {expected}")]
+    Members { record: String, expected: String, found: String },
+}
+
+/// Finds the first `{ ... }` block in `text`, such as the output of `leo run`,
+/// and parses it as a record.
+pub fn find_record(text: &str) -> Result<Record, RecordError> {
+    let start = text.find('{').ok_or(RecordError::NotFound)?;
+    let mut depth = 0;
+    let end = text[start..]
+        .char_indices()
+        .find_map(|(offset, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(start + offset + 1)
+        })
+        .ok_or(RecordError::NotFound)?;
+    match Value::parse(&text[start..end]).map_err(RecordError::Parse)? {
+        Value::Record(record) => Ok(record),
+        Value::Plaintext(_) => Err(RecordError::NotARecord),
+    }
+}
+
+/// The record input of a function that a record should be written to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub function: String,
+    /// The Leo parameter name, e.g. `input`.
+    pub name: String,
+    /// The record type, e.g. `Token`.
+    pub record: String,
+}
+
+/// Picks the record input to fill in. Without a `function`, the program must
+/// have exactly one function taking a record; without a `name`, that function
+/// must take exactly one. Names come from the Leo `transitions`, falling back
+/// to `r<index>` when the source is unavailable.
+pub fn target(
+    program: &Program,
+    transitions: &[Transition],
+    function: Option<&str>,
+    name: Option<&str>,
+) -> Result<Target, RecordError> {
+    let takes_record =
+        |function: &crate::aleo::Function| function.inputs.iter().any(|input| matches!(input.ty, ValueType::Record(_)));
+    let function = match function {
+        Some(function) => program.function(function).ok_or_else(|| RecordError::UnknownFunction(function.into()))?,
+        None => {
+            let candidates: Vec<_> = program.functions().filter(|function| takes_record(function)).collect();
+            match candidates.as_slice() {
+                [function] => *function,
+                [] => return Err(RecordError::NoRecordFunction),
+                _ => {
+                    let names: Vec<_> = candidates.iter().map(|function| format!("`{}`", function.name)).collect();
+                    let candidates = format!("{} all take records", names.join(", "));
+                    return Err(RecordError::Ambiguous { candidates, flag: "--function" });
+                }
+            }
+        }
+    };
+
+    let parameters = transitions.iter().find(|transition| transition.name == function.name.name);
+    let records: Vec<(String, String)> = function
+        .inputs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, input)| {
+            let ValueType::Record(record) = &input.ty else { return None };
+            let name = parameters
+                .and_then(|transition| transition.parameters.get(index))
+                .map_or_else(|| input.register.to_string(), |parameter| parameter.name.clone());
+            Some((name, record.name.clone()))
+        })
+        .collect();
+    let function_name = function.name.name.clone();
+    let (name, record) = match (name, records.as_slice()) {
+        (_, []) => return Err(RecordError::NoRecordInput { function: function_name }),
+        (None, [(name, record)]) => (name.clone(), record.clone()),
+        (None, _) => {
+            let names: Vec<_> = records.iter().map(|(name, _)| format!("`{name}`")).collect();
+            let candidates = format!("`{function_name}` takes records {}", names.join(", "));
+            return Err(RecordError::Ambiguous { candidates, flag: "--name" });
+        }
+        (Some(name), _) => match records.iter().find(|(input, _)| input == name) {
+            Some((name, record)) => (name.clone(), record.clone()),
+            None => return Err(RecordError::UnknownInput { function: function_name, name: name.to_string() }),
+        },
+    };
+    Ok(Target { function: function_name, name, record })
+}
+
+/// Prints a record as an input file literal, `Token { owner: ..., _nonce: ... }`,
+/// checking its members against the program's declaration.
+pub fn record_literal(program: &Program, record_type: &str, record: &Record) -> Result<String, RecordError> {
+    let declaration = program.record(record_type).ok_or_else(|| RecordError::UnknownRecord(record_type.into()))?;
+    let found: Vec<&str> = record.entries.iter().map(|entry| entry.name.as_str()).collect();
+    let expected: Vec<&str> = declaration.members.iter().map(|member| member.name.name.as_str()).collect();
+    if found != expected {
+        return Err(RecordError::Members {
+            record: record_type.to_string(),
+            expected: expected.join(", "),
+            found: found.join(", "),
+        });
+    }
+    let mut members: Vec<(String, String)> = record
+        .entries
+        .iter()
+        .zip(&declaration.members)
+        .map(|(entry, member)| (entry.name.clone(), plaintext_literal(program, &entry.value, &member.ty, 1)))
+        .collect();
+    members.push(("_nonce".to_string(), record.nonce.clone()));
+    Ok(format!("{record_type} {}", braces(&members, 0)))
+}
+
+/// The whole assignment, `input: Token = Token { ... };`.
+pub fn assignment(target: &Target, literal: &str) -> String {
+    format!("{}: {} = {literal};", target.name, target.record)
+}
+
+fn plaintext_literal(program: &Program, value: &Plaintext, ty: &PlaintextType, depth: usize) -> String {
+    match (value, ty) {
+        (Plaintext::Struct(members), PlaintextType::Struct(name)) => {
+            let declaration = program.struct_(&name.name);
+            let members: Vec<(String, String)> = members
+                .iter()
+                .map(|(member, value)| {
+                    let ty = declaration
+                        .and_then(|declaration| declaration.members.iter().find(|m| m.name.name == *member))
+                        .map(|member| member.ty.clone())
+                        .unwrap_or_else(|| ty.clone());
+                    (member.clone(), plaintext_literal(program, value, &ty, depth + 1))
+                })
+                .collect();
+            format!("{name} {}", braces(&members, depth))
+        }
+        (value, _) => value.to_string(),
+    }
+}
+
+/// `{`, one `name: value` per line indented two spaces per level, `}`, in
+/// the layout of the package's input file.
+fn braces(members: &[(String, String)], depth: usize) -> String {
+    let indent = "  ".repeat(depth + 1);
+    let lines: Vec<String> = members.iter().map(|(name, value)| format!("{indent}{name}: {value}")).collect();
+    format!("{{\n{}\n{}}}", lines.join(",\n"), "  ".repeat(depth))
+}
+
+/// Sets the input `name` of `[section]` to `assignment`: replaces the
+/// existing assignment, or adds it at the end of the section, or adds the
+/// section at the end of the file.
+pub fn set_input(source: &str, file: &InputFile, section: &str, name: &str, assignment: &str) -> String {
+    let Some(found) = file.section(section) else {
+        let separator = if source.is_empty() || source.ends_with("\n\n") {
+            ""
+        } else if source.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        return format!("{source}{separator}[{section}]\n{assignment}\n");
+    };
+    if let Some(input) = found.inputs.iter().find(|input| input.name.name == name) {
+        let start = offset(source, input.span.start);
+        let mut end = offset(source, input.span.end);
+        if let Some(semicolon) =
+            source[end..].find(|c: char| !c.is_whitespace()).filter(|&i| source[end + i..].starts_with(';'))
+        {
+            end += semicolon + 1;
+        }
+        return format!("{}{assignment}{}", &source[..start], &source[end..]);
+    }
+    let after = match found.inputs.last() {
+        Some(last) => {
+            let end = offset(source, last.span.end);
+            source[end..].find('\n').map_or(source.len(), |newline| end + newline + 1)
+        }
+        None => {
+            let header = offset(source, found.name.span.end);
+            source[header..].find('\n').map_or(source.len(), |newline| header + newline + 1)
+        }
+    };
+    let newline = if after == source.len() && !source.ends_with('\n') { "\n" } else { "" };
+    format!("{}{newline}{assignment}\n{}", &source[..after], &source[after..])
+}
+
+/// The byte offset of a 1-based line/column (in characters) position.
+fn offset(source: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 1..position.line {
+        match source[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return source.len(),
+        }
+    }
+    let line = &source[line_start..];
+    line.char_indices()
+        .nth(position.column - 1)
+        .map_or(source.len().min(line_start + line.len()), |(i, _)| line_start + i)
+}
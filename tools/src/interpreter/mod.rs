@@ -0,0 +1,608 @@
+//! Running the functions of a compiled program without proving.
+//!
+//! [`Interpreter`] executes a [`Function`] of an [`aleo::Program`](Program) directly on
+//! plaintext [`Value`]s, the way `leo run` would but in milliseconds: no keys
+//! are synthesized and no proof is produced. Integer arithmetic halts exactly
+//! where snarkVM does, record inputs must be owned by the caller, and a
+//! function's finalize block runs against mappings held by the interpreter.
+//!
+//! `hash.*` into a field or integer gives a stand-in digest: equal inputs hash
+//! equally, so mappings keyed by hashes behave, but the values differ from
+//! snarkVM's. Other field and curve operations (`commit.*`, `sign.verify`,
+//! field arithmetic) and calls to other programs are reported as unsupported
+//! rather than approximated. Record nonces are a counter, not a real group
+//! element.
+//!
+//! ```
+//! use workshop::interpreter::{Interpreter, Value, ZERO_ADDRESS};
+//!
+//! let program = workshop::aleo::parse(
+//!     "program token.aleo;\n\nfunction double:\n    input r0 as u32.private;\n    add r0 r0 into r1;\n    output r1 as u32.private;\n",
+//! )
+//! .unwrap();
+//! let mut interpreter = Interpreter::new(&program, ZERO_ADDRESS);
+//! let outputs = interpreter.execute("double", vec![Value::parse("21u32").unwrap()]).unwrap();
+//! assert_eq!(outputs[0].to_string(), "42u32");
+//! ```
+
+mod ops;
+mod value;
+
+pub use value::{Entry, Integer, Literal, Plaintext, Record, Value};
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::aleo::{
+    AssertOp, CallTarget, Command, CommandKind, Function, Instruction, LiteralType, Operand, Operation, PlaintextType,
+    Program, Register, RegisterType, ValueType,
+};
+use crate::diagnostic::{Diagnostic, Span};
+
+use ops::Failure;
+
+/// The all-zero address, a caller that owns nothing.
+pub const ZERO_ADDRESS: &str = "aleo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq3ljyzc";
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum InterpretError {
+    #[error("the program has no function `{0}`")]
+    UnknownFunction(String),
+    #[error("`{function}` takes {expected} input(s), found {found}")]
+    InputCount { function: String, expected: usize, found: usize },
+    #[error("input {register}: {message}")]
+    Input { register: String, message: String, span: Span },
+    /// The program halted, as it would when executed on-chain.
+    #[error("{message}")]
+    Halt { message: String, span: Span },
+    /// A checked arithmetic instruction halted on the given operand values.
+    #[error("{message}")]
+    Arithmetic { values: Vec<Literal>, message: String, span: Span },
+    #[error("{what} is not supported by the interpreter")]
+    Unsupported { what: String, span: Span },
+}
+
+impl InterpretError {
+    /// The location in the program the error points at, if any.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            InterpretError::UnknownFunction(_) | InterpretError::InputCount { .. } => None,
+            InterpretError::Input { span, .. }
+            | InterpretError::Halt { span, .. }
+            | InterpretError::Arithmetic { span, .. }
+            | InterpretError::Unsupported { span, .. } => Some(*span),
+        }
+    }
+}
+
+impl From<InterpretError> for Diagnostic {
+    fn from(error: InterpretError) -> Self {
+        Diagnostic::error(error.span().unwrap_or_default(), error.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, InterpretError>;
+
+fn halt<T>(span: Span, message: impl Into<String>) -> Result<T> {
+    Err(InterpretError::Halt { message: message.into(), span })
+}
+
+/// Attributes the failure of an operation on `values` to the instruction at `span`.
+fn fail(failure: Failure, values: &[&Literal], span: Span) -> InterpretError {
+    match failure {
+        Failure::Halt(message) => InterpretError::Halt { message, span },
+        Failure::Arithmetic(message) => {
+            InterpretError::Arithmetic { values: values.iter().map(|&value| value.clone()).collect(), message, span }
+        }
+        Failure::Unsupported(what) => InterpretError::Unsupported { what, span },
+    }
+}
+
+/// The type a register is checked against.
+enum Expected<'a> {
+    Plaintext(&'a PlaintextType),
+    Record(&'a str),
+    ExternalRecord,
+}
+
+impl<'a> From<&'a ValueType> for Expected<'a> {
+    fn from(ty: &'a ValueType) -> Self {
+        match ty {
+            ValueType::Plaintext(ty, _) => Expected::Plaintext(ty),
+            ValueType::Record(name) => Expected::Record(&name.name),
+            ValueType::ExternalRecord(_) => Expected::ExternalRecord,
+        }
+    }
+}
+
+impl<'a> From<&'a RegisterType> for Expected<'a> {
+    fn from(ty: &'a RegisterType) -> Self {
+        match ty {
+            RegisterType::Plaintext(ty) => Expected::Plaintext(ty),
+            RegisterType::Record(name) => Expected::Record(&name.name),
+            RegisterType::ExternalRecord(_) => Expected::ExternalRecord,
+        }
+    }
+}
+
+impl<'a> From<&'a PlaintextType> for Expected<'a> {
+    fn from(ty: &'a PlaintextType) -> Self {
+        Expected::Plaintext(ty)
+    }
+}
+
+/// The registers of one function, closure or finalize invocation.
+#[derive(Default)]
+struct Registers(BTreeMap<u32, Value>);
+
+impl Registers {
+    fn store(&mut self, register: &Register, value: Value) {
+        self.0.insert(register.index, value);
+    }
+}
+
+/// Executes the functions of one program, keeping its mapping state between
+/// calls.
+pub struct Interpreter<'a> {
+    program: &'a Program,
+    caller: String,
+    block_height: u32,
+    mappings: BTreeMap<String, Vec<(Plaintext, Plaintext)>>,
+    /// How many records have been created, which numbers their nonces.
+    records: u64,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(program: &'a Program, caller: impl Into<String>) -> Self {
+        Self { program, caller: caller.into(), block_height: 0, mappings: BTreeMap::new(), records: 0 }
+    }
+
+    pub fn program(&self) -> &'a Program {
+        self.program
+    }
+
+    /// The address `self.caller` and `self.signer` evaluate to.
+    pub fn caller(&self) -> &str {
+        &self.caller
+    }
+
+    pub fn set_caller(&mut self, caller: impl Into<String>) {
+        self.caller = caller.into();
+    }
+
+    /// The height `block.height` evaluates to in finalize blocks.
+    pub fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    pub fn set_block_height(&mut self, height: u32) {
+        self.block_height = height;
+    }
+
+    /// The value stored under `key` in a mapping, if any.
+    pub fn mapping_value(&self, mapping: &str, key: &Plaintext) -> Option<&Plaintext> {
+        let entries = self.mappings.get(mapping)?;
+        entries.iter().find(|(entry, _)| entry == key).map(|(_, value)| value)
+    }
+
+    /// Every entry of a mapping, in the order they were first set.
+    pub fn mapping_entries(&self, mapping: &str) -> &[(Plaintext, Plaintext)] {
+        self.mappings.get(mapping).map_or(&[], Vec::as_slice)
+    }
+
+    /// Runs `function` on `inputs` and returns its outputs. If the function
+    /// has a finalize block it runs too, and its mapping updates are kept
+    /// only if it completes.
+    pub fn execute(&mut self, function: &str, inputs: Vec<Value>) -> Result<Vec<Value>> {
+        let program = self.program;
+        let function = program.function(function).ok_or_else(|| InterpretError::UnknownFunction(function.into()))?;
+        if inputs.len() != function.inputs.len() {
+            return Err(InterpretError::InputCount {
+                function: function.name.name.clone(),
+                expected: function.inputs.len(),
+                found: inputs.len(),
+            });
+        }
+
+        let mut registers = Registers::default();
+        for (input, value) in function.inputs.iter().zip(inputs) {
+            let error = |message: String| InterpretError::Input {
+                register: input.register.to_string(),
+                message,
+                span: input.span,
+            };
+            self.check(&value, (&input.ty).into()).map_err(error)?;
+            if let Value::Record(record) = &value {
+                if record.owner() != Some(self.caller.as_str()) {
+                    let owner = record.owner().unwrap_or("nobody");
+                    return Err(error(format!("the record is owned by {owner}, not the caller {}", self.caller)));
+                }
+            }
+            registers.store(&input.register, value);
+        }
+
+        self.instructions(&mut registers, &function.instructions)?;
+        let outputs = function
+            .outputs
+            .iter()
+            .map(|output| self.output(&registers, &output.operand, (&output.ty).into(), output.span))
+            .collect::<Result<Vec<_>>>()?;
+
+        if let Some((call, _)) = &function.finalize {
+            let arguments = call
+                .operands
+                .iter()
+                .map(|operand| self.load(&registers, operand, call.span))
+                .collect::<Result<Vec<_>>>()?;
+            let saved = self.mappings.clone();
+            if let Err(error) = self.finalize(function, arguments) {
+                self.mappings = saved;
+                return Err(error);
+            }
+        }
+        Ok(outputs)
+    }
+
+    fn finalize(&mut self, function: &Function, arguments: Vec<Value>) -> Result<()> {
+        let (call, finalize) = function.finalize.as_ref().expect("checked by the caller");
+        if arguments.len() != finalize.inputs.len() {
+            return halt(
+                call.span,
+                format!(
+                    "`finalize` passes {} value(s) but `{}` takes {}",
+                    arguments.len(),
+                    finalize.name,
+                    finalize.inputs.len()
+                ),
+            );
+        }
+        let mut registers = Registers::default();
+        for (input, value) in finalize.inputs.iter().zip(arguments) {
+            self.check(&value, (&input.ty).into()).map_err(|message| InterpretError::Halt {
+                message: format!("{}: {message}", input.register),
+                span: input.span,
+            })?;
+            registers.store(&input.register, value);
+        }
+
+        let commands = &finalize.commands;
+        let mut next = 0;
+        while let Some(command) = commands.get(next) {
+            next += 1;
+            if let Some(label) = self.command(&mut registers, command)? {
+                let position = commands[next..].iter().position(
+                    |command| matches!(&command.kind, CommandKind::Position { label: position } if position.name == label),
+                );
+                match position {
+                    Some(offset) => next += offset,
+                    None => return halt(command.span, format!("no `position {label}` follows this branch")),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs one finalize command, returning the label to jump to if it is a
+    /// branch that is taken.
+    fn command(&mut self, registers: &mut Registers, command: &Command) -> Result<Option<String>> {
+        let span = command.span;
+        match &command.kind {
+            CommandKind::Instruction(operation) => self.operation(registers, operation, span)?,
+            CommandKind::Get { access, destination } => {
+                let key = self.key(registers, &access.mapping.name, &access.key, span)?;
+                match self.mapping_value(&access.mapping.name, &key) {
+                    Some(value) => registers.store(destination, value.clone().into()),
+                    None => return halt(span, format!("`{}` has no entry for {key}", access.mapping)),
+                }
+            }
+            CommandKind::GetOrUse { access, default, destination } => {
+                let key = self.key(registers, &access.mapping.name, &access.key, span)?;
+                let value = match self.mapping_value(&access.mapping.name, &key) {
+                    Some(value) => value.clone().into(),
+                    None => self.load(registers, default, span)?,
+                };
+                registers.store(destination, value);
+            }
+            CommandKind::Contains { access, destination } => {
+                let key = self.key(registers, &access.mapping.name, &access.key, span)?;
+                let contains = self.mapping_value(&access.mapping.name, &key).is_some();
+                registers.store(destination, Literal::Boolean(contains).into());
+            }
+            CommandKind::Set { value, access } => {
+                let key = self.key(registers, &access.mapping.name, &access.key, span)?;
+                let mapping = self.program.mapping(&access.mapping.name).expect("checked by `key`");
+                let value = self.plaintext(self.load(registers, value, span)?, span)?;
+                self.check(&value.clone().into(), (&mapping.value.ty).into()).map_err(|message| {
+                    InterpretError::Halt { message: format!("cannot store in `{}`: {message}", mapping.name), span }
+                })?;
+                let entries = self.mappings.entry(mapping.name.name.clone()).or_default();
+                match entries.iter_mut().find(|(entry, _)| *entry == key) {
+                    Some((_, existing)) => *existing = value,
+                    None => entries.push((key, value)),
+                }
+            }
+            CommandKind::Remove { access } => {
+                let key = self.key(registers, &access.mapping.name, &access.key, span)?;
+                if let Some(entries) = self.mappings.get_mut(&access.mapping.name) {
+                    entries.retain(|(entry, _)| *entry != key);
+                }
+            }
+            CommandKind::RandChaCha { .. } => {
+                return Err(InterpretError::Unsupported { what: "`rand.chacha`".to_string(), span });
+            }
+            CommandKind::Position { .. } => {}
+            CommandKind::BranchEq { operands: [a, b], label } | CommandKind::BranchNeq { operands: [a, b], label } => {
+                let equal = self.load(registers, a, span)? == self.load(registers, b, span)?;
+                let taken = equal == matches!(command.kind, CommandKind::BranchEq { .. });
+                return Ok(taken.then(|| label.name.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Evaluates a mapping key, checking the mapping exists and the key has
+    /// its key type.
+    fn key(&self, registers: &Registers, mapping: &str, key: &Operand, span: Span) -> Result<Plaintext> {
+        let Some(declaration) = self.program.mapping(mapping) else {
+            return halt(span, format!("the program has no mapping `{mapping}`"));
+        };
+        let key = self.plaintext(self.load(registers, key, span)?, span)?;
+        self.check(&key.clone().into(), (&declaration.key.ty).into())
+            .map_err(|message| InterpretError::Halt { message: format!("bad key for `{mapping}`: {message}"), span })?;
+        Ok(key)
+    }
+
+    fn instructions(&mut self, registers: &mut Registers, instructions: &[Instruction]) -> Result<()> {
+        for instruction in instructions {
+            self.operation(registers, &instruction.operation, instruction.span)?;
+        }
+        Ok(())
+    }
+
+    fn operation(&mut self, registers: &mut Registers, operation: &Operation, span: Span) -> Result<()> {
+        match operation {
+            Operation::Unary { op, operand, destination } => {
+                let operand = self.literal(registers, operand, span)?;
+                let result = ops::unary(*op, &operand).map_err(|failure| fail(failure, &[&operand], span))?;
+                registers.store(destination, result.into());
+            }
+            Operation::Binary { op, operands: [a, b], destination } => {
+                let (a, b) = (self.literal(registers, a, span)?, self.literal(registers, b, span)?);
+                let result = ops::binary(*op, &a, &b).map_err(|failure| fail(failure, &[&a, &b], span))?;
+                registers.store(destination, result.into());
+            }
+            Operation::Ternary { operands: [condition, a, b], destination } => {
+                let value = match self.literal(registers, condition, span)? {
+                    Literal::Boolean(true) => self.load(registers, a, span)?,
+                    Literal::Boolean(false) => self.load(registers, b, span)?,
+                    other => return halt(span, format!("the condition of `ternary` must be a boolean, found {other}")),
+                };
+                registers.store(destination, value);
+            }
+            Operation::Assert { op, operands: [a, b] } => {
+                let (a, b) = (self.load(registers, a, span)?, self.load(registers, b, span)?);
+                let holds = (a == b) == (*op == AssertOp::Eq);
+                if !holds {
+                    return halt(span, format!("`{operation}` failed with {a} and {b}"));
+                }
+            }
+            Operation::Cast { lossy, operands, destination, ty } => {
+                let values =
+                    operands.iter().map(|operand| self.load(registers, operand, span)).collect::<Result<Vec<_>>>()?;
+                let value = self.cast(values, ty, *lossy, span)?;
+                registers.store(destination, value);
+            }
+            Operation::Call { target: CallTarget::Local(name), operands, destinations } => {
+                let Some(closure) = self.program.closure(&name.name) else {
+                    return halt(span, format!("the program has no closure `{name}`"));
+                };
+                if operands.len() != closure.inputs.len() || destinations.len() != closure.outputs.len() {
+                    return halt(
+                        span,
+                        format!(
+                            "`{name}` takes {} input(s) and returns {} output(s)",
+                            closure.inputs.len(),
+                            closure.outputs.len()
+                        ),
+                    );
+                }
+                let mut frame = Registers::default();
+                for (input, operand) in closure.inputs.iter().zip(operands) {
+                    let value = self.load(registers, operand, span)?;
+                    self.check(&value, (&input.ty).into()).map_err(|message| InterpretError::Halt {
+                        message: format!("argument {} of `{name}`: {message}", input.register),
+                        span,
+                    })?;
+                    frame.store(&input.register, value);
+                }
+                self.instructions(&mut frame, &closure.instructions)?;
+                for (output, destination) in closure.outputs.iter().zip(destinations) {
+                    let value = self.output(&frame, &output.operand, (&output.ty).into(), output.span)?;
+                    registers.store(destination, value);
+                }
+            }
+            Operation::Call { target: CallTarget::External(locator), .. } => {
+                return Err(InterpretError::Unsupported { what: format!("calling `{locator}`"), span });
+            }
+            Operation::Hash { op, operand, destination, ty } => {
+                let input = self.load(registers, operand, span)?;
+                let result =
+                    ops::hash(*op, &input.to_string(), ty.as_ref()).map_err(|failure| fail(failure, &[], span))?;
+                registers.store(destination, result.into());
+            }
+            Operation::Commit { op, .. } => return Err(InterpretError::Unsupported { what: format!("`{op}`"), span }),
+            Operation::SignVerify { .. } => {
+                return Err(InterpretError::Unsupported { what: "`sign.verify`".to_string(), span });
+            }
+        }
+        Ok(())
+    }
+
+    fn cast(&mut self, values: Vec<Value>, ty: &RegisterType, lossy: bool, span: Span) -> Result<Value> {
+        let plaintexts = values.into_iter().map(|value| self.plaintext(value, span)).collect::<Result<Vec<_>>>()?;
+        match ty {
+            RegisterType::Plaintext(PlaintextType::Literal(ty)) => {
+                let [Plaintext::Literal(literal)] = plaintexts.as_slice() else {
+                    return halt(span, format!("casting to {ty} takes a single literal"));
+                };
+                let result = ops::cast(literal, *ty, lossy).map_err(|failure| fail(failure, &[literal], span))?;
+                Ok(result.into())
+            }
+            RegisterType::Plaintext(PlaintextType::Struct(name)) => {
+                let Some(declaration) = self.program.struct_(&name.name) else {
+                    return halt(span, format!("the program has no struct `{name}`"));
+                };
+                if plaintexts.len() != declaration.members.len() {
+                    return halt(
+                        span,
+                        format!("`{name}` has {} member(s), found {}", declaration.members.len(), plaintexts.len()),
+                    );
+                }
+                let members =
+                    declaration.members.iter().map(|member| member.name.name.clone()).zip(plaintexts).collect();
+                let value = Plaintext::Struct(members).into();
+                self.check(&value, Expected::Plaintext(&PlaintextType::Struct(name.clone())))
+                    .map_err(|message| InterpretError::Halt { message, span })?;
+                Ok(value)
+            }
+            RegisterType::Record(name) => {
+                let Some(declaration) = self.program.record(&name.name) else {
+                    return halt(span, format!("the program has no record `{name}`"));
+                };
+                if plaintexts.len() != declaration.members.len() {
+                    return halt(
+                        span,
+                        format!("`{name}` has {} member(s), found {}", declaration.members.len(), plaintexts.len()),
+                    );
+                }
+                let entries = declaration
+                    .members
+                    .iter()
+                    .zip(plaintexts)
+                    .map(|(member, value)| Entry {
+                        name: member.name.name.clone(),
+                        value,
+                        visibility: member.visibility,
+                    })
+                    .collect();
+                let value = Value::Record(Record { entries, nonce: format!("{}group", self.records) });
+                self.check(&value, Expected::Record(&name.name))
+                    .map_err(|message| InterpretError::Halt { message, span })?;
+                self.records += 1;
+                Ok(value)
+            }
+            RegisterType::ExternalRecord(locator) => {
+                Err(InterpretError::Unsupported { what: format!("casting to `{locator}.record`"), span })
+            }
+        }
+    }
+
+    fn output(&self, registers: &Registers, operand: &Operand, ty: Expected<'_>, span: Span) -> Result<Value> {
+        let value = self.load(registers, operand, span)?;
+        self.check(&value, ty)
+            .map_err(|message| InterpretError::Halt { message: format!("output {operand}: {message}"), span })?;
+        Ok(value)
+    }
+
+    /// Reads an operand: a literal, a register or one of its members, or
+    /// `self.caller`.
+    fn load(&self, registers: &Registers, operand: &Operand, span: Span) -> Result<Value> {
+        match operand {
+            Operand::Literal(literal) => {
+                Literal::from_ast(literal).map(Value::from).or_else(|message| halt(span, message))
+            }
+            Operand::Register(register) => {
+                let Some(value) = registers.0.get(&register.index) else {
+                    return halt(span, format!("r{} is read before it is assigned", register.index));
+                };
+                let mut value = value.clone();
+                let mut path = format!("r{}", register.index);
+                for member in &register.members {
+                    let next = match &value {
+                        Value::Record(record) => record.member(&member.name),
+                        Value::Plaintext(plaintext) => plaintext.member(&member.name),
+                    };
+                    let Some(next) = next.cloned() else {
+                        return halt(span, format!("{path} has no member `{member}`"));
+                    };
+                    value = Value::Plaintext(next);
+                    path = format!("{path}.{member}");
+                }
+                Ok(value)
+            }
+            Operand::Caller | Operand::Signer => Ok(Literal::Address(self.caller.clone()).into()),
+            Operand::BlockHeight => Ok(Literal::Integer(
+                Integer::from_u128(LiteralType::U32, self.block_height.into()).expect("a u32 fits"),
+            )
+            .into()),
+            Operand::ProgramId(id) => Err(InterpretError::Unsupported { what: format!("the address of `{id}`"), span }),
+        }
+    }
+
+    fn literal(&self, registers: &Registers, operand: &Operand, span: Span) -> Result<Literal> {
+        match self.load(registers, operand, span)? {
+            Value::Plaintext(Plaintext::Literal(literal)) => Ok(literal),
+            _ => halt(span, format!("{operand} must be a literal")),
+        }
+    }
+
+    fn plaintext(&self, value: Value, span: Span) -> Result<Plaintext> {
+        match value {
+            Value::Plaintext(plaintext) => Ok(plaintext),
+            Value::Record(_) => halt(span, "a record cannot be used as plaintext"),
+        }
+    }
+
+    /// Checks that a value has the given type, describing the mismatch if not.
+    fn check(&self, value: &Value, expected: Expected<'_>) -> std::result::Result<(), String> {
+        match (expected, value) {
+            (Expected::Plaintext(ty), Value::Plaintext(plaintext)) => self.check_plaintext(plaintext, ty),
+            (Expected::Record(name), Value::Record(record)) => {
+                let Some(declaration) = self.program.record(name) else {
+                    return Err(format!("the program has no record `{name}`"));
+                };
+                let names: Vec<&str> = record.entries.iter().map(|entry| entry.name.as_str()).collect();
+                let expected: Vec<&str> = declaration.members.iter().map(|member| member.name.name.as_str()).collect();
+                if names != expected {
+                    return Err(format!(
+                        "expected a {name} record with {}, found {}",
+                        expected.join(", "),
+                        names.join(", ")
+                    ));
+                }
+                for (entry, member) in record.entries.iter().zip(&declaration.members) {
+                    self.check_plaintext(&entry.value, &member.ty)
+                        .map_err(|message| format!("{}: {message}", entry.name))?;
+                }
+                Ok(())
+            }
+            (Expected::Plaintext(ty), Value::Record(_)) => Err(format!("expected {ty}, found a record")),
+            (Expected::Record(name), Value::Plaintext(plaintext)) => {
+                Err(format!("expected a {name} record, found {}", plaintext.to_string().replace('\n', " ")))
+            }
+            (Expected::ExternalRecord, _) => Err("records of other programs are not supported".to_string()),
+        }
+    }
+
+    fn check_plaintext(&self, value: &Plaintext, ty: &PlaintextType) -> std::result::Result<(), String> {
+        match (ty, value) {
+            (PlaintextType::Literal(ty), Plaintext::Literal(literal)) if literal.ty() == *ty => Ok(()),
+            (PlaintextType::Struct(name), Plaintext::Struct(members)) => {
+                let Some(declaration) = self.program.struct_(&name.name) else {
+                    return Err(format!("the program has no struct `{name}`"));
+                };
+                let names: Vec<&str> = members.iter().map(|(name, _)| name.as_str()).collect();
+                let expected: Vec<&str> = declaration.members.iter().map(|member| member.name.name.as_str()).collect();
+                if names != expected {
+                    return Err(format!("expected {name} with {}, found {}", expected.join(", "), names.join(", ")));
+                }
+                for ((member, value), declared) in members.iter().zip(&declaration.members) {
+                    self.check_plaintext(value, &declared.ty).map_err(|message| format!("{member}: {message}"))?;
+                }
+                Ok(())
+            }
+            (ty, value) => Err(format!("expected {ty}, found {}", value.to_string().replace('\n', " "))),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! The arithmetic, logic and cast instructions on literals.
+//!
+//! Integers follow snarkVM: checked operations halt on overflow, underflow,
+//! division by zero and out-of-range shifts, and `.w` operations wrap.
+
+use sha2::{Digest, Sha256};
+
+use crate::aleo::{BinaryOp, HashOp, LiteralType, PlaintextType, UnaryOp};
+
+use super::value::{Integer, Literal};
+
+/// Why an operation produced no value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Failure {
+    /// The program halts, as it would on-chain.
+    Halt(String),
+    /// A checked operation overflowed, underflowed, divided by zero or
+    /// shifted out of range; the program halts.
+    Arithmetic(String),
+    /// The operation needs field or curve arithmetic, which is not interpreted.
+    Unsupported(String),
+}
+
+type Result<T> = std::result::Result<T, Failure>;
+
+fn halt<T>(message: impl Into<String>) -> Result<T> {
+    Err(Failure::Halt(message.into()))
+}
+
+fn arithmetic<T>(message: impl Into<String>) -> Result<T> {
+    Err(Failure::Arithmetic(message.into()))
+}
+
+fn unsupported<T>(op: impl std::fmt::Display, literal: &Literal) -> Result<T> {
+    Err(Failure::Unsupported(format!("`{op}` on {}", literal.ty())))
+}
+
+pub(super) fn unary(op: UnaryOp, operand: &Literal) -> Result<Literal> {
+    let integer = match operand {
+        Literal::Boolean(value) if op == UnaryOp::Not => return Ok(Literal::Boolean(!value)),
+        Literal::Integer(integer) => *integer,
+        _ => return unsupported(op, operand),
+    };
+    let ty = integer.ty();
+    let result = match op {
+        UnaryOp::Not => Integer::from_bits(ty, !integer.bits()),
+        UnaryOp::Abs | UnaryOp::AbsWrapped | UnaryOp::Neg if !ty.is_signed() => {
+            return halt(format!("`{op}` is not defined for {ty}"));
+        }
+        UnaryOp::Abs => match integer.signed().checked_abs().and_then(|value| Integer::from_i128(ty, value)) {
+            Some(result) => result,
+            None => return arithmetic(format!("`abs {integer}` overflows {ty}")),
+        },
+        UnaryOp::AbsWrapped => Integer::from_bits(ty, integer.signed().unsigned_abs()),
+        UnaryOp::Neg => match integer.signed().checked_neg().and_then(|value| Integer::from_i128(ty, value)) {
+            Some(result) => result,
+            None => return arithmetic(format!("`neg {integer}` overflows {ty}")),
+        },
+        UnaryOp::Double | UnaryOp::Inv | UnaryOp::Square | UnaryOp::SquareRoot => {
+            return unsupported(op, operand);
+        }
+    };
+    Ok(Literal::Integer(result))
+}
+
+pub(super) fn binary(op: BinaryOp, a: &Literal, b: &Literal) -> Result<Literal> {
+    let shift = matches!(
+        op,
+        BinaryOp::Pow
+            | BinaryOp::PowWrapped
+            | BinaryOp::Shl
+            | BinaryOp::ShlWrapped
+            | BinaryOp::Shr
+            | BinaryOp::ShrWrapped
+    );
+    if !shift && a.ty() != b.ty() {
+        return halt(format!("`{op}` needs operands of the same type, found {} and {}", a.ty(), b.ty()));
+    }
+    match op {
+        BinaryOp::IsEq => return Ok(Literal::Boolean(a == b)),
+        BinaryOp::IsNeq => return Ok(Literal::Boolean(a != b)),
+        _ => {}
+    }
+    if let (Literal::Boolean(x), Literal::Boolean(y)) = (a, b) {
+        return match op {
+            BinaryOp::And => Ok(Literal::Boolean(x & y)),
+            BinaryOp::Or => Ok(Literal::Boolean(x | y)),
+            BinaryOp::Xor => Ok(Literal::Boolean(x ^ y)),
+            BinaryOp::Nand => Ok(Literal::Boolean(!(x & y))),
+            BinaryOp::Nor => Ok(Literal::Boolean(!(x | y))),
+            _ => halt(format!("`{op}` is not defined for boolean")),
+        };
+    }
+    let (Literal::Integer(x), Literal::Integer(y)) = (a, b) else {
+        return unsupported(op, a);
+    };
+    let (x, y) = (*x, *y);
+    let ty = x.ty();
+    let signed = ty.is_signed();
+    let compare = |ordering: fn(std::cmp::Ordering) -> bool| {
+        let order = if signed { x.signed().cmp(&y.signed()) } else { x.unsigned().cmp(&y.unsigned()) };
+        Ok(Literal::Boolean(ordering(order)))
+    };
+    let result = match op {
+        BinaryOp::GreaterThan => return compare(|order| order.is_gt()),
+        BinaryOp::GreaterThanOrEqual => return compare(|order| order.is_ge()),
+        BinaryOp::LessThan => return compare(|order| order.is_lt()),
+        BinaryOp::LessThanOrEqual => return compare(|order| order.is_le()),
+        BinaryOp::And => Integer::from_bits(ty, x.bits() & y.bits()),
+        BinaryOp::Or => Integer::from_bits(ty, x.bits() | y.bits()),
+        BinaryOp::Xor => Integer::from_bits(ty, x.bits() ^ y.bits()),
+        BinaryOp::Nand | BinaryOp::Nor => return halt(format!("`{op}` is not defined for {ty}")),
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => checked(op, x, y)?,
+        BinaryOp::AddWrapped => Integer::from_bits(ty, x.bits().wrapping_add(y.bits())),
+        BinaryOp::SubWrapped => Integer::from_bits(ty, x.bits().wrapping_sub(y.bits())),
+        BinaryOp::MulWrapped => Integer::from_bits(ty, x.bits().wrapping_mul(y.bits())),
+        BinaryOp::DivWrapped | BinaryOp::RemWrapped | BinaryOp::Modulo if y.is_zero() => {
+            return arithmetic(format!("`{op} {x} {y}` divides by zero"));
+        }
+        BinaryOp::DivWrapped if signed => Integer::from_bits(ty, x.signed().wrapping_div(y.signed()) as u128),
+        BinaryOp::DivWrapped => Integer::from_bits(ty, x.unsigned() / y.unsigned()),
+        BinaryOp::RemWrapped if signed => Integer::from_bits(ty, x.signed().wrapping_rem(y.signed()) as u128),
+        BinaryOp::RemWrapped => Integer::from_bits(ty, x.unsigned() % y.unsigned()),
+        BinaryOp::Modulo if signed => return halt(format!("`mod` is not defined for {ty}")),
+        BinaryOp::Modulo => Integer::from_bits(ty, x.unsigned() % y.unsigned()),
+        BinaryOp::Pow
+        | BinaryOp::PowWrapped
+        | BinaryOp::Shl
+        | BinaryOp::ShlWrapped
+        | BinaryOp::Shr
+        | BinaryOp::ShrWrapped => shifted(op, x, y)?,
+        BinaryOp::IsEq | BinaryOp::IsNeq => unreachable!("handled above"),
+    };
+    Ok(Literal::Integer(result))
+}
+
+/// `add`, `sub`, `mul`, `div` and `rem`, halting when the exact result does
+/// not fit the type.
+fn checked(op: BinaryOp, x: Integer, y: Integer) -> Result<Integer> {
+    let ty = x.ty();
+    if matches!(op, BinaryOp::Div | BinaryOp::Rem) && y.is_zero() {
+        return arithmetic(format!("`{op} {x} {y}` divides by zero"));
+    }
+    let result = if ty.is_signed() {
+        let (a, b) = (x.signed(), y.signed());
+        let exact = match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Div => a.checked_div(b),
+            _ => a.checked_rem(b),
+        };
+        exact.and_then(|value| Integer::from_i128(ty, value))
+    } else {
+        let (a, b) = (x.unsigned(), y.unsigned());
+        let exact = match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Div => a.checked_div(b),
+            _ => a.checked_rem(b),
+        };
+        exact.and_then(|value| Integer::from_u128(ty, value))
+    };
+    result.ok_or_else(|| {
+        let negative = |integer: Integer| ty.is_signed() && integer.signed() < 0;
+        let below = match op {
+            BinaryOp::Add => negative(y),
+            BinaryOp::Sub => !negative(y),
+            BinaryOp::Mul => negative(x) != negative(y),
+            _ => false,
+        };
+        let direction = if below { "underflows" } else { "overflows" };
+        Failure::Arithmetic(format!("`{op} {x} {y}` {direction} {ty}"))
+    })
+}
+
+/// `pow`, `shl` and `shr`, whose right operand is a `u8`, `u16` or `u32`.
+fn shifted(op: BinaryOp, x: Integer, y: Integer) -> Result<Integer> {
+    let ty = x.ty();
+    if !matches!(y.ty(), LiteralType::U8 | LiteralType::U16 | LiteralType::U32) {
+        return halt(format!("the right operand of `{op}` must be a u8, u16 or u32, found {}", y.ty()));
+    }
+    let amount = y.unsigned() as u32;
+    let width = x.width();
+    let result = match op {
+        BinaryOp::Pow if ty.is_signed() => {
+            x.signed().checked_pow(amount).and_then(|value| Integer::from_i128(ty, value))
+        }
+        BinaryOp::Pow => x.unsigned().checked_pow(amount).and_then(|value| Integer::from_u128(ty, value)),
+        BinaryOp::PowWrapped => Some(Integer::from_bits(ty, x.bits().wrapping_pow(amount))),
+        BinaryOp::Shl | BinaryOp::Shr if amount >= width => {
+            return arithmetic(format!("`{op} {x} {y}` shifts by more than the {width} bits of {ty}"));
+        }
+        BinaryOp::Shl | BinaryOp::ShlWrapped => Some(Integer::from_bits(ty, x.bits() << (amount % width))),
+        _ if ty.is_signed() => Some(Integer::from_bits(ty, (x.signed() >> (amount % width)) as u128)),
+        _ => Some(Integer::from_bits(ty, x.bits() >> (amount % width))),
+    };
+    result.ok_or_else(|| Failure::Arithmetic(format!("`{op} {x} {y}` overflows {ty}")))
+}
+
+/// `cast` and `cast.lossy` between literal types. A checked cast halts when
+/// the value does not fit; a lossy one keeps the low bits.
+pub(super) fn cast(literal: &Literal, ty: LiteralType, lossy: bool) -> Result<Literal> {
+    if literal.ty() == ty {
+        return Ok(literal.clone());
+    }
+    let opcode = if lossy { "cast.lossy" } else { "cast" };
+    let integer = match literal {
+        Literal::Integer(integer) => *integer,
+        Literal::Boolean(value) if ty.is_integer() => {
+            return Ok(Literal::Integer(Integer::from_bits(ty, u128::from(*value))));
+        }
+        _ => return Err(Failure::Unsupported(format!("`{opcode}` from {} to {ty}", literal.ty()))),
+    };
+    // Sign-extend signed values so a lossy cast keeps the two's complement bits.
+    let bits = if integer.ty().is_signed() { integer.signed() as u128 } else { integer.unsigned() };
+    if ty == LiteralType::Boolean {
+        return match (bits, lossy) {
+            (0, _) => Ok(Literal::Boolean(false)),
+            (1, _) => Ok(Literal::Boolean(true)),
+            (_, true) => Ok(Literal::Boolean(bits & 1 == 1)),
+            (_, false) => halt(format!("cannot cast {integer} to boolean")),
+        };
+    }
+    if !ty.is_integer() {
+        return Err(Failure::Unsupported(format!("`{opcode}` from {} to {ty}", literal.ty())));
+    }
+    if lossy {
+        return Ok(Literal::Integer(Integer::from_bits(ty, bits)));
+    }
+    let result = if integer.ty().is_signed() {
+        Integer::from_i128(ty, integer.signed())
+    } else {
+        Integer::from_u128(ty, integer.unsigned())
+    };
+    match result {
+        Some(result) => Ok(Literal::Integer(result)),
+        None => halt(format!("cannot cast {integer} to {ty} without losing bits")),
+    }
+}
+
+/// `hash.*` into a field or integer. The digest is a stand-in: SHA-256 of the
+/// opcode and the input's text, so equal inputs hash equally and mappings
+/// keyed by a hash behave as on-chain, but the values are not snarkVM's.
+pub(super) fn hash(op: HashOp, input: &str, ty: Option<&PlaintextType>) -> Result<Literal> {
+    let digest = Sha256::new().chain_update(op.to_string()).chain_update([0]).chain_update(input).finalize();
+    let bits = u128::from_be_bytes(digest[..16].try_into().expect("a SHA-256 digest has 32 bytes"));
+    match ty {
+        None | Some(PlaintextType::Literal(LiteralType::Field)) => {
+            Ok(Literal::Opaque(crate::aleo::Literal { ty: LiteralType::Field, text: format!("{bits}field") }))
+        }
+        Some(PlaintextType::Literal(ty)) if ty.is_integer() => Ok(Literal::Integer(Integer::from_bits(*ty, bits))),
+        Some(ty) => Err(Failure::Unsupported(format!("`{op}` into {ty}"))),
+    }
+}
@@ -0,0 +1,483 @@
+//! Runtime values: literals, structs and records in plaintext.
+//!
+//! Values print the way `leo run` and `snarkos` show them, and [`Value::parse`]
+//! reads that form back, so an output record can be pasted in as the input of
+//! the next call.
+
+use std::fmt;
+
+use crate::address::Address;
+use crate::aleo::{self, LiteralType, Visibility};
+use crate::inputs;
+
+/// An integer of any width, stored as its two's complement bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Integer {
+    ty: LiteralType,
+    bits: u128,
+}
+
+impl Integer {
+    /// Creates an unsigned integer, or `None` if `value` does not fit `ty`.
+    pub fn from_u128(ty: LiteralType, value: u128) -> Option<Self> {
+        if ty.is_unsigned() {
+            (value <= max_unsigned(ty)).then_some(Self { ty, bits: value })
+        } else {
+            i128::try_from(value).ok().and_then(|value| Self::from_i128(ty, value))
+        }
+    }
+
+    /// Creates an integer from a signed value, or `None` if it does not fit `ty`.
+    pub fn from_i128(ty: LiteralType, value: i128) -> Option<Self> {
+        if ty.is_unsigned() {
+            return u128::try_from(value).ok().and_then(|value| Self::from_u128(ty, value));
+        }
+        let (min, max) = signed_range(ty);
+        (min <= value && value <= max).then(|| Self::from_bits(ty, value as u128))
+    }
+
+    /// Truncates `bits` to the width of `ty`, as wrapping operations do.
+    pub fn from_bits(ty: LiteralType, bits: u128) -> Self {
+        Self { ty, bits: bits & max_unsigned(unsigned_of(ty)) }
+    }
+
+    pub fn ty(self) -> LiteralType {
+        self.ty
+    }
+
+    pub fn bits(self) -> u128 {
+        self.bits
+    }
+
+    pub fn width(self) -> u32 {
+        width(self.ty)
+    }
+
+    /// The value of a signed integer, sign-extended.
+    pub fn signed(self) -> i128 {
+        let shift = 128 - self.width();
+        ((self.bits << shift) as i128) >> shift
+    }
+
+    /// The value of an unsigned integer.
+    pub fn unsigned(self) -> u128 {
+        self.bits
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.bits == 0
+    }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ty.is_signed() {
+            write!(f, "{}{}", self.signed(), self.ty)
+        } else {
+            write!(f, "{}{}", self.unsigned(), self.ty)
+        }
+    }
+}
+
+fn width(ty: LiteralType) -> u32 {
+    match ty {
+        LiteralType::I8 | LiteralType::U8 => 8,
+        LiteralType::I16 | LiteralType::U16 => 16,
+        LiteralType::I32 | LiteralType::U32 => 32,
+        LiteralType::I64 | LiteralType::U64 => 64,
+        _ => 128,
+    }
+}
+
+fn unsigned_of(ty: LiteralType) -> LiteralType {
+    match width(ty) {
+        8 => LiteralType::U8,
+        16 => LiteralType::U16,
+        32 => LiteralType::U32,
+        64 => LiteralType::U64,
+        _ => LiteralType::U128,
+    }
+}
+
+fn max_unsigned(ty: LiteralType) -> u128 {
+    u128::MAX >> (128 - width(ty))
+}
+
+fn signed_range(ty: LiteralType) -> (i128, i128) {
+    let max = i128::MAX >> (128 - width(ty));
+    (-max - 1, max)
+}
+
+/// A single value of a literal type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Literal {
+    Address(String),
+    Boolean(bool),
+    Integer(Integer),
+    /// A `field`, `group`, `scalar` or `signature`. These are passed through
+    /// and compared, but arithmetic on them needs the real curve and is not
+    /// interpreted.
+    Opaque(aleo::Literal),
+}
+
+impl Literal {
+    /// Converts a literal from program text, checking integers against the
+    /// range of their type and addresses against their checksum.
+    pub fn from_ast(literal: &aleo::Literal) -> Result<Self, String> {
+        Ok(match literal.ty {
+            LiteralType::Address => match Address::parse(&literal.text) {
+                Ok(address) => Literal::Address(address.to_string()),
+                Err(error) => return Err(format!("`{}` is not a valid address: {error}", literal.text)),
+            },
+            LiteralType::Boolean => Literal::Boolean(literal.text == "true"),
+            ty if ty.is_integer() => {
+                let digits = literal.value_text().replace('_', "");
+                let integer = digits
+                    .parse::<i128>()
+                    .ok()
+                    .and_then(|value| Integer::from_i128(ty, value))
+                    .or_else(|| digits.parse::<u128>().ok().and_then(|value| Integer::from_u128(ty, value)));
+                match integer {
+                    Some(integer) => Literal::Integer(integer),
+                    None => return Err(format!("`{}` is out of range for {ty}", literal.text)),
+                }
+            }
+            _ => Literal::Opaque(literal.clone()),
+        })
+    }
+
+    pub fn ty(&self) -> LiteralType {
+        match self {
+            Literal::Address(_) => LiteralType::Address,
+            Literal::Boolean(_) => LiteralType::Boolean,
+            Literal::Integer(integer) => integer.ty(),
+            Literal::Opaque(literal) => literal.ty,
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Address(address) => f.write_str(address),
+            Literal::Boolean(value) => write!(f, "{value}"),
+            Literal::Integer(integer) => write!(f, "{integer}"),
+            Literal::Opaque(literal) => write!(f, "{literal}"),
+        }
+    }
+}
+
+/// A literal or a struct of plaintext members.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Plaintext {
+    Literal(Literal),
+    Struct(Vec<(String, Plaintext)>),
+}
+
+impl Plaintext {
+    pub fn member(&self, name: &str) -> Option<&Plaintext> {
+        match self {
+            Plaintext::Struct(members) => members.iter().find(|(member, _)| member == name).map(|(_, value)| value),
+            Plaintext::Literal(_) => None,
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        match self {
+            Plaintext::Literal(literal) => write!(f, "{literal}"),
+            Plaintext::Struct(members) => {
+                let entries: Vec<_> = members.iter().map(|(name, value)| (name.as_str(), value, None)).collect();
+                fmt_entries(f, &entries, depth)
+            }
+        }
+    }
+}
+
+impl From<Literal> for Plaintext {
+    fn from(literal: Literal) -> Self {
+        Plaintext::Literal(literal)
+    }
+}
+
+impl fmt::Display for Plaintext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// One member of a record with the visibility it is stored with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub value: Plaintext,
+    pub visibility: Visibility,
+}
+
+/// A record: its members, starting with `owner`, and its `_nonce`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub entries: Vec<Entry>,
+    /// The `group` literal that makes the record unique.
+    pub nonce: String,
+}
+
+impl Record {
+    pub fn entry(&self, name: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    pub fn member(&self, name: &str) -> Option<&Plaintext> {
+        self.entry(name).map(|entry| &entry.value)
+    }
+
+    pub fn owner(&self) -> Option<&str> {
+        match self.member("owner")? {
+            Plaintext::Literal(Literal::Address(owner)) => Some(owner),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nonce =
+            Plaintext::Literal(Literal::Opaque(aleo::Literal { ty: LiteralType::Group, text: self.nonce.clone() }));
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), &entry.value, Some(entry.visibility)))
+            .chain([("_nonce", &nonce, Some(Visibility::Public))])
+            .collect();
+        fmt_entries(f, &entries, 0)
+    }
+}
+
+/// Prints `{ name: value[.visibility], ... }` across lines, indenting two
+/// spaces per level of nesting like snarkVM does.
+fn fmt_entries(
+    f: &mut fmt::Formatter<'_>,
+    entries: &[(&str, &Plaintext, Option<Visibility>)],
+    depth: usize,
+) -> fmt::Result {
+    writeln!(f, "{{")?;
+    let indent = "  ".repeat(depth + 1);
+    for (index, (name, value, visibility)) in entries.iter().enumerate() {
+        write!(f, "{indent}{name}: ")?;
+        value.fmt_indented(f, depth + 1)?;
+        if let (Plaintext::Literal(_), Some(visibility)) = (value, visibility) {
+            write!(f, ".{visibility}")?;
+        }
+        writeln!(f, "{}", if index + 1 < entries.len() { "," } else { "" })?;
+    }
+    write!(f, "{}}}", "  ".repeat(depth))
+}
+
+/// A register value: plaintext or a record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Plaintext(Plaintext),
+    Record(Record),
+}
+
+impl Value {
+    /// Converts a value from a Leo input file. A struct literal with a
+    /// `_nonce` member is a record, whose members are private.
+    pub fn from_input(value: &inputs::Value) -> Result<Self, String> {
+        let members = match value {
+            inputs::Value::Literal(literal) => return Ok(input_literal(literal)?.into()),
+            inputs::Value::Struct(literal) => &literal.members,
+        };
+        if !members.iter().any(|(name, _)| name.name == "_nonce") {
+            return input_plaintext(value).map(Value::Plaintext);
+        }
+        let mut entries = Vec::new();
+        let mut nonce = None;
+        for (name, value) in members {
+            match (name.name.as_str(), value) {
+                ("_nonce", inputs::Value::Literal(literal)) if literal.ty() == Some("group") => {
+                    nonce = Some(literal.text.clone())
+                }
+                ("_nonce", _) => return Err("`_nonce` must be a group literal".to_string()),
+                (_, value) => entries.push(Entry {
+                    name: name.name.clone(),
+                    value: input_plaintext(value)?,
+                    visibility: Visibility::Private,
+                }),
+            }
+        }
+        Ok(Value::Record(Record { entries, nonce: nonce.expect("checked above") }))
+    }
+
+    /// Parses a literal such as `10u32`, a struct `{ a: 1u8, b: true }`, or a
+    /// record, which is recognised by its `_nonce` member. Record members may
+    /// carry a `.private`/`.public` suffix and default to private.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = ValueParser { tokens: value_tokens(text)?, index: 0 };
+        let value = parser.value()?;
+        if let Some(token) = parser.tokens.get(parser.index) {
+            return Err(format!("unexpected `{token}` after the value"));
+        }
+        let members = match value {
+            Parsed::Literal(literal, _) => return Ok(literal.into()),
+            Parsed::Struct(members) => members,
+        };
+        if !members.iter().any(|(name, _)| name == "_nonce") {
+            return Ok(Value::Plaintext(Parsed::Struct(members).into_plaintext()));
+        }
+        let mut entries = Vec::new();
+        let mut nonce = None;
+        for (name, value) in members {
+            match (name.as_str(), value) {
+                ("_nonce", Parsed::Literal(Literal::Opaque(literal), _)) if literal.ty == LiteralType::Group => {
+                    nonce = Some(literal.text)
+                }
+                ("_nonce", _) => return Err("`_nonce` must be a group literal".to_string()),
+                (_, value) => {
+                    let visibility = value.visibility().unwrap_or(Visibility::Private);
+                    entries.push(Entry { name, value: value.into_plaintext(), visibility });
+                }
+            }
+        }
+        Ok(Value::Record(Record { entries, nonce: nonce.expect("checked above") }))
+    }
+}
+
+impl From<Plaintext> for Value {
+    fn from(plaintext: Plaintext) -> Self {
+        Value::Plaintext(plaintext)
+    }
+}
+
+impl From<Literal> for Value {
+    fn from(literal: Literal) -> Self {
+        Value::Plaintext(Plaintext::Literal(literal))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Plaintext(plaintext) => write!(f, "{plaintext}"),
+            Value::Record(record) => write!(f, "{record}"),
+        }
+    }
+}
+
+fn input_literal(literal: &inputs::Literal) -> Result<Literal, String> {
+    let parsed = aleo::Literal::parse(&literal.text).ok_or_else(|| format!("`{}` is not a literal", literal.text))?;
+    Literal::from_ast(&parsed)
+}
+
+fn input_plaintext(value: &inputs::Value) -> Result<Plaintext, String> {
+    match value {
+        inputs::Value::Literal(literal) => input_literal(literal).map(Plaintext::Literal),
+        inputs::Value::Struct(literal) => literal
+            .members
+            .iter()
+            .map(|(name, value)| Ok((name.name.clone(), input_plaintext(value)?)))
+            .collect::<Result<_, String>>()
+            .map(Plaintext::Struct),
+    }
+}
+
+fn value_tokens(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if "{}:,".contains(c) {
+            tokens.push(c.to_string());
+            chars.next();
+        } else if c.is_ascii_alphanumeric() || "_.-".contains(c) {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || "_.-".contains(**c)) {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(word);
+        } else {
+            return Err(format!("unexpected character `{c}`"));
+        }
+    }
+    Ok(tokens)
+}
+
+/// A value as written, before records are told apart from structs.
+enum Parsed {
+    Literal(Literal, Option<Visibility>),
+    Struct(Vec<(String, Parsed)>),
+}
+
+impl Parsed {
+    fn visibility(&self) -> Option<Visibility> {
+        match self {
+            Parsed::Literal(_, visibility) => *visibility,
+            Parsed::Struct(_) => None,
+        }
+    }
+
+    fn into_plaintext(self) -> Plaintext {
+        match self {
+            Parsed::Literal(literal, _) => Plaintext::Literal(literal),
+            Parsed::Struct(members) => {
+                Plaintext::Struct(members.into_iter().map(|(name, value)| (name, value.into_plaintext())).collect())
+            }
+        }
+    }
+}
+
+struct ValueParser {
+    tokens: Vec<String>,
+    index: usize,
+}
+
+impl ValueParser {
+    fn next(&mut self) -> Result<&str, String> {
+        let token = self.tokens.get(self.index).ok_or("unexpected end of value")?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(format!("expected `{expected}`, found `{token}`")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Parsed, String> {
+        let token = self.next()?.to_string();
+        if token != "{" {
+            let (text, visibility) = match token.rsplit_once('.') {
+                Some((text, "private")) => (text, Some(Visibility::Private)),
+                Some((text, "public")) => (text, Some(Visibility::Public)),
+                Some((text, "constant")) => (text, Some(Visibility::Constant)),
+                _ => (token.as_str(), None),
+            };
+            let literal = aleo::Literal::parse(text).ok_or_else(|| format!("`{text}` is not a literal"))?;
+            return Ok(Parsed::Literal(Literal::from_ast(&literal)?, visibility));
+        }
+        let mut members: Vec<(String, Parsed)> = Vec::new();
+        loop {
+            let name = self.next()?.to_string();
+            if name == "}" {
+                // A trailing comma.
+                return match members.is_empty() {
+                    true => Err("empty struct".to_string()),
+                    false => Ok(Parsed::Struct(members)),
+                };
+            }
+            if members.iter().any(|(member, _)| *member == name) {
+                return Err(format!("member `{name}` is given twice"));
+            }
+            self.expect(":")?;
+            members.push((name, self.value()?));
+            match self.next()? {
+                "," => {}
+                "}" => return Ok(Parsed::Struct(members)),
+                token => return Err(format!("expected `,` or `}}`, found `{token}`")),
+            }
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! An encrypted, per-user store of named Aleo private keys.
+//!
+//! Each account is a JSON file `<name>.json` in the keystore directory,
+//! `$WORKSHOP_KEYSTORE` or `~/.aleo-workshop/keystore`. The private key in it
+//! is encrypted with ChaCha20-Poly1305 under a key derived from a passphrase
+//! with scrypt, so nothing in a package, a script or the environment has to
+//! hold the key itself:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "address": "aleo1...",
+//!   "kdf": { "name": "scrypt", "log_n": 15, "r": 8, "p": 1, "salt": "..." },
+//!   "cipher": { "name": "chacha20poly1305", "nonce": "..." },
+//!   "ciphertext": "..."
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::address::Address;
+use crate::deploy::{DeployError, PrivateKey};
+
+/// Overrides the keystore directory.
+pub const KEYSTORE_DIR_VAR: &str = "WORKSHOP_KEYSTORE";
+
+/// Supplies the passphrase without a prompt, for scripts and CI.
+pub const PASSPHRASE_VAR: &str = "WORKSHOP_KEYSTORE_PASSPHRASE";
+
+const VERSION: u32 = 1;
+const KDF: &str = "scrypt";
+const CIPHER: &str = "chacha20poly1305";
+/// scrypt cost parameters: 2^15 iterations of 8 blocks, about 32 MiB.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const SALT_LENGTH: usize = 16;
+
+/// The bytes every base58-decoded private key starts with, before its
+/// 32-byte little-endian seed.
+const PRIVATE_KEY_PREFIX: [u8; 11] = [127, 134, 189, 116, 210, 221, 210, 137, 145, 18, 253];
+
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    #[error("cannot find the keystore: set `{KEYSTORE_DIR_VAR}` or `HOME`")]
+    NoDirectory,
+    #[error("`{0}` is not a valid account name: use letters, digits, `-` and `_`")]
+    InvalidName(String),
+    #[error("an account named `{0}` already exists; remove it first")]
+    Exists(String),
+    #[error("no account named `{name}` in `{}`{}", dir.display(), known_accounts(known))]
+    Unknown { name: String, dir: PathBuf, known: Vec<String> },
+    #[error("wrong passphrase for account `{0}`")]
+    WrongPassphrase(String),
+    #[error("the passphrases do not match")]
+    PassphraseMismatch,
+    #[error("the passphrase is empty")]
+    EmptyPassphrase,
+    #[error("`{}` is not a valid keystore file: {message}", path.display())]
+    Corrupt { path: PathBuf, message: String },
+    #[error("failed to access `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error(transparent)]
+    Key(#[from] DeployError),
+}
+
+fn known_accounts(known: &[String]) -> String {
+    if known.is_empty() {
+        "; it has no accounts yet".to_string()
+    } else {
+        format!("; known accounts: {}", known.join(", "))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AccountFile {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    kdf: KdfParams,
+    cipher: CipherParams,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    name: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    name: String,
+    nonce: String,
+}
+
+/// An account in the keystore, without its key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+    pub name: String,
+    /// The address, when it was given on import or with `set_address`;
+    /// deriving it from the key needs snarkVM.
+    pub address: Option<Address>,
+    pub path: PathBuf,
+}
+
+/// A directory of encrypted accounts.
+#[derive(Clone, Debug)]
+pub struct Keystore {
+    dir: PathBuf,
+}
+
+impl Keystore {
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The keystore at `$WORKSHOP_KEYSTORE`, or `~/.aleo-workshop/keystore`.
+    pub fn open_default() -> Result<Self, KeystoreError> {
+        if let Some(dir) = std::env::var_os(KEYSTORE_DIR_VAR).filter(|dir| !dir.is_empty()) {
+            return Ok(Self::open(dir));
+        }
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        let home = home.filter(|home| !home.is_empty()).ok_or(KeystoreError::NoDirectory)?;
+        Ok(Self::open(Path::new(&home).join(".aleo-workshop").join("keystore")))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Every account, sorted by name. A missing directory has none.
+    pub fn accounts(&self) -> Result<Vec<Account>, KeystoreError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => return Err(KeystoreError::Io { path: self.dir.clone(), source }),
+        };
+        let mut accounts = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|source| KeystoreError::Io { path: self.dir.clone(), source })?.path();
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            if path.extension().is_some_and(|extension| extension == "json") && valid_name(name) {
+                let file = read_account(&path)?;
+                accounts.push(Account { name: name.to_string(), address: account_address(&path, &file)?, path });
+            }
+        }
+        accounts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(accounts)
+    }
+
+    /// The account `name`, or an error listing the known ones.
+    pub fn account(&self, name: &str) -> Result<Account, KeystoreError> {
+        let accounts = self.accounts()?;
+        let known = accounts.iter().map(|account| account.name.clone()).collect();
+        accounts.into_iter().find(|account| account.name == name).ok_or_else(|| KeystoreError::Unknown {
+            name: name.to_string(),
+            dir: self.dir.clone(),
+            known,
+        })
+    }
+
+    /// Encrypts `key` under `passphrase` and stores it as a new account.
+    pub fn add(
+        &self,
+        name: &str,
+        key: &PrivateKey,
+        address: Option<Address>,
+        passphrase: &str,
+    ) -> Result<Account, KeystoreError> {
+        if !valid_name(name) {
+            return Err(KeystoreError::InvalidName(name.to_string()));
+        }
+        if passphrase.is_empty() {
+            return Err(KeystoreError::EmptyPassphrase);
+        }
+        let path = self.path(name);
+        if path.exists() {
+            return Err(KeystoreError::Exists(name.to_string()));
+        }
+
+        let mut salt = [0; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        let key_bytes =
+            derive_key(passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P).expect("the defaults are valid");
+        let cipher = ChaCha20Poly1305::new(&key_bytes);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher.encrypt(&nonce, key.expose().as_bytes()).expect("encrypting in memory cannot fail");
+        let file = AccountFile {
+            version: VERSION,
+            address: address.as_ref().map(Address::to_string),
+            kdf: KdfParams {
+                name: KDF.to_string(),
+                log_n: SCRYPT_LOG_N,
+                r: SCRYPT_R,
+                p: SCRYPT_P,
+                salt: hex::encode(salt),
+            },
+            cipher: CipherParams { name: CIPHER.to_string(), nonce: hex::encode(nonce) },
+            ciphertext: hex::encode(ciphertext),
+        };
+        let text = serde_json::to_string_pretty(&file).expect("account files serialize");
+        create_private_dir(&self.dir)?;
+        write_private_file(&path, &format!("{text}\n"))?;
+        Ok(Account { name: name.to_string(), address, path })
+    }
+
+    /// Records the address of account `name`, for accounts created by `new`
+    /// or imported without one.
+    pub fn set_address(&self, name: &str, address: Address) -> Result<Account, KeystoreError> {
+        let account = self.account(name)?;
+        let mut file = read_account(&account.path)?;
+        file.address = Some(address.to_string());
+        let text = serde_json::to_string_pretty(&file).expect("account files serialize");
+        // Write a new file beside the old one and swap it in, so a failed
+        // write cannot lose the key.
+        let temporary = account.path.with_extension("json.tmp");
+        let _ = std::fs::remove_file(&temporary);
+        write_private_file(&temporary, &format!("{text}\n"))?;
+        std::fs::rename(&temporary, &account.path)
+            .map_err(|source| KeystoreError::Io { path: account.path.clone(), source })?;
+        Ok(Account { address: Some(address), ..account })
+    }
+
+    /// Decrypts the private key of account `name`.
+    pub fn unlock(&self, name: &str, passphrase: &str) -> Result<PrivateKey, KeystoreError> {
+        let account = self.account(name)?;
+        let file = read_account(&account.path)?;
+        let corrupt = |message: &str| KeystoreError::Corrupt { path: account.path.clone(), message: message.into() };
+        if file.version != VERSION || file.kdf.name != KDF || file.cipher.name != CIPHER {
+            return Err(corrupt("unsupported version, key derivation or cipher"));
+        }
+        let salt = hex::decode(&file.kdf.salt).map_err(|_| corrupt("the salt is not hex"))?;
+        let nonce = hex::decode(&file.cipher.nonce).map_err(|_| corrupt("the nonce is not hex"))?;
+        let ciphertext = hex::decode(&file.ciphertext).map_err(|_| corrupt("the ciphertext is not hex"))?;
+        if nonce.len() != 12 {
+            return Err(corrupt("the nonce is not 12 bytes"));
+        }
+
+        let key = derive_key(passphrase, &salt, file.kdf.log_n, file.kdf.r, file.kdf.p)
+            .ok_or_else(|| corrupt("invalid scrypt parameters"))?;
+        let plaintext = ChaCha20Poly1305::new(&key)
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| KeystoreError::WrongPassphrase(name.to_string()))?;
+        let text = String::from_utf8(plaintext).map_err(|_| corrupt("the decrypted key is not text"))?;
+        Ok(PrivateKey::parse(&text, &format!("account `{name}`"))?)
+    }
+
+    /// Deletes account `name`.
+    pub fn remove(&self, name: &str) -> Result<Account, KeystoreError> {
+        let account = self.account(name)?;
+        std::fs::remove_file(&account.path)
+            .map_err(|source| KeystoreError::Io { path: account.path.clone(), source })?;
+        Ok(account)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+/// Generates a fresh private key from the operating system's random source.
+pub fn generate_private_key() -> PrivateKey {
+    let mut bytes = [0; 43];
+    bytes[..PRIVATE_KEY_PREFIX.len()].copy_from_slice(&PRIVATE_KEY_PREFIX);
+    OsRng.fill_bytes(&mut bytes[PRIVATE_KEY_PREFIX.len()..]);
+    // The seed is a field element; clearing the top four bits keeps it below
+    // the modulus, which is just over 2^252.
+    bytes[42] &= 0x0f;
+    PrivateKey::parse(&bs58::encode(bytes).into_string(), "the generator").expect("generated keys are well formed")
+}
+
+/// Checks that `key` decodes to a private key prefix and seed, which the
+/// shape check in [`PrivateKey::parse`] does not.
+pub fn check_private_key(key: &PrivateKey) -> Result<(), DeployError> {
+    let malformed = || DeployError::MalformedKey { source_name: "the imported key".to_string() };
+    let bytes = bs58::decode(key.expose()).into_vec().map_err(|_| malformed())?;
+    if bytes.len() != 43 || bytes[..PRIVATE_KEY_PREFIX.len()] != PRIVATE_KEY_PREFIX {
+        return Err(malformed());
+    }
+    Ok(())
+}
+
+/// Reads a passphrase from `$WORKSHOP_KEYSTORE_PASSPHRASE` or, failing
+/// that, prompts for it on the terminal without echoing; with `confirm`, the
+/// prompt asks twice.
+pub fn read_passphrase(prompt: &str, confirm: bool) -> Result<String, KeystoreError> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    let io = |source| KeystoreError::Io { path: PathBuf::from("<terminal>"), source };
+    let passphrase = rpassword::prompt_password(prompt).map_err(io)?;
+    if passphrase.is_empty() {
+        return Err(KeystoreError::EmptyPassphrase);
+    }
+    if confirm && rpassword::prompt_password("Repeat the passphrase: ").map_err(io)? != passphrase {
+        return Err(KeystoreError::PassphraseMismatch);
+    }
+    Ok(passphrase)
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Derives the encryption key, or `None` if the scrypt parameters are invalid.
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Option<Key> {
+    let params = scrypt::Params::new(log_n, r, p, 32).ok()?;
+    let mut key = Key::default();
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key).expect("the output length is valid");
+    Some(key)
+}
+
+fn read_account(path: &Path) -> Result<AccountFile, KeystoreError> {
+    let text =
+        std::fs::read_to_string(path).map_err(|source| KeystoreError::Io { path: path.to_path_buf(), source })?;
+    serde_json::from_str(&text)
+        .map_err(|error| KeystoreError::Corrupt { path: path.to_path_buf(), message: error.to_string() })
+}
+
+fn account_address(path: &Path, file: &AccountFile) -> Result<Option<Address>, KeystoreError> {
+    file.address
+        .as_deref()
+        .map(Address::parse)
+        .transpose()
+        .map_err(|error| KeystoreError::Corrupt { path: path.to_path_buf(), message: format!("address: {error}") })
+}
+
+fn create_private_dir(dir: &Path) -> Result<(), KeystoreError> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir).map_err(|source| KeystoreError::Io { path: dir.to_path_buf(), source })
+}
+
+fn write_private_file(path: &Path, text: &str) -> Result<(), KeystoreError> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let io = |source| KeystoreError::Io { path: path.to_path_buf(), source };
+    options.open(path).and_then(|mut file| file.write_all(text.as_bytes())).map_err(io)
+}
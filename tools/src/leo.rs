@@ -0,0 +1,141 @@
+//! A light scanner over Leo sources for the pieces the tooling needs
+//! without a full Leo front end: the program id and transition parameters.
+
+use crate::diagnostic::{Position, Span};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: String,
+    pub ty: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Token<'a> {
+    text: &'a str,
+    span: Span,
+}
+
+/// Splits Leo source into identifier-like words and single punctuation
+/// characters, dropping whitespace and comments.
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    while let Some(&(start, c)) = chars.peek() {
+        let begin = Position::new(line, column);
+        let mut advance = |c: char| {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        };
+
+        if c.is_whitespace() {
+            advance(c);
+            chars.next();
+        } else if source[start..].starts_with("//") {
+            while let Some(&(_, c)) = chars.peek() {
+                if c == '\n' {
+                    break;
+                }
+                advance(c);
+                chars.next();
+            }
+        } else if source[start..].starts_with("/*") {
+            let mut previous = ' ';
+            for (_, c) in chars.by_ref() {
+                advance(c);
+                if previous == '*' && c == '/' {
+                    break;
+                }
+                previous = c;
+            }
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                advance(c);
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token { text: &source[start..end], span: Span::new(begin, Position::new(line, column)) });
+        } else {
+            advance(c);
+            chars.next();
+            let end = start + c.len_utf8();
+            tokens.push(Token { text: &source[start..end], span: Span::new(begin, Position::new(line, column)) });
+        }
+    }
+    tokens
+}
+
+/// Returns the `program <name>.aleo` id declared by a Leo source, with the
+/// span of the name.
+pub fn program_id(source: &str) -> Option<(String, Span)> {
+    let tokens = tokenize(source);
+    let index = tokens.iter().position(|token| token.text == "program")?;
+    match &tokens[index + 1..] {
+        [name, dot, network, ..] if dot.text == "." => Some((format!("{}.{}", name.text, network.text), name.span)),
+        _ => None,
+    }
+}
+
+/// Returns every `transition` declared by a Leo source in order.
+pub fn transitions(source: &str) -> Vec<Transition> {
+    let tokens = tokenize(source);
+    let mut transitions = Vec::new();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        if tokens[index].text != "transition" || index + 2 >= tokens.len() || tokens[index + 2].text != "(" {
+            index += 1;
+            continue;
+        }
+        let name = &tokens[index + 1];
+        let mut parameters = Vec::new();
+        let mut cursor = index + 3;
+        let mut current: Vec<&Token> = Vec::new();
+        let mut depth = 0;
+        while cursor < tokens.len() {
+            let token = &tokens[cursor];
+            cursor += 1;
+            match token.text {
+                "(" => depth += 1,
+                ")" if depth > 0 => depth -= 1,
+                ")" | "," if depth == 0 => {
+                    parameters.extend(parameter(&current));
+                    current.clear();
+                    if token.text == ")" {
+                        break;
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(token);
+        }
+        transitions.push(Transition { name: name.text.to_string(), parameters, span: name.span });
+        index = cursor;
+    }
+    transitions
+}
+
+/// Builds a parameter from `[visibility] name : type...`.
+fn parameter(tokens: &[&Token]) -> Option<Parameter> {
+    let colon = tokens.iter().position(|token| token.text == ":")?;
+    let name = tokens[..colon].last()?.text.to_string();
+    let ty = tokens[colon + 1..].iter().map(|token| token.text).collect::<String>();
+    Some(Parameter { name, ty })
+}
@@ -0,0 +1,24 @@
+//! Tooling for the workshop's Aleo packages: checking program inputs,
+//! inspecting compiled programs and preparing deployments.
+
+pub mod abi;
+pub mod address;
+pub mod aleo;
+pub mod amount;
+pub mod arithmetic;
+pub mod build;
+pub mod deploy;
+pub mod diagnostic;
+pub mod doctor;
+pub mod explain;
+pub mod fee;
+pub mod inputs;
+pub mod interpreter;
+pub mod keystore;
+pub mod leo;
+pub mod memo;
+pub mod mock_node;
+pub mod network;
+pub mod package;
+pub mod rename;
+pub mod wallet;
@@ -0,0 +1,110 @@
+//! Short text memos carried in field elements.
+//!
+//! `transfer_with_memo` gives the receiver a `TransferMemo` record next to the
+//! tokens, holding a `memo` field. [`encode_memo`] packs up to 31 bytes of
+//! UTF-8 into a field, big-endian like the token's `name` and `symbol`, and
+//! [`decode_memo`] unpacks it again. `0field` is the empty memo.
+//!
+//! ```
+//! use workshop::memo::{decode_memo, encode_memo};
+//!
+//! assert_eq!(encode_memo("hi").unwrap(), "26729field");
+//! assert_eq!(decode_memo("26729field"), Ok("hi".to_string()));
+//! ```
+
+use thiserror::Error;
+
+use crate::interpreter::{Literal, Plaintext, Record};
+
+/// The most bytes a memo can hold: 31 bytes always fit below the field
+/// modulus, 32 do not.
+pub const MEMO_CAPACITY: usize = 31;
+
+/// The record member holding a memo.
+pub const MEMO_MEMBER: &str = "memo";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MemoError {
+    #[error("the memo is {0} bytes of UTF-8, more than the {MEMO_CAPACITY} a field holds")]
+    TooLong(usize),
+    #[error("a memo cannot contain NUL characters")]
+    Nul,
+    #[error("`{0}` is not a field literal")]
+    NotField(String),
+    #[error("`{0}` does not hold UTF-8 text")]
+    NotText(String),
+}
+
+/// Packs `text` into a field literal such as `26729field`, ready to pass as
+/// the `memo` input.
+pub fn encode_memo(text: &str) -> Result<String, MemoError> {
+    if text.len() > MEMO_CAPACITY {
+        return Err(MemoError::TooLong(text.len()));
+    }
+    if text.contains('\0') {
+        return Err(MemoError::Nul);
+    }
+    let mut value = [0u8; MEMO_CAPACITY];
+    value[MEMO_CAPACITY - text.len()..].copy_from_slice(text.as_bytes());
+
+    let mut digits = Vec::new();
+    while value.iter().any(|&byte| byte != 0) {
+        digits.push(char::from(b'0' + divide(&mut value, 10)));
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    Ok(format!("{}field", digits.iter().rev().collect::<String>()))
+}
+
+/// The inverse of [`encode_memo`]. Takes a field literal, with or without
+/// its `field` suffix and visibility.
+pub fn decode_memo(field: &str) -> Result<String, MemoError> {
+    let not_field = || MemoError::NotField(field.to_string());
+    let digits = field.split('.').next().unwrap_or_default();
+    let digits = digits.strip_suffix("field").unwrap_or(digits).replace('_', "");
+    if digits.is_empty() {
+        return Err(not_field());
+    }
+    let mut value = [0u8; MEMO_CAPACITY];
+    for character in digits.chars() {
+        let digit = character.to_digit(10).ok_or_else(not_field)?;
+        // A carry out of the top byte means the field is wider than any memo.
+        if multiply_add(&mut value, 10, digit as u8) != 0 {
+            return Err(MemoError::NotText(field.to_string()));
+        }
+    }
+    let bytes: Vec<u8> = value.into_iter().skip_while(|&byte| byte == 0).collect();
+    String::from_utf8(bytes).map_err(|_| MemoError::NotText(field.to_string()))
+}
+
+/// The decoded memo of a record with a `memo` field, such as `TransferMemo`.
+pub fn record_memo(record: &Record) -> Option<Result<String, MemoError>> {
+    match record.member(MEMO_MEMBER)? {
+        Plaintext::Literal(literal @ Literal::Opaque(_)) => Some(decode_memo(&literal.to_string())),
+        _ => None,
+    }
+}
+
+/// Divides a big-endian number in place, returning the remainder.
+fn divide(value: &mut [u8], divisor: u8) -> u8 {
+    let mut remainder = 0u16;
+    for byte in value.iter_mut() {
+        let current = (remainder << 8) | u16::from(*byte);
+        *byte = (current / u16::from(divisor)) as u8;
+        remainder = current % u16::from(divisor);
+    }
+    remainder as u8
+}
+
+/// Sets a big-endian number to `value * factor + addend`, returning the carry
+/// out of its top byte.
+fn multiply_add(value: &mut [u8], factor: u8, addend: u8) -> u16 {
+    let mut carry = u16::from(addend);
+    for byte in value.iter_mut().rev() {
+        let current = u16::from(*byte) * u16::from(factor) + carry;
+        *byte = current as u8;
+        carry = current >> 8;
+    }
+    carry
+}
@@ -0,0 +1,362 @@
+//! An offline stand-in for a node's REST API.
+//!
+//! [`MockNode`] answers what `snarkos developer deploy` asks of `--query` and
+//! `--broadcast`: the latest height and state root, deployed programs, their
+//! mappings and mapping values, and transaction broadcasts. Accepted
+//! transactions are written to its directory and each advances the height by
+//! one block, so a deployment against it succeeds the same way every time,
+//! in CI or on a machine without network access.
+//!
+//! A deployment adds its program. Executions are stored but their finalize
+//! blocks are not run: mapping values are whatever `state.json` holds, and an
+//! account missing from `credits.aleo/account` reads as [`DEFAULT_CREDITS`] so
+//! public fees are always covered.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::address::encode_bech32m;
+
+/// The network name every route starts with, as in `/testnet3/latest/height`.
+pub const NETWORK: &str = "testnet3";
+
+/// The port snarkOS serves its REST API on, which the `local` profile in
+/// `networks.toml` points at.
+pub const DEFAULT_PORT: u16 = 3030;
+
+/// The node's state, in its directory.
+pub const STATE_FILE: &str = "state.json";
+
+/// Where accepted transactions are written, one `<id>.json` each.
+pub const TRANSACTIONS_DIR: &str = "transactions";
+
+/// The public balance of an account the state does not mention: 1,000
+/// credits, in microcredits.
+pub const DEFAULT_CREDITS: u64 = 1_000_000_000;
+
+/// The largest request body the node reads, far above any deployment.
+pub const MAX_BODY_LENGTH: usize = 8 * 1024 * 1024;
+
+/// How long a connection may stall before the node gives up on it.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The characters of a bech32 data part, which follow `at1` in an id.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+const CREDITS_PROGRAM: &str = "credits.aleo";
+const CREDITS_MAPPING: &str = "account";
+
+#[derive(Debug, Error)]
+pub enum MockNodeError {
+    #[error("failed to access `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("`{}` is not valid node state: {source}", path.display())]
+    Parse { path: PathBuf, source: serde_json::Error },
+    #[error("cannot add the program: {0}")]
+    Program(String),
+}
+
+/// Everything the node knows, persisted as `state.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeState {
+    pub height: u32,
+    /// Program source by program id.
+    pub programs: BTreeMap<String, String>,
+    /// Values by program id, mapping name and key, all as literal text such
+    /// as `"aleo1..." => "100u128"`. Edit the file to seed them.
+    #[serde(default)]
+    pub mappings: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+    /// Accepted transaction ids, oldest first.
+    #[serde(default)]
+    pub transactions: Vec<String>,
+    /// The `credits.aleo/account` balance of accounts not in `mappings`.
+    #[serde(default = "default_credits")]
+    pub credits: u64,
+}
+
+fn default_credits() -> u64 {
+    DEFAULT_CREDITS
+}
+
+impl Default for NodeState {
+    fn default() -> Self {
+        Self {
+            height: 0,
+            programs: BTreeMap::new(),
+            mappings: BTreeMap::new(),
+            transactions: Vec::new(),
+            credits: DEFAULT_CREDITS,
+        }
+    }
+}
+
+/// An HTTP response: JSON on success, a plain-text message otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json(value: serde_json::Value) -> Self {
+        Self { status: 200, body: value.to_string() }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: message.into() }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        if self.status == 200 {
+            "application/json"
+        } else {
+            "text/plain; charset=utf-8"
+        }
+    }
+}
+
+pub struct MockNode {
+    dir: PathBuf,
+    state: NodeState,
+}
+
+impl MockNode {
+    /// Opens the node kept in `dir`, creating it empty at height 0 if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, MockNodeError> {
+        let dir = dir.into();
+        let path = dir.join(STATE_FILE);
+        let state = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|source| MockNodeError::Parse { path, source })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => NodeState::default(),
+            Err(source) => return Err(MockNodeError::Io { path, source }),
+        };
+        Ok(Self { dir, state })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn state(&self) -> &NodeState {
+        &self.state
+    }
+
+    /// Makes a program available as if deployed, e.g. one the package
+    /// imports, without a transaction or a new block. Adding the same source
+    /// again does nothing.
+    pub fn add_program(&mut self, source: &str) -> Result<String, MockNodeError> {
+        if let Some((id, _)) = self.state.programs.iter().find(|(_, existing)| *existing == source) {
+            return Ok(id.clone());
+        }
+        let id = self.insert_program(source).map_err(MockNodeError::Program)?;
+        self.save()?;
+        Ok(id)
+    }
+
+    /// The state root at the current height: a field, written `ar1...`,
+    /// derived from the height so it changes with every block.
+    pub fn state_root(&self) -> String {
+        let digest = Sha256::new().chain_update(b"state root").chain_update(self.state.height.to_le_bytes()).finalize();
+        let mut bytes: [u8; 32] = digest.into();
+        // Little-endian, and kept below 2^252 so it is less than the modulus.
+        bytes[31] &= 0x0f;
+        encode_bech32m("ar", &bytes)
+    }
+
+    /// Answers one request. `path` is everything after the host, such as
+    /// `/testnet3/latest/height`.
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> Response {
+        let path = path.split('?').next().unwrap_or_default();
+        let Some(route) = path.strip_prefix('/').and_then(|path| path.strip_prefix(NETWORK)) else {
+            return Response::error(404, format!("this node only serves `/{NETWORK}/...`"));
+        };
+        let segments: Vec<&str> = route.split('/').filter(|segment| !segment.is_empty()).collect();
+        match (method, segments.as_slice()) {
+            ("POST", ["transaction", "broadcast"]) => self.broadcast(body),
+            (_, ["transaction", "broadcast"]) => Response::error(405, "broadcast with POST"),
+            ("GET", route) => self.query(route),
+            _ => Response::error(405, format!("`{method}` is not supported")),
+        }
+    }
+
+    fn query(&self, route: &[&str]) -> Response {
+        match route {
+            ["latest", "height"] => Response::json(json!(self.state.height)),
+            ["latest", "stateRoot"] | ["stateRoot", "latest"] => Response::json(json!(self.state_root())),
+            ["program", id] => match self.state.programs.get(*id) {
+                Some(source) => Response::json(json!(source)),
+                None => Response::error(404, format!("program `{id}` does not exist")),
+            },
+            ["program", id, "mappings"] => match self.mapping_names(id) {
+                Some(names) => Response::json(json!(names)),
+                None => Response::error(404, format!("program `{id}` does not exist")),
+            },
+            ["program", id, "mapping", name, key] => self.mapping_value(id, name, key),
+            ["transaction", id] if self.state.transactions.iter().any(|known| known == id) => {
+                let path = self.dir.join(TRANSACTIONS_DIR).join(format!("{id}.json"));
+                match fs::read_to_string(&path) {
+                    Ok(transaction) => Response { status: 200, body: transaction },
+                    Err(error) => Response::error(500, format!("failed to read `{}`: {error}", path.display())),
+                }
+            }
+            ["transaction", id] => Response::error(404, format!("transaction `{id}` does not exist")),
+            _ => Response::error(404, format!("no route `/{NETWORK}/{}`", route.join("/"))),
+        }
+    }
+
+    fn mapping_names(&self, id: &str) -> Option<Vec<String>> {
+        let program = crate::aleo::parse(self.state.programs.get(id)?).ok()?;
+        Some(program.mappings().map(|mapping| mapping.name.name.clone()).collect())
+    }
+
+    fn mapping_value(&self, id: &str, name: &str, key: &str) -> Response {
+        let stored =
+            self.state.mappings.get(id).and_then(|mappings| mappings.get(name)).and_then(|values| values.get(key));
+        if id == CREDITS_PROGRAM && name == CREDITS_MAPPING {
+            let value = stored.cloned().unwrap_or_else(|| format!("{}u64", self.state.credits));
+            return Response::json(json!(value));
+        }
+        match self.mapping_names(id) {
+            Some(names) if names.iter().any(|known| known == name) => Response::json(json!(stored)),
+            Some(_) => Response::error(404, format!("program `{id}` has no mapping `{name}`")),
+            None => Response::error(404, format!("program `{id}` does not exist")),
+        }
+    }
+
+    fn broadcast(&mut self, body: &str) -> Response {
+        let transaction: serde_json::Value = match serde_json::from_str(body) {
+            Ok(transaction) => transaction,
+            Err(error) => return Response::error(400, format!("the transaction is not JSON: {error}")),
+        };
+        // The id names the file the transaction is written to.
+        let Some(id) = transaction["id"].as_str().filter(|id| is_transaction_id(id)) else {
+            return Response::error(400, "the transaction has no `at1...` id");
+        };
+        if self.state.transactions.iter().any(|known| known == id) {
+            return Response::error(400, format!("transaction `{id}` already exists"));
+        }
+        match transaction["type"].as_str() {
+            Some("deploy") => {
+                let Some(source) = transaction["deployment"]["program"].as_str() else {
+                    return Response::error(400, "the deployment has no program");
+                };
+                if let Err(message) = self.insert_program(source) {
+                    return Response::error(400, message);
+                }
+            }
+            Some("execute" | "fee") => {}
+            _ => return Response::error(400, "the transaction `type` must be deploy, execute or fee"),
+        }
+
+        let path = self.dir.join(TRANSACTIONS_DIR).join(format!("{id}.json"));
+        let written = fs::create_dir_all(self.dir.join(TRANSACTIONS_DIR)).and_then(|()| fs::write(&path, body));
+        if let Err(error) = written {
+            return Response::error(500, format!("failed to write `{}`: {error}", path.display()));
+        }
+        self.state.transactions.push(id.to_string());
+        self.state.height += 1;
+        match self.save() {
+            Ok(()) => Response::json(json!(id)),
+            Err(error) => Response::error(500, error.to_string()),
+        }
+    }
+
+    fn insert_program(&mut self, source: &str) -> Result<String, String> {
+        let program = crate::aleo::parse(source).map_err(|error| format!("the program does not parse: {error}"))?;
+        let id = program.id.to_string();
+        if self.state.programs.contains_key(&id) {
+            return Err(format!("program `{id}` already exists"));
+        }
+        self.state.programs.insert(id.clone(), source.to_string());
+        Ok(id)
+    }
+
+    fn save(&self) -> Result<(), MockNodeError> {
+        fs::create_dir_all(&self.dir).map_err(|source| MockNodeError::Io { path: self.dir.clone(), source })?;
+        let path = self.dir.join(STATE_FILE);
+        let text = serde_json::to_string_pretty(&self.state).expect("node state serializes");
+        fs::write(&path, text + "\n").map_err(|source| MockNodeError::Io { path, source })
+    }
+
+    /// Answers connections one at a time until the listener fails, logging
+    /// each request to standard error.
+    pub fn serve(&mut self, listener: &TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            if let Err(error) = self.connection(stream?) {
+                eprintln!("connection failed: {error}");
+            }
+        }
+        Ok(())
+    }
+
+    fn connection(&mut self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = BufReader::new(&stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+        let mut length = Some(0);
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().ok();
+                }
+            }
+        }
+        let response = match length {
+            None => Response::error(400, "`Content-Length` is not a number"),
+            Some(length) if length > MAX_BODY_LENGTH => {
+                Response::error(413, format!("the body is over the {MAX_BODY_LENGTH} byte limit"))
+            }
+            Some(length) => {
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body)?;
+                self.handle(method, path, &String::from_utf8_lossy(&body))
+            }
+        };
+        eprintln!("{method} {path} -> {}", response.status);
+        let mut stream = &stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            response.reason(),
+            response.content_type(),
+            response.body.len(),
+            response.body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Whether `id` is `at1` followed by lowercase bech32 characters, as
+/// transaction ids are.
+fn is_transaction_id(id: &str) -> bool {
+    id.strip_prefix("at1").is_some_and(|data| !data.is_empty() && data.chars().all(|c| BECH32_CHARSET.contains(c)))
+}
@@ -0,0 +1,104 @@
+//! Named network profiles: where to query state, where to broadcast
+//! transactions and which fee to pay.
+//!
+//! Profiles live in `networks.toml` next to the package:
+//!
+//! ```toml
+//! default = "testnet3"
+//!
+//! [networks.testnet3]
+//! query = "https://vm.aleo.org/api"
+//! broadcast = "https://vm.aleo.org/api/testnet3/transaction/broadcast"
+//! fee = 1000000
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The profile file looked up in the package directory.
+pub const CONFIG_FILE: &str = "networks.toml";
+
+/// The profile used when no configuration file exists.
+pub const BUILTIN_NETWORK: &str = "testnet3";
+
+/// The fee `deploy.sh` has always paid, in microcredits.
+pub const DEFAULT_FEE: u64 = 1_000_000;
+
+#[derive(Debug, Error)]
+pub enum NetworkError {
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("`{}` is not a valid network config: {source}", path.display())]
+    Parse { path: PathBuf, source: toml::de::Error },
+    #[error("no network named `{name}`; known networks: {}", known.join(", "))]
+    Unknown { name: String, known: Vec<String> },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    /// Base URL of the node API used for `--query`.
+    pub query: String,
+    /// Full URL transactions are posted to.
+    pub broadcast: String,
+    /// Deployment fee in microcredits.
+    #[serde(default = "default_fee")]
+    pub fee: u64,
+}
+
+fn default_fee() -> u64 {
+    DEFAULT_FEE
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworksConfig {
+    /// The profile selected when none is named on the command line.
+    pub default: String,
+    pub networks: BTreeMap<String, NetworkProfile>,
+}
+
+impl Default for NetworksConfig {
+    /// The endpoints `deploy.sh` hard-codes.
+    fn default() -> Self {
+        let testnet3 = NetworkProfile {
+            query: "https://vm.aleo.org/api".to_string(),
+            broadcast: "https://vm.aleo.org/api/testnet3/transaction/broadcast".to_string(),
+            fee: DEFAULT_FEE,
+        };
+        Self {
+            default: BUILTIN_NETWORK.to_string(),
+            networks: BTreeMap::from([(BUILTIN_NETWORK.to_string(), testnet3)]),
+        }
+    }
+}
+
+impl NetworksConfig {
+    pub fn parse(path: &Path, text: &str) -> Result<Self, NetworkError> {
+        toml::from_str(text).map_err(|source| NetworkError::Parse { path: path.to_path_buf(), source })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, NetworkError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|source| NetworkError::Io { path: path.to_path_buf(), source })?;
+        Self::parse(path, &text)
+    }
+
+    /// Loads `path` if it exists, falling back to the built-in profiles.
+    pub fn load_or_default(path: &Path) -> Result<Self, NetworkError> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Looks up a profile by name, or the default profile when `name` is `None`.
+    pub fn select(&self, name: Option<&str>) -> Result<(&str, &NetworkProfile), NetworkError> {
+        let name = name.unwrap_or(&self.default);
+        self.networks.get_key_value(name).map(|(name, profile)| (name.as_str(), profile)).ok_or_else(|| {
+            NetworkError::Unknown { name: name.to_string(), known: self.networks.keys().cloned().collect() }
+        })
+    }
+}
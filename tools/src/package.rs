@@ -0,0 +1,230 @@
+//! Loading a Leo package directory and locating the artifacts inside it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The manifest file at the root of a package and inside `build/`.
+pub const MANIFEST_FILE: &str = "program.json";
+
+/// The network suffix every program id carries.
+pub const PROGRAM_SUFFIX: &str = ".aleo";
+
+#[derive(Debug, Error)]
+pub enum PackageError {
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("`{}` is not a valid manifest: {source}", path.display())]
+    Manifest { path: PathBuf, source: serde_json::Error },
+    #[error("program id `{0}` in the manifest must end in `.aleo`")]
+    ProgramId(String),
+}
+
+/// The contents of `program.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub program: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub license: String,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, PackageError> {
+        let text = read(path)?;
+        serde_json::from_str(&text).map_err(|source| PackageError::Manifest { path: path.to_path_buf(), source })
+    }
+}
+
+/// A Leo package on disk, e.g. `token_dsfl348dfl93w1/`.
+#[derive(Clone, Debug)]
+pub struct Package {
+    root: PathBuf,
+    manifest: Manifest,
+}
+
+impl Package {
+    /// Opens the package rooted at `root` by reading its `program.json`.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, PackageError> {
+        let root = root.into();
+        let manifest = Manifest::load(&root.join(MANIFEST_FILE))?;
+        if !manifest.program.ends_with(PROGRAM_SUFFIX) {
+            return Err(PackageError::ProgramId(manifest.program));
+        }
+        Ok(Self { root, manifest })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// The full program id, e.g. `token_dsfl348dfl93w1.aleo`.
+    pub fn program_id(&self) -> &str {
+        &self.manifest.program
+    }
+
+    /// The program id without its `.aleo` suffix.
+    pub fn name(&self) -> &str {
+        self.program_id().trim_end_matches(PROGRAM_SUFFIX)
+    }
+
+    pub fn source_path(&self) -> PathBuf {
+        self.root.join("src").join("main.leo")
+    }
+
+    pub fn build_dir(&self) -> PathBuf {
+        self.root.join("build")
+    }
+
+    pub fn build_program_path(&self) -> PathBuf {
+        self.build_dir().join("main.aleo")
+    }
+
+    pub fn build_manifest_path(&self) -> PathBuf {
+        self.build_dir().join(MANIFEST_FILE)
+    }
+
+    /// The build manifest recording what `build/` was compiled from; see
+    /// [`crate::build`].
+    pub fn checksums_path(&self) -> PathBuf {
+        self.build_dir().join(crate::build::CHECKSUMS_FILE)
+    }
+
+    pub fn inputs_dir(&self) -> PathBuf {
+        self.root.join("inputs")
+    }
+
+    /// The input file `leo run` reads for this package, `inputs/<name>.in`.
+    pub fn input_path(&self) -> PathBuf {
+        self.inputs_dir().join(format!("{}.in", self.name()))
+    }
+
+    pub fn deploy_script_path(&self) -> PathBuf {
+        self.root.join("deploy.sh")
+    }
+
+    /// Every `*.in` file under `inputs/`, sorted by name.
+    pub fn input_files(&self) -> Result<Vec<PathBuf>, PackageError> {
+        let dir = self.inputs_dir();
+        let entries = fs::read_dir(&dir).map_err(|source| PackageError::Io { path: dir.clone(), source })?;
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|source| PackageError::Io { path: dir.clone(), source })?.path();
+            if path.extension().is_some_and(|ext| ext == "in") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Reads a file to a string, attaching the path to any error.
+pub fn read(path: &Path) -> Result<String, PackageError> {
+    fs::read_to_string(path).map_err(|source| PackageError::Io { path: path.to_path_buf(), source })
+}
+
+/// The longest identifier Aleo accepts; identifiers are packed into a field.
+pub const MAX_NAME_LENGTH: usize = 31;
+
+/// Words Leo or Aleo instructions reserve, which cannot name a program.
+const RESERVED_NAMES: &[&str] = &[
+    "address",
+    "aleo",
+    "as",
+    "block",
+    "bool",
+    "boolean",
+    "closure",
+    "const",
+    "constant",
+    "else",
+    "false",
+    "field",
+    "finalize",
+    "for",
+    "function",
+    "group",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "if",
+    "import",
+    "in",
+    "input",
+    "into",
+    "let",
+    "mapping",
+    "network",
+    "output",
+    "private",
+    "program",
+    "public",
+    "record",
+    "return",
+    "scalar",
+    "self",
+    "signature",
+    "string",
+    "struct",
+    "transition",
+    "true",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NameError {
+    #[error("program name is empty")]
+    Empty,
+    #[error("program name `{0}` is longer than {MAX_NAME_LENGTH} characters")]
+    TooLong(String),
+    #[error("program name `{0}` must start with a lowercase letter")]
+    Start(String),
+    #[error("program name `{name}` contains `{character}`; only lowercase letters, digits and `_` are allowed")]
+    Character { name: String, character: char },
+    #[error("program name `{0}` is a reserved word")]
+    Reserved(String),
+}
+
+/// Checks a program name, given without its `.aleo` suffix, against the
+/// Aleo identifier rules.
+pub fn validate_program_name(name: &str) -> Result<(), NameError> {
+    let first = name.chars().next().ok_or(NameError::Empty)?;
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(NameError::TooLong(name.to_string()));
+    }
+    if !first.is_ascii_lowercase() {
+        return Err(NameError::Start(name.to_string()));
+    }
+    if let Some(character) = name.chars().find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '_')) {
+        return Err(NameError::Character { name: name.to_string(), character });
+    }
+    if RESERVED_NAMES.contains(&name) {
+        return Err(NameError::Reserved(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Returns the value assigned to `APPNAME` in a deploy script and the 1-based
+/// line it is on, with surrounding quotes removed.
+pub fn deploy_script_appname(script: &str) -> Option<(&str, usize)> {
+    script.lines().enumerate().find_map(|(index, line)| {
+        let value = line.trim().strip_prefix("APPNAME=")?.trim();
+        let value = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')).unwrap_or(value);
+        Some((value, index + 1))
+    })
+}
@@ -0,0 +1,281 @@
+//! Renaming a package everywhere its program name appears.
+//!
+//! The FAQ lists six places that must agree: the package directory, the
+//! input file name, the `program` header of `src/main.leo` and of
+//! `build/main.aleo`, `APPNAME` in `deploy.sh`, and `program` in
+//! `program.json`. [`plan`] computes every change up front and fails before
+//! touching the disk if any artifact is not in the expected shape;
+//! [`RenamePlan::apply`] then performs the changes and undoes the ones already
+//! made if a later one fails. A build manifest that is current before the
+//! rename is updated to stay current after it.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::build::{check, BuildError, BuildManifest};
+use crate::package::{validate_program_name, NameError, Package, PackageError, MANIFEST_FILE, PROGRAM_SUFFIX};
+
+#[derive(Debug, Error)]
+pub enum RenameError {
+    #[error(transparent)]
+    Name(#[from] NameError),
+    #[error(transparent)]
+    Package(#[from] PackageError),
+    #[error(transparent)]
+    Build(#[from] BuildError),
+    #[error("the package is already named `{0}`")]
+    Unchanged(String),
+    #[error("`{}` does not contain {expected}", path.display())]
+    Pattern { path: PathBuf, expected: String },
+    #[error("`{}` already exists", path.display())]
+    Exists { path: PathBuf },
+    #[error("failed to {action} `{}`: {source}; all changes were rolled back", path.display())]
+    Apply { action: &'static str, path: PathBuf, source: io::Error },
+    /// Rolling back failed for every path in `failures`; the other steps
+    /// were still undone.
+    #[error(
+        "failed to {action} `{}`: {source}; rolling back also failed for {}",
+        path.display(),
+        describe_failures(failures)
+    )]
+    Rollback { action: &'static str, path: PathBuf, source: io::Error, failures: Vec<(PathBuf, io::Error)> },
+}
+
+fn describe_failures(failures: &[(PathBuf, io::Error)]) -> String {
+    failures.iter().map(|(path, error)| format!("`{}`: {error}", path.display())).collect::<Vec<_>>().join(", ")
+}
+
+/// A rewrite of one file's contents.
+#[derive(Clone, Debug)]
+pub struct Edit {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+/// Every change needed to rename a package, computed without side effects.
+#[derive(Clone, Debug)]
+pub struct RenamePlan {
+    pub old_name: String,
+    pub new_name: String,
+    /// The package directory as it is before the rename.
+    pub root: PathBuf,
+    pub edits: Vec<Edit>,
+    /// `inputs/<old>.in` to `inputs/<new>.in`, if the input file exists.
+    pub input_move: Option<(PathBuf, PathBuf)>,
+    /// The package directory move, if the directory is named after the package.
+    pub root_move: Option<(PathBuf, PathBuf)>,
+}
+
+/// Normalizes the requested name, accepting it with or without `.aleo` so
+/// that `APPNAME` can never end up as `name.aleo.aleo`.
+pub fn normalize_name(name: &str) -> Result<&str, NameError> {
+    let name = name.strip_suffix(PROGRAM_SUFFIX).unwrap_or(name);
+    validate_program_name(name)?;
+    Ok(name)
+}
+
+/// Computes the changes that rename `package` to `new_name`.
+pub fn plan(package: &Package, new_name: &str) -> Result<RenamePlan, RenameError> {
+    let new_name = normalize_name(new_name)?;
+    let old_name = package.name();
+    if old_name == new_name {
+        return Err(RenameError::Unchanged(new_name.to_string()));
+    }
+    let old_id = format!("{old_name}{PROGRAM_SUFFIX}");
+    let new_id = format!("{new_name}{PROGRAM_SUFFIX}");
+    let mut edits = Vec::new();
+
+    for path in [package.root().join(MANIFEST_FILE), package.build_manifest_path()] {
+        if path.exists() {
+            let key = format!("\"program\": \"{old_id}\"");
+            edits.push(required_edit(&path, format!("`{key}`"), |text| {
+                text.contains(&key).then(|| text.replacen(&key, &format!("\"program\": \"{new_id}\""), 1))
+            })?);
+        }
+    }
+
+    let source = package.source_path();
+    let edit = required_edit(&source, format!("`program {old_id}`"), |text| {
+        let header = format!("program {old_id}");
+        text.contains(&header).then(|| {
+            text.replacen(&header, &format!("program {new_id}"), 1).replacen(
+                &format!("// The '{old_name}' program."),
+                &format!("// The '{new_name}' program."),
+                1,
+            )
+        })
+    })?;
+    edits.push(edit);
+
+    let build = package.build_program_path();
+    if build.exists() {
+        edits.push(required_edit(&build, format!("`program {old_id};`"), |text| {
+            let header = format!("program {old_id};");
+            text.contains(&header).then(|| text.replacen(&header, &format!("program {new_id};"), 1))
+        })?);
+    }
+
+    let script = package.deploy_script_path();
+    if script.exists() {
+        edits.push(required_edit(&script, "an `APPNAME=\"...\"` line".to_string(), |text| {
+            rewrite_appname(text, new_name)
+        })?);
+    }
+
+    let readme = package.root().join("README.md");
+    if let Some(edit) = optional_edit(&readme, |text| {
+        let title = format!("# {old_id}");
+        text.contains(&title).then(|| text.replacen(&title, &format!("# {new_id}"), 1))
+    })? {
+        edits.push(edit);
+    }
+
+    // A build manifest that matched before the rename is rewritten to match
+    // after it; a stale one is left stale.
+    let checksums = package.checksums_path();
+    if matches!(check(package), Ok(stale) if stale.is_empty()) {
+        let manifest = BuildManifest::record_with(package, |path| match edits.iter().find(|edit| edit.path == path) {
+            Some(edit) => Ok(edit.updated.clone().into_bytes()),
+            None => fs::read(path).map_err(|source| BuildError::Io { path: path.to_path_buf(), source }),
+        })?;
+        let original = crate::package::read(&checksums)?;
+        edits.push(Edit { path: checksums, original, updated: manifest.to_json() });
+    }
+
+    let old_input = package.input_path();
+    let input_move = if old_input.exists() {
+        let new_input = package.inputs_dir().join(format!("{new_name}.in"));
+        ensure_free(&new_input)?;
+        Some((old_input, new_input))
+    } else {
+        None
+    };
+
+    // Resolve `.` and friends so a package renamed from inside its own
+    // directory still gets its directory renamed.
+    let root =
+        package.root().canonicalize().map_err(|source| PackageError::Io { path: package.root().into(), source })?;
+    let root_move = match root.file_name() {
+        Some(dir) if dir == old_name => {
+            let new_root = root.with_file_name(new_name);
+            ensure_free(&new_root)?;
+            Some((root.clone(), new_root))
+        }
+        _ => None,
+    };
+
+    Ok(RenamePlan {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        root,
+        edits,
+        input_move,
+        root_move,
+    })
+}
+
+/// Replaces the value of the `APPNAME=` assignment with `name`.
+fn rewrite_appname(text: &str, name: &str) -> Option<String> {
+    let mut found = false;
+    let lines: Vec<String> = text
+        .split_inclusive('\n')
+        .map(|line| {
+            if !found && line.trim_start().starts_with("APPNAME=") {
+                found = true;
+                let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+                let indent = &line[..line.len() - line.trim_start().len()];
+                format!("{indent}APPNAME=\"{name}\"{ending}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    found.then(|| lines.concat())
+}
+
+fn pattern(path: &Path, expected: String) -> RenameError {
+    RenameError::Pattern { path: path.to_path_buf(), expected }
+}
+
+fn ensure_free(path: &Path) -> Result<(), RenameError> {
+    if path.exists() {
+        return Err(RenameError::Exists { path: path.to_path_buf() });
+    }
+    Ok(())
+}
+
+fn optional_edit(path: &Path, rewrite: impl Fn(&str) -> Option<String>) -> Result<Option<Edit>, RenameError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let original = crate::package::read(path)?;
+    Ok(rewrite(&original).map(|updated| Edit { path: path.to_path_buf(), original, updated }))
+}
+
+fn required_edit(path: &Path, expected: String, rewrite: impl Fn(&str) -> Option<String>) -> Result<Edit, RenameError> {
+    let original = crate::package::read(path)?;
+    let updated = rewrite(&original).ok_or_else(|| pattern(path, expected))?;
+    Ok(Edit { path: path.to_path_buf(), original, updated })
+}
+
+/// A completed step, kept so it can be undone.
+enum Done {
+    Wrote(PathBuf, String),
+    Moved(PathBuf, PathBuf),
+}
+
+impl RenamePlan {
+    /// Applies the plan and returns the package's new root. If any step
+    /// fails, the steps already taken are reverted in reverse order; a step
+    /// that cannot be reverted does not stop the others from being tried.
+    pub fn apply(self) -> Result<PathBuf, RenameError> {
+        let mut done = Vec::new();
+        let result = self.apply_steps(&mut done);
+        if let Err((action, path, source)) = result {
+            let mut failures = Vec::new();
+            for step in done.into_iter().rev() {
+                let (rollback_path, rollback) = match step {
+                    Done::Wrote(path, original) => (path.clone(), write_atomic(&path, &original)),
+                    Done::Moved(from, to) => (from.clone(), fs::rename(&to, &from)),
+                };
+                if let Err(rollback) = rollback {
+                    failures.push((rollback_path, rollback));
+                }
+            }
+            if !failures.is_empty() {
+                return Err(RenameError::Rollback { action, path, source, failures });
+            }
+            return Err(RenameError::Apply { action, path, source });
+        }
+        Ok(self.root_move.map_or(self.root, |(_, to)| to))
+    }
+
+    fn apply_steps(&self, done: &mut Vec<Done>) -> Result<(), (&'static str, PathBuf, io::Error)> {
+        for edit in &self.edits {
+            write_atomic(&edit.path, &edit.updated).map_err(|error| ("write", edit.path.clone(), error))?;
+            done.push(Done::Wrote(edit.path.clone(), edit.original.clone()));
+        }
+        // Moves go last: the edits above address files by their old paths.
+        for (from, to) in self.input_move.iter().chain(&self.root_move) {
+            fs::rename(from, to).map_err(|error| ("move", from.clone(), error))?;
+            done.push(Done::Moved(from.clone(), to.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Replaces a file's contents via a temporary sibling, so a failed write
+/// never leaves it truncated.
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".rename-tmp");
+    let temporary = PathBuf::from(temporary);
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
@@ -0,0 +1,96 @@
+//! Working with the records an account holds.
+//!
+//! A `transfer` spends one `Token` record, so sending more than the largest
+//! record holds means calling `join` first. [`select`] picks the records to
+//! join: as few as possible, taking the largest first and then the smallest
+//! record that covers the rest. That keeps the change small, but not always
+//! as small as another set of equally many records would.
+
+use thiserror::Error;
+
+use crate::interpreter::{Literal, Plaintext, Record};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SelectError {
+    #[error("the records hold {available} in total, less than the {requested} requested")]
+    Insufficient { available: u128, requested: u128 },
+    #[error("record {index} has no integer `{member}`")]
+    NoAmount { index: usize, member: String },
+    #[error("the records covering {requested} hold more than a u128 can, so joining them would overflow")]
+    Overflow { requested: u128 },
+}
+
+/// The records chosen to cover an amount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection {
+    /// Indices into the records, largest first, in the order to join them.
+    pub indices: Vec<usize>,
+    /// What the chosen records hold together.
+    pub total: u128,
+}
+
+impl Selection {
+    /// How many `join` calls merge the chosen records into one.
+    pub fn joins(&self) -> usize {
+        self.indices.len().saturating_sub(1)
+    }
+
+    /// What is left in the joined record after spending `amount`.
+    pub fn change(&self, amount: u128) -> u128 {
+        self.total - amount
+    }
+}
+
+/// The amount a record holds in its integer `member`, e.g. `balance`.
+pub fn record_amount(record: &Record, member: &str) -> Option<u128> {
+    match record.member(member)? {
+        Plaintext::Literal(Literal::Integer(integer)) => Some(integer.unsigned()),
+        _ => None,
+    }
+}
+
+/// Picks which of `records` to join so that their `member` covers `amount`.
+///
+/// A single record is chosen whenever one is enough, the smallest such.
+/// Otherwise the largest records are taken until they cover the amount,
+/// which needs the fewest joins, and the last of them is swapped for the
+/// smallest record that still covers it. Fails with [`SelectError::Overflow`]
+/// if even that record brings the total past `u128::MAX`.
+pub fn select(records: &[Record], member: &str, amount: u128) -> Result<Selection, SelectError> {
+    let amounts = records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            record_amount(record, member).ok_or_else(|| SelectError::NoAmount { index, member: member.to_string() })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // A sum past `u128::MAX` covers any amount.
+    let available = amounts.iter().try_fold(0u128, |sum, &amount| sum.checked_add(amount));
+    if let Some(available) = available.filter(|&available| records.is_empty() || available < amount) {
+        return Err(SelectError::Insufficient { available, requested: amount });
+    }
+
+    let mut order: Vec<usize> = (0..records.len()).collect();
+    order.sort_by(|&a, &b| amounts[b].cmp(&amounts[a]).then(a.cmp(&b)));
+
+    // Take the largest records while they fall short, so `before` stays below
+    // `amount`; the last pick can be any remaining record that covers the rest.
+    let covers = |before: u128, index: usize| before.checked_add(amounts[index]).is_none_or(|total| total >= amount);
+    let mut before = 0;
+    let mut count = 0;
+    while !covers(before, order[count]) {
+        before += amounts[order[count]];
+        count += 1;
+    }
+    let last = order[count..]
+        .iter()
+        .copied()
+        .filter(|&index| covers(before, index))
+        .min_by(|&a, &b| amounts[a].cmp(&amounts[b]).then(a.cmp(&b)))
+        .expect("the largest remaining record covers the rest");
+    let total = before.checked_add(amounts[last]).ok_or(SelectError::Overflow { requested: amount })?;
+
+    let mut indices = order[..count].to_vec();
+    indices.push(last);
+    Ok(Selection { total, indices })
+}
@@ -0,0 +1,146 @@
+//! Exporting a compiled program's ABI and checking it against the schema.
+
+mod common;
+
+use serde_json::Value;
+use workshop::abi::{Abi, ValueAbi, ABI_VERSION, SCHEMA};
+use workshop::aleo;
+
+fn value(ty: &str, visibility: &str) -> ValueAbi {
+    ValueAbi { ty: ty.to_string(), visibility: visibility.to_string() }
+}
+
+/// Checks `json` against the parts of JSON Schema that `SCHEMA` uses, other
+/// than `pattern`, and returns the path of the first mismatch.
+fn validate(schema: &Value, root: &Value, json: &Value, path: &str) -> Result<(), String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.strip_prefix("#/$defs/").expect("local references");
+        validate(&root["$defs"][name], root, json, path)?;
+    }
+    if let Some(ty) = schema.get("type").and_then(Value::as_str) {
+        let matches = match ty {
+            "object" => json.is_object(),
+            "array" => json.is_array(),
+            "string" => json.is_string(),
+            "null" => json.is_null(),
+            other => panic!("unhandled type `{other}`"),
+        };
+        if !matches {
+            return Err(format!("{path}: expected {ty}, found {json}"));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if json != expected {
+            return Err(format!("{path}: expected {expected}, found {json}"));
+        }
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(json) {
+            return Err(format!("{path}: {json} is not one of {options:?}"));
+        }
+    }
+    if let Some(options) = schema.get("oneOf").and_then(Value::as_array) {
+        let valid = options.iter().filter(|option| validate(option, root, json, path).is_ok()).count();
+        if valid != 1 {
+            return Err(format!("{path}: {json} matches {valid} of the `oneOf` options"));
+        }
+    }
+    if let Some(object) = json.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for required in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+            if !object.contains_key(required.as_str().unwrap()) {
+                return Err(format!("{path}: missing {required}"));
+            }
+        }
+        for (key, member) in object {
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(property) => validate(property, root, member, &format!("{path}.{key}"))?,
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    return Err(format!("{path}: unexpected `{key}`"));
+                }
+                None => {}
+            }
+        }
+    }
+    if let (Some(items), Some(array)) = (schema.get("items"), json.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate(items, root, item, &format!("{path}[{index}]"))?;
+        }
+    }
+    Ok(())
+}
+
+fn check_schema(json: &Value) -> Result<(), String> {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    validate(&schema, &schema, json, "$")
+}
+
+#[test]
+fn function_signatures_come_from_the_build() {
+    let abi = Abi::new(&common::program());
+    assert_eq!(abi.version, ABI_VERSION);
+    assert_eq!(abi.program, "token_dsfl348dfl93w1.aleo");
+
+    let transfer = abi.function("transfer").unwrap();
+    assert_eq!(transfer.inputs, [value("address", "private"), value("u128", "private"), value("Token", "record")]);
+    assert_eq!(transfer.outputs, [value("Token", "record"), value("Token", "record")]);
+    assert_eq!(transfer.finalize, None);
+
+    let transfer_public = abi.function("transfer_public").unwrap();
+    assert_eq!(transfer_public.inputs, [value("address", "public"), value("u128", "public")]);
+    assert_eq!(transfer_public.outputs, []);
+    let finalize = transfer_public.finalize.as_ref().unwrap();
+    assert_eq!(finalize.inputs, [value("address", "public"), value("address", "public"), value("u128", "public")]);
+
+    let names: Vec<_> = abi.functions.iter().map(|function| function.name.as_str()).collect();
+    let expected: Vec<_> = common::program().functions().map(|function| function.name.name.clone()).collect();
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn records_structs_and_mappings_keep_their_types() {
+    let abi = Abi::new(&common::program());
+
+    let token = abi.records.iter().find(|record| record.name == "Token").unwrap();
+    let members: Vec<_> = token
+        .members
+        .iter()
+        .map(|member| (member.name.as_str(), member.ty.as_str(), member.visibility.as_deref()))
+        .collect();
+    assert_eq!(members, [("owner", "address", Some("private")), ("balance", "u128", Some("private"))]);
+
+    let approval = abi.structs.iter().find(|struct_| struct_.name == "Approval").unwrap();
+    assert!(approval.members.iter().all(|member| member.ty == "address" && member.visibility.is_none()));
+
+    let account = abi.mappings.iter().find(|mapping| mapping.name == "account").unwrap();
+    assert_eq!((account.key.as_str(), account.value.as_str()), ("address", "u128"));
+}
+
+#[test]
+fn the_export_validates_and_round_trips() {
+    let abi = Abi::new(&common::program());
+    let json = abi.to_json();
+    let parsed: Value = serde_json::from_str(&json).unwrap();
+    check_schema(&parsed).unwrap();
+    assert_eq!(serde_json::from_str::<Abi>(&json).unwrap(), abi);
+
+    let mut broken = parsed.clone();
+    broken["functions"][0]["inputs"] = serde_json::json!([{ "type": "u8", "visibility": "secret" }]);
+    assert!(check_schema(&broken).unwrap_err().contains("secret"));
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    assert_eq!(schema["properties"]["version"]["const"], ABI_VERSION);
+    let mut broken = parsed;
+    broken["version"] = (ABI_VERSION + 1).into();
+    assert!(check_schema(&broken).is_err());
+}
+
+#[test]
+fn external_records_and_imports_are_named_by_program() {
+    let source = "import credits.aleo;\n\nprogram wrapper.aleo;\n\n\n\
+        function pay:\n    input r0 as credits.aleo/credits.record;\n    input r1 as u64.constant;\n    output r0 as credits.aleo/credits.record;\n";
+    let abi = Abi::new(&aleo::parse(source).unwrap());
+    assert_eq!(abi.imports, ["credits.aleo"]);
+    let pay = abi.function("pay").unwrap();
+    assert_eq!(pay.inputs, [value("credits.aleo/credits", "record"), value("u64", "constant")]);
+    check_schema(&serde_json::to_value(&abi).unwrap()).unwrap();
+}
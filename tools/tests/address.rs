@@ -0,0 +1,80 @@
+//! Parsing and encoding bech32m account addresses.
+
+mod common;
+
+use common::{ALICE, BOB, CAROL};
+use workshop::address::{encode_bech32m, Address, AddressError, ADDRESS_HRP, ADDRESS_LENGTH};
+use workshop::interpreter::ZERO_ADDRESS;
+
+/// `address` with the character at `index` replaced by `character`.
+fn replaced(address: &str, index: usize, character: char) -> String {
+    let mut characters: Vec<char> = address.chars().collect();
+    characters[index] = character;
+    characters.into_iter().collect()
+}
+
+#[test]
+fn valid_addresses_parse() {
+    for text in [ALICE, BOB, CAROL, ZERO_ADDRESS] {
+        let address = Address::parse(text).unwrap_or_else(|error| panic!("{text}: {error}"));
+        assert_eq!(address.as_str(), text);
+        assert_eq!(text.parse::<Address>().unwrap(), address);
+    }
+    assert_eq!(Address::parse(ZERO_ADDRESS).unwrap().to_bytes(), [0; 32]);
+}
+
+#[test]
+fn malformed_addresses_are_rejected() {
+    assert_eq!(Address::parse(&ALICE.replacen("aleo1", "alea1", 1)), Err(AddressError::Prefix));
+    assert_eq!(Address::parse(&ALICE[1..]), Err(AddressError::Prefix));
+    assert_eq!(Address::parse(&ALICE.replacen("aleo1", "aleo2", 1)), Err(AddressError::Prefix));
+    assert_eq!(Address::parse("aleo1"), Err(AddressError::Length(5)));
+    assert_eq!(Address::parse(&ALICE[..ADDRESS_LENGTH - 1]), Err(AddressError::Length(ADDRESS_LENGTH - 1)));
+    assert_eq!(Address::parse(&format!("{ALICE}q")), Err(AddressError::Length(ADDRESS_LENGTH + 1)));
+
+    // `b`, `i`, `o` and `1` are not in the bech32 character set, and neither are capitals.
+    let error = Address::parse(&replaced(ALICE, 10, 'b')).unwrap_err();
+    assert_eq!(error, AddressError::Character { index: 10, character: 'b' });
+    assert_eq!(error.index(), Some(10));
+    assert!(error.to_string().contains("at character 11"), "{error}");
+    assert_eq!(
+        Address::parse(&ALICE.to_uppercase().replacen("ALEO1", "aleo1", 1)),
+        Err(AddressError::Character { index: 5, character: 'R' })
+    );
+}
+
+#[test]
+fn a_single_typo_is_located_by_the_checksum() {
+    let original = ALICE.chars().nth(20).unwrap();
+    let typo = if original == 'q' { 'p' } else { 'q' };
+    let error = Address::parse(&replaced(ALICE, 20, typo)).unwrap_err();
+    assert_eq!(error, AddressError::Typo { index: 20, expected: original });
+    assert_eq!(error.index(), Some(20));
+    assert!(error.to_string().contains(&format!("character 21 is probably a typo for `{original}`")), "{error}");
+
+    let last = ADDRESS_LENGTH - 1;
+    let original = ALICE.chars().nth(last).unwrap();
+    let typo = if original == 'q' { 'p' } else { 'q' };
+    assert_eq!(
+        Address::parse(&replaced(ALICE, last, typo)),
+        Err(AddressError::Typo { index: last, expected: original })
+    );
+
+    // Two typos cannot be pinned to a single character.
+    let two = replaced(&replaced(ALICE, 10, 'q'), 30, 'q');
+    assert_eq!(Address::parse(&two), Err(AddressError::Checksum));
+    assert_eq!(AddressError::Checksum.index(), None);
+}
+
+#[test]
+fn encoding_round_trips_through_parsing() {
+    for text in [ALICE, BOB, CAROL, ZERO_ADDRESS] {
+        let bytes = Address::parse(text).unwrap().to_bytes();
+        assert_eq!(encode_bech32m(ADDRESS_HRP, &bytes), text);
+    }
+    let bytes: Vec<u8> = (0..32).collect();
+    let encoded = encode_bech32m(ADDRESS_HRP, &bytes);
+    assert_eq!(encoded.len(), ADDRESS_LENGTH);
+    assert_eq!(Address::parse(&encoded).unwrap().to_bytes().to_vec(), bytes);
+    assert!(encode_bech32m("ar", &bytes).starts_with("ar1"));
+}
@@ -0,0 +1,115 @@
+//! Minting is limited to the admin address, counted in the `supply` mapping
+//! and capped at `MAX_SUPPLY`.
+
+mod common;
+
+use common::{minted, program, public_balance, record_balance, run, ADMIN, BOB, MAX_SUPPLY};
+use workshop::interpreter::{InterpretError, Interpreter};
+
+fn u128_literal(amount: u128) -> String {
+    format!("{amount}u128")
+}
+
+#[test]
+fn admin_mints_are_counted_in_supply() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    assert_eq!(minted(&interpreter), None);
+
+    let outputs = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap();
+    assert_eq!(record_balance(&outputs[0]), 100);
+    assert_eq!(minted(&interpreter), Some(100));
+
+    run(&mut interpreter, ADMIN, "mint_public", &[BOB, "50u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, BOB), Some(50));
+    assert_eq!(minted(&interpreter), Some(150));
+}
+
+#[test]
+fn unauthorized_mints_halt_without_minting() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, BOB);
+
+    let error = run(&mut interpreter, BOB, "mint", &["100u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    let error = run(&mut interpreter, BOB, "mint_public", &[BOB, "100u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+
+    assert_eq!(minted(&interpreter), None);
+    assert_eq!(public_balance(&interpreter, BOB), None);
+}
+
+#[test]
+fn transfers_and_conversions_leave_supply_alone() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint_public", &[ADMIN, "100u128"]).unwrap();
+
+    run(&mut interpreter, ADMIN, "transfer_public", &[BOB, "40u128"]).unwrap();
+    let token = run(&mut interpreter, BOB, "transfer_public_to_private", &[BOB, "40u128"]).unwrap().remove(0);
+    interpreter.set_caller(BOB);
+    let mut inputs = vec![token];
+    inputs.extend(common::inputs(&[ADMIN, "10u128"]));
+    interpreter.execute("transfer_private_to_public", inputs).unwrap();
+
+    assert_eq!(minted(&interpreter), Some(100));
+}
+
+#[test]
+fn mints_up_to_the_cap_succeed() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint", &[&u128_literal(MAX_SUPPLY - 1)]).unwrap();
+    run(&mut interpreter, ADMIN, "mint_public", &[BOB, "1u128"]).unwrap();
+    assert_eq!(minted(&interpreter), Some(MAX_SUPPLY));
+}
+
+#[test]
+fn mints_past_the_cap_halt_and_roll_back() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint", &[&u128_literal(MAX_SUPPLY - 10)]).unwrap();
+
+    let error = run(&mut interpreter, ADMIN, "mint", &["11u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    let error = run(&mut interpreter, ADMIN, "mint_public", &[BOB, "11u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+
+    assert_eq!(minted(&interpreter), Some(MAX_SUPPLY - 10));
+    assert_eq!(public_balance(&interpreter, BOB), None);
+}
+
+#[test]
+fn a_mint_overflowing_u128_halts_before_the_cap_check() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint", &["1000u128"]).unwrap();
+
+    let error = run(&mut interpreter, ADMIN, "mint", &[&u128_literal(u128::MAX)]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert_eq!(minted(&interpreter), Some(1000));
+}
+
+#[test]
+fn the_admin_is_declared_once_and_gates_every_admin_transition() {
+    let source = include_str!("../../token_dsfl348dfl93w1/src/main.leo");
+    assert_eq!(source.matches(ADMIN).count(), 1);
+    assert!(source.contains(&format!("const ADMIN: address = {ADMIN};")));
+    assert_eq!(source.matches("assert_eq(self.caller, ADMIN);").count(), 7);
+
+    let program = program();
+    for (function, inputs) in [
+        ("mint", vec!["1u128"]),
+        ("mint_public", vec![BOB, "1u128"]),
+        ("pause", vec![]),
+        ("unpause", vec![]),
+        ("freeze", vec![BOB]),
+        ("unfreeze", vec![BOB]),
+        ("vest_mint", vec![BOB, "1u128", "10u32"]),
+    ] {
+        let mut interpreter = Interpreter::new(&program, ADMIN);
+        run(&mut interpreter, ADMIN, function, &inputs).unwrap_or_else(|error| panic!("{function}: {error}"));
+        let error = run(&mut interpreter, BOB, function, &inputs).unwrap_err();
+        assert!(matches!(error, InterpretError::Halt { .. }), "{function}: {error}");
+    }
+}
@@ -0,0 +1,101 @@
+//! Parsing compiled Aleo instructions and printing them back.
+
+use workshop::aleo::{self, Operation};
+use workshop::diagnostic::{Position, Span};
+
+const BUILD: &str = include_str!("../../token_dsfl348dfl93w1/build/main.aleo");
+
+/// A program with one function, `mint`, whose finalize block runs `finalize`.
+fn with_finalize(finalize: &str) -> String {
+    format!(
+        "program token.aleo;\n\nmapping account:\n    key as address.public;\n    value as u64.public;\n\n\n\
+         function mint:\n    input r0 as u64.public;\n    finalize r0;\n\nfinalize mint:\n    input r0 as u64.public;\n{finalize}"
+    )
+}
+
+/// A program with one function, `run`, made of `body`.
+fn with_function(body: &str) -> String {
+    format!("program token.aleo;\n\nfunction run:\n    input r0 as u64.private;\n{body}")
+}
+
+fn parse_error(source: &str) -> (Span, String) {
+    match aleo::parse(source) {
+        Ok(program) => panic!("parsed:\n{program}"),
+        Err(error) => (error.span, error.message),
+    }
+}
+
+#[test]
+fn the_token_build_round_trips_byte_for_byte() {
+    let program = aleo::parse(BUILD).unwrap();
+    assert_eq!(program.to_string(), BUILD);
+    assert_eq!(program.id.to_string(), "token_dsfl348dfl93w1.aleo");
+    assert_eq!(aleo::parse(&program.to_string()).unwrap(), program);
+}
+
+#[test]
+fn every_declaration_kind_round_trips() {
+    let source =
+        "import credits.aleo;\n\nprogram kinds.aleo;\n\nstruct Pair:\n    left as u8;\n    right as field;\n\n\n\
+        record Coin:\n    owner as address.private;\n    amount as u64.public;\n\n\n\
+        mapping seen:\n    key as field.public;\n    value as boolean.public;\n\n\n\
+        closure double:\n    input r0 as u64;\n    add r0 r0 into r1;\n    output r1 as u64;\n\n\n\
+        function spend:\n    input r0 as Coin.record;\n    input r1 as credits.aleo/credits.record;\n    \
+        call double r0.amount into r2;\n    hash.bhp256 r0.owner into r3 as field;\n    \
+        cast r0.owner r2 into r4 as Coin.record;\n    output r4 as Coin.record;\n    finalize r3;\n\n\
+        finalize spend:\n    input r0 as field.public;\n    rand.chacha into r1 as u8;\n    \
+        get.or_use seen[r0] false into r2;\n    set true into seen[r0];\n";
+    let program = aleo::parse(source).unwrap();
+    assert_eq!(program.to_string(), source);
+    let spend = program.function("spend").unwrap();
+    assert!(matches!(spend.instructions[1].operation, Operation::Hash { ty: Some(_), .. }));
+}
+
+#[test]
+fn a_missing_type_after_as_is_an_error_not_a_panic() {
+    let (span, message) = parse_error(&with_finalize("    rand.chacha into r1;\n"));
+    assert_eq!(message, "`rand.chacha` needs a literal type: `as <type>`");
+    assert_eq!(span.start, Position::new(14, 5));
+    let (_, message) = parse_error(&with_finalize("    rand.chacha into r1 as;\n"));
+    assert_eq!(message, "`rand.chacha` needs a literal type: `as <type>`");
+
+    let source = "program token.aleo;\n\nmapping account:\n    key as;\n    value as u64.public;\n";
+    let (span, message) = parse_error(source);
+    assert_eq!(message, "expected a type after `as`");
+    assert_eq!(span, Span::new(Position::new(4, 5), Position::new(4, 12)));
+
+    let (span, message) = parse_error(&with_function("    hash.bhp256 r0 into r1 as;\n"));
+    assert_eq!(message, "expected a type after `as`");
+    assert_eq!(span.start, Position::new(5, 5));
+
+    let (_, message) = parse_error(&with_function("    cast r0 into r1 as;\n"));
+    assert_eq!(message, "`cast` needs a destination type: `as <type>`");
+}
+
+#[test]
+fn malformed_statements_are_spanned_errors() {
+    let (span, message) = parse_error(&with_function("    add r0 r0 into r1 as u64;\n"));
+    assert_eq!(message, "`add` does not take an `as` type");
+    assert_eq!(span.start, Position::new(5, 26));
+
+    let (span, message) = parse_error(&with_function("    frobnicate r0 into r1;\n"));
+    assert_eq!(message, "unknown opcode `frobnicate`");
+    assert_eq!(span.start, Position::new(5, 5));
+
+    let (_, message) = parse_error(&with_function("    add r0 into r1;\n"));
+    assert_eq!(message, "`add` takes 2 operand(s), found 1");
+
+    let (_, message) = parse_error("program token.aleo;\n\nfunction run:\n    input r0 as u64.secret;\n");
+    assert_eq!(message, "unknown visibility `secret`");
+
+    let (_, message) = parse_error("program token.aleo;\n\nfunction run:\n    input r0 as u64.private\n");
+    assert_eq!(message, "statement is missing its terminating `;`");
+
+    let source = "program token.aleo;\n\nmapping account:\n    key as address.public;\n";
+    let (_, message) = parse_error(source);
+    assert_eq!(message, "a mapping declares exactly one `key` line followed by one `value` line");
+
+    let source = with_finalize("").replace("finalize mint:", "finalize burn:");
+    let (_, message) = parse_error(&source);
+    assert_eq!(message, "finalize block must be named `mint`");
+}
@@ -0,0 +1,123 @@
+//! `approve_public`, `unapprove_public` and `transfer_from_public`, checking
+//! the `allowance` mapping alongside the public balances.
+
+mod common;
+
+use common::{program, public_balance, run, ADMIN, ALICE, BOB, CAROL};
+use workshop::aleo;
+use workshop::interpreter::{InterpretError, Interpreter, Literal, Plaintext, Value};
+
+/// Hashes an `Approval` the way the token's finalize blocks do, giving the
+/// `allowance` key for a pair.
+fn allowance_key(approver: &str, spender: &str) -> Plaintext {
+    let program = aleo::parse(
+        "program key.aleo;\n\nstruct Approval:\n    approver as address;\n    spender as address;\n\n\nfunction key:\n    input r0 as address.private;\n    input r1 as address.private;\n    cast r0 r1 into r2 as Approval;\n    hash.bhp256 r2 into r3 as field;\n    output r3 as field.private;\n",
+    )
+    .unwrap();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    match run(&mut interpreter, ADMIN, "key", &[approver, spender]).unwrap().remove(0) {
+        Value::Plaintext(key) => key,
+        other => panic!("expected a field, found {other}"),
+    }
+}
+
+/// What `spender` may still transfer out of `approver`'s public balance.
+fn allowance(interpreter: &Interpreter<'_>, approver: &str, spender: &str) -> Option<u128> {
+    match interpreter.mapping_value("allowance", &allowance_key(approver, spender))? {
+        Plaintext::Literal(Literal::Integer(integer)) => Some(integer.unsigned()),
+        other => panic!("`allowance` holds a non-integer value {other}"),
+    }
+}
+
+/// An interpreter where ALICE holds 100 publicly.
+fn funded(program: &aleo::Program) -> Interpreter<'_> {
+    let mut interpreter = Interpreter::new(program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint_public", &[ALICE, "100u128"]).unwrap();
+    interpreter
+}
+
+#[test]
+fn approve_public_adds_to_the_allowance() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    assert_eq!(allowance(&interpreter, ALICE, BOB), None);
+
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "30u128"]).unwrap();
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "20u128"]).unwrap();
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(50));
+    assert_eq!(allowance(&interpreter, BOB, ALICE), None);
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+}
+
+#[test]
+fn transfer_from_public_spends_the_allowance() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "50u128"]).unwrap();
+
+    run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, CAROL, "30u128"]).unwrap();
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(20));
+    assert_eq!(public_balance(&interpreter, ALICE), Some(70));
+    assert_eq!(public_balance(&interpreter, BOB), None);
+    assert_eq!(public_balance(&interpreter, CAROL), Some(30));
+
+    run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, BOB, "20u128"]).unwrap();
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(0));
+    assert_eq!(public_balance(&interpreter, BOB), Some(20));
+}
+
+#[test]
+fn transfer_from_public_past_the_allowance_halts_and_rolls_back() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "50u128"]).unwrap();
+
+    let error = run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, BOB, "51u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(50));
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+    assert_eq!(public_balance(&interpreter, BOB), None);
+}
+
+#[test]
+fn transfer_from_public_past_the_balance_halts_and_rolls_back() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "500u128"]).unwrap();
+
+    let error = run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, BOB, "101u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(500));
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+}
+
+#[test]
+fn allowances_belong_to_one_approver_and_spender() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    run(&mut interpreter, ADMIN, "mint_public", &[CAROL, "100u128"]).unwrap();
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "50u128"]).unwrap();
+
+    let error = run(&mut interpreter, CAROL, "transfer_from_public", &[ALICE, CAROL, "1u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    let error = run(&mut interpreter, BOB, "transfer_from_public", &[CAROL, BOB, "1u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+    assert_eq!(public_balance(&interpreter, CAROL), Some(100));
+}
+
+#[test]
+fn unapprove_public_takes_back_the_allowance() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "50u128"]).unwrap();
+
+    run(&mut interpreter, ALICE, "unapprove_public", &[BOB, "40u128"]).unwrap();
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(10));
+    let error = run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, BOB, "11u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+
+    let error = run(&mut interpreter, ALICE, "unapprove_public", &[BOB, "11u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert_eq!(allowance(&interpreter, ALICE, BOB), Some(10));
+}
@@ -0,0 +1,59 @@
+//! The token's metadata and converting between human amounts and base units.
+
+mod common;
+
+use common::program;
+use workshop::amount::{format_amount, pack_ascii, parse_amount, unpack_ascii, AmountError, TokenMetadata};
+
+#[test]
+fn the_program_exposes_its_metadata() {
+    let metadata = TokenMetadata::from_program(&program()).unwrap();
+    assert_eq!(metadata, TokenMetadata { name: "Workshop Token".to_string(), symbol: "WST".to_string(), decimals: 6 });
+    assert_eq!(metadata.format(12_500_000), "12.5 WST");
+    assert_eq!(metadata.parse("12.5 WST"), Ok(12_500_000));
+    assert_eq!(metadata.parse("12.5"), Ok(12_500_000));
+    assert!(matches!(metadata.parse("12.5 ETH"), Err(AmountError::Symbol { .. })));
+}
+
+#[test]
+fn amounts_format_without_trailing_zeros() {
+    assert_eq!(format_amount(0, 6), "0");
+    assert_eq!(format_amount(1, 6), "0.000001");
+    assert_eq!(format_amount(1_000_000, 6), "1");
+    assert_eq!(format_amount(1_230_000, 6), "1.23");
+    assert_eq!(format_amount(42, 0), "42");
+    assert_eq!(format_amount(u128::MAX, 18), "340282366920938463463.374607431768211455");
+}
+
+#[test]
+fn amounts_parse_into_base_units() {
+    assert_eq!(parse_amount("12", 6), Ok(12_000_000));
+    assert_eq!(parse_amount("0.000001", 6), Ok(1));
+    assert_eq!(parse_amount("1_000.25", 6), Ok(1_000_250_000));
+    assert_eq!(parse_amount("7.", 2), Ok(700));
+    assert_eq!(parse_amount("340282366920938463463.374607431768211455", 18), Ok(u128::MAX));
+}
+
+#[test]
+fn invalid_amounts_are_rejected() {
+    assert_eq!(parse_amount("", 6), Err(AmountError::Empty));
+    assert_eq!(parse_amount("1.2.3", 6), Err(AmountError::Character { index: 3, character: '.' }));
+    assert_eq!(parse_amount("-1", 6), Err(AmountError::Character { index: 0, character: '-' }));
+    assert_eq!(
+        parse_amount("0.0000001", 6),
+        Err(AmountError::Precision { text: "0.0000001".to_string(), found: 7, decimals: 6 })
+    );
+    assert_eq!(
+        parse_amount("340282366920938463464", 18),
+        Err(AmountError::Overflow("340282366920938463464".to_string()))
+    );
+}
+
+#[test]
+fn names_round_trip_through_packed_ascii() {
+    assert_eq!(pack_ascii("WST"), Some(5_722_964));
+    assert_eq!(unpack_ascii(5_722_964).as_deref(), Some("WST"));
+    assert_eq!(pack_ascii("seventeen chars!!"), None);
+    let name = "Workshop Token";
+    assert_eq!(unpack_ascii(pack_ascii(name).unwrap()).as_deref(), Some(name));
+}
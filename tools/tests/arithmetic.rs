@@ -0,0 +1,94 @@
+//! Running the package's input file sections the way `check-arithmetic` does.
+
+mod common;
+
+use common::{program, run, ADMIN, BOB};
+use workshop::aleo;
+use workshop::arithmetic::{admin, caller_for, explain, lint, section_inputs};
+use workshop::inputs;
+use workshop::interpreter::{InterpretError, Interpreter};
+
+const BUILD: &str = include_str!("../../token_dsfl348dfl93w1/build/main.aleo");
+
+/// The trimmed source line of every instruction `lint` flags in `source`.
+fn flagged(source: &str) -> Vec<&str> {
+    let lines: Vec<&str> = source.lines().collect();
+    lint(&aleo::parse(source).unwrap()).iter().map(|diagnostic| lines[diagnostic.span.start.line - 1].trim()).collect()
+}
+
+#[test]
+fn the_admin_is_read_from_the_access_checks() {
+    assert_eq!(admin(&program()).as_deref(), Some(ADMIN));
+
+    let gated = |name: &str, caller: &str| format!("function {name}:\n    assert.eq self.caller {caller};\n");
+    let one = format!("program gated.aleo;\n\n{}", gated("a", ADMIN));
+    assert_eq!(admin(&aleo::parse(&one).unwrap()).as_deref(), Some(ADMIN));
+    let two = format!("{one}\n\n{}", gated("b", BOB));
+    assert_eq!(admin(&aleo::parse(&two).unwrap()), None);
+    assert_eq!(admin(&aleo::parse("program open.aleo;\n\nfunction a:\n    input r0 as u8.private;\n").unwrap()), None);
+}
+
+#[test]
+fn every_input_section_runs_as_the_admin_by_default() {
+    let program = program();
+    let admin = admin(&program).unwrap();
+    let (file, _) = inputs::parse(include_str!("../../token_dsfl348dfl93w1/inputs/token_dsfl348dfl93w1.in"));
+    let mut interpreter = Interpreter::new(&program, &admin);
+    for section in &file.sections {
+        let values = section_inputs(section).unwrap();
+        interpreter.set_caller(caller_for(&values, &admin));
+        interpreter
+            .execute(&section.name.name, values)
+            .unwrap_or_else(|error| panic!("[{}]: {error}", section.name.name));
+    }
+}
+
+#[test]
+fn unguarded_arithmetic_is_flagged() {
+    let flagged = flagged(BUILD);
+    assert!(flagged.contains(&"sub r2.balance r1 into r3;"), "{flagged:?}");
+    // `transfer_from_public` asserts `lte r3 r9` before spending the allowance.
+    assert!(flagged.contains(&"sub r12 r3 into r13;"), "{flagged:?}");
+    assert!(!flagged.contains(&"sub r9 r3 into r11;"), "{flagged:?}");
+
+    let diagnostic =
+        lint(&program()).into_iter().find(|diagnostic| diagnostic.message.starts_with("`sub r2.balance")).unwrap();
+    assert_eq!(diagnostic.message, "`sub r2.balance r1` halts if r1 is greater than r2.balance");
+    assert!(diagnostic.help.unwrap().starts_with("assert `r2.balance >= r1` first"));
+}
+
+#[test]
+fn arithmetic_after_a_matching_guard_is_not_flagged() {
+    let function = |body: &str| {
+        format!("program guarded.aleo;\n\nfunction run:\n    input r0 as u64.private;\n    input r1 as u64.private;\n{body}")
+    };
+    let guarded = function(
+        "    gte r0 r1 into r2;\n    assert.eq r2 true;\n    sub r0 r1 into r3;\n    \
+         lt r0 r1 into r4;\n    assert.eq r4 false;\n    sub r0 r1 into r5;\n    \
+         assert.neq r1 0u64;\n    div r0 r1 into r6;\n",
+    );
+    assert_eq!(flagged(&guarded), Vec::<&str>::new());
+
+    // A guard on other operands, or one that is never asserted, does not count.
+    let unguarded = function(
+        "    gte r1 r0 into r2;\n    assert.eq r2 true;\n    sub r0 r1 into r3;\n    \
+         gte r0 r1 into r4;\n    sub r0 r1 into r5;\n    add r0 r1 into r6;\n",
+    );
+    assert_eq!(flagged(&unguarded), ["sub r0 r1 into r3;", "sub r0 r1 into r5;", "add r0 r1 into r6;"]);
+}
+
+#[test]
+fn an_underflowing_transfer_is_explained_as_an_insufficient_balance() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let record = format!("{{ owner: {ADMIN}.private, balance: 100u128.private, _nonce: 0group.public }}");
+    let error = run(&mut interpreter, ADMIN, "transfer", &[BOB, "250u128", &record]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+
+    let diagnostic = explain(&program, &error);
+    assert_eq!(diagnostic.message, "insufficient balance: record holds 100, requested 250");
+    assert_eq!(
+        diagnostic.help.as_deref(),
+        Some("`sub r2.balance r1 into r3` would underflow; spend a Token record holding at least 250, or request at most 100")
+    );
+}
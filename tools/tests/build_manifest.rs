@@ -0,0 +1,138 @@
+//! Detecting a `build/` that no longer matches the package sources.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use common::{package_copy, TempDir, PACKAGE};
+use workshop::build::{check, leo_build, BuildError, BuildManifest, Stale};
+use workshop::package::Package;
+use workshop::rename::plan;
+
+/// A directory holding a `leo` script that runs `script` in place of `leo build`.
+#[cfg(unix)]
+fn fake_leo(name: &str, script: &str) -> TempDir {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new(name);
+    let leo = dir.join("leo");
+    fs::write(&leo, format!("#!/bin/sh\n[ \"$1\" = build ] || exit 2\n{script}\n")).unwrap();
+    fs::set_permissions(&leo, fs::Permissions::from_mode(0o755)).unwrap();
+    dir
+}
+
+fn append(path: &Path, text: &str) {
+    let mut contents = fs::read_to_string(path).unwrap();
+    contents.push_str(text);
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn the_committed_build_is_current() {
+    let package = Package::open(PACKAGE).unwrap();
+    assert_eq!(check(&package).unwrap(), []);
+
+    let manifest = BuildManifest::load(&package.checksums_path()).unwrap();
+    let sources: Vec<_> = manifest.sources.keys().map(String::as_str).collect();
+    assert_eq!(sources, ["program.json", "src/main.leo"]);
+    let outputs: Vec<_> = manifest.outputs.keys().map(String::as_str).collect();
+    assert_eq!(outputs, ["build/main.aleo", "build/program.json"]);
+    assert_eq!(manifest, BuildManifest::record(&package).unwrap());
+}
+
+#[test]
+fn changed_sources_are_named_until_the_build_is_recorded() {
+    let copy = package_copy("build-source");
+    let package = Package::open(&copy).unwrap();
+    append(&package.source_path(), "\n// A change after the last build.\n");
+    assert_eq!(check(&package).unwrap(), [Stale::SourceChanged("src/main.leo".to_string())]);
+
+    BuildManifest::record(&package).unwrap().save(&package.checksums_path()).unwrap();
+    assert_eq!(check(&package).unwrap(), []);
+
+    fs::write(package.root().join("src/helpers.leo"), "").unwrap();
+    assert_eq!(check(&package).unwrap(), [Stale::SourceAdded("src/helpers.leo".to_string())]);
+
+    BuildManifest::record(&package).unwrap().save(&package.checksums_path()).unwrap();
+    assert_eq!(check(&package).unwrap(), []);
+    fs::remove_file(package.root().join("src/helpers.leo")).unwrap();
+    assert_eq!(check(&package).unwrap(), [Stale::SourceRemoved("src/helpers.leo".to_string())]);
+}
+
+#[test]
+fn outputs_edited_or_removed_after_the_build_are_stale() {
+    let copy = package_copy("build-output");
+    let package = Package::open(&copy).unwrap();
+    append(&package.build_program_path(), "\n");
+    fs::remove_file(package.build_manifest_path()).unwrap();
+    assert_eq!(
+        check(&package).unwrap(),
+        [Stale::OutputChanged("build/main.aleo".to_string()), Stale::OutputMissing("build/program.json".to_string()),]
+    );
+}
+
+#[test]
+fn a_missing_or_unknown_manifest_is_not_trusted() {
+    let copy = package_copy("build-unrecorded");
+    let package = Package::open(&copy).unwrap();
+    fs::remove_file(package.checksums_path()).unwrap();
+    assert_eq!(check(&package).unwrap(), [Stale::Unrecorded]);
+
+    fs::write(package.checksums_path(), r#"{"version": 2, "sources": {}, "outputs": {}}"#).unwrap();
+    assert!(matches!(check(&package), Err(BuildError::Version { found: 2, .. })));
+}
+
+#[test]
+fn renaming_keeps_a_current_build_current() {
+    let copy = package_copy("build-rename");
+    let package = Package::open(&copy).unwrap();
+    let root = plan(&package, "token_renamed_build").unwrap().apply().unwrap();
+    let package = Package::open(root).unwrap();
+    assert_eq!(package.program_id(), "token_renamed_build.aleo");
+    assert_eq!(check(&package).unwrap(), []);
+}
+
+#[cfg(unix)]
+#[test]
+fn building_then_recording_makes_a_changed_source_current() {
+    let copy = package_copy("build-leo");
+    let package = Package::open(&copy).unwrap();
+    append(&package.source_path(), "\n// A change after the last build.\n");
+    let leo = fake_leo("build-leo-bin", "echo '// rebuilt' >> build/main.aleo");
+
+    let status = leo_build(&package).env("PATH", &*leo).status().unwrap();
+    assert!(status.success());
+    assert!(fs::read_to_string(package.build_program_path()).unwrap().ends_with("// rebuilt\n"));
+    assert_eq!(check(&package).unwrap().len(), 2);
+    BuildManifest::record(&package).unwrap().save(&package.checksums_path()).unwrap();
+    assert_eq!(check(&package).unwrap(), []);
+}
+
+#[cfg(unix)]
+#[test]
+fn the_build_flag_records_only_after_a_successful_build() {
+    let copy = package_copy("build-flag");
+    let package = Package::open(&copy).unwrap();
+    append(&package.source_path(), "\n// A change after the last build.\n");
+    let recorded = fs::read_to_string(package.checksums_path()).unwrap();
+    let build_manifest = |leo: &Path| {
+        Command::new(env!("CARGO_BIN_EXE_build-manifest"))
+            .args([package.root().as_os_str(), "--build".as_ref()])
+            .env("PATH", leo)
+            .output()
+            .unwrap()
+    };
+
+    let failing = fake_leo("build-flag-failing", "echo 'Error: failed to compile' >&2; exit 1");
+    let output = build_manifest(&failing);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`leo build` failed"));
+    assert_eq!(fs::read_to_string(package.checksums_path()).unwrap(), recorded);
+    assert_eq!(check(&package).unwrap(), [Stale::SourceChanged("src/main.leo".to_string())]);
+
+    let output = build_manifest(&fake_leo("build-flag-passing", "echo '// rebuilt' >> build/main.aleo"));
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(check(&package).unwrap(), []);
+}
@@ -0,0 +1,94 @@
+//! `burn` and `burn_public` destroy tokens and take them out of the
+//! `supply` mapping.
+
+mod common;
+
+use common::{minted, program, public_balance, record_balance, run, ADMIN, BOB};
+use workshop::interpreter::{InterpretError, Interpreter, Value};
+
+/// Runs `burn` as `caller`, spending `token`.
+fn burn(interpreter: &mut Interpreter<'_>, caller: &str, token: Value, amount: &str) -> Result<Value, InterpretError> {
+    interpreter.set_caller(caller);
+    let mut inputs = vec![token];
+    inputs.extend(common::inputs(&[amount]));
+    Ok(interpreter.execute("burn", inputs)?.remove(0))
+}
+
+#[test]
+fn burn_returns_change_and_reduces_supply() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let token = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap().remove(0);
+    assert_eq!(minted(&interpreter), Some(100));
+
+    let change = burn(&mut interpreter, ADMIN, token, "30u128").unwrap();
+    assert_eq!(record_balance(&change), 70);
+    assert_eq!(minted(&interpreter), Some(70));
+
+    let change = burn(&mut interpreter, ADMIN, change, "70u128").unwrap();
+    assert_eq!(record_balance(&change), 0);
+    assert_eq!(minted(&interpreter), Some(0));
+}
+
+#[test]
+fn any_holder_can_burn_their_own_tokens() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint_public", &[ADMIN, "50u128"]).unwrap();
+    let token = run(&mut interpreter, ADMIN, "transfer_public_to_private", &[BOB, "50u128"]).unwrap().remove(0);
+    assert_eq!(minted(&interpreter), Some(50));
+
+    let change = burn(&mut interpreter, BOB, token, "20u128").unwrap();
+    let Value::Record(change) = &change else { unreachable!() };
+    assert_eq!(change.owner(), Some(BOB));
+    assert_eq!(minted(&interpreter), Some(30));
+}
+
+#[test]
+fn burning_more_than_the_record_holds_halts() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let token = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap().remove(0);
+
+    let error = burn(&mut interpreter, ADMIN, token, "101u128").unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert_eq!(minted(&interpreter), Some(100));
+}
+
+#[test]
+fn burning_someone_elses_record_is_rejected() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let token = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap().remove(0);
+
+    let error = burn(&mut interpreter, BOB, token, "10u128").unwrap_err();
+    assert!(matches!(error, InterpretError::Input { .. }), "{error}");
+    assert_eq!(minted(&interpreter), Some(100));
+}
+
+#[test]
+fn burn_public_debits_the_caller_and_reduces_supply() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint_public", &[BOB, "100u128"]).unwrap();
+    run(&mut interpreter, ADMIN, "mint_public", &[ADMIN, "10u128"]).unwrap();
+    assert_eq!(minted(&interpreter), Some(110));
+
+    run(&mut interpreter, BOB, "burn_public", &["40u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, BOB), Some(60));
+    assert_eq!(public_balance(&interpreter, ADMIN), Some(10));
+    assert_eq!(minted(&interpreter), Some(70));
+}
+
+#[test]
+fn burn_public_beyond_the_balance_halts_and_rolls_back() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint_public", &[BOB, "100u128"]).unwrap();
+    run(&mut interpreter, ADMIN, "mint_public", &[ADMIN, "100u128"]).unwrap();
+
+    let error = run(&mut interpreter, BOB, "burn_public", &["101u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert_eq!(public_balance(&interpreter, BOB), Some(100));
+    assert_eq!(minted(&interpreter), Some(200));
+}
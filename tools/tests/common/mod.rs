@@ -0,0 +1,164 @@
+//! Shared setup for the tests that run the workshop token program.
+
+#![allow(dead_code)]
+
+use std::ffi::OsStr;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use workshop::aleo::{self, LiteralType, Program};
+use workshop::interpreter::{Integer, InterpretError, Interpreter, Literal, Plaintext, Value};
+
+pub const ALICE: &str = "aleo1rgg7jdpcka5wxggltt8vs6c5t6dw7q60r79xrn2qz9jpqefexuzsc2lkzs";
+pub const BOB: &str = "aleo1yn6halw6astkc8jsl88sukelef3e8xrawugfjtx7kjcuuxdm6spsdtc249";
+pub const CAROL: &str = "aleo102nryeeun6da4atqggu0q9aj5cqem7tpjzvce4nc88yzu29n8sgs9qelp7";
+
+/// The only address allowed to mint.
+pub const ADMIN: &str = ALICE;
+
+/// The cap `mint` and `mint_public` enforce on the `supply` mapping: a
+/// billion tokens of six decimals.
+pub const MAX_SUPPLY: u128 = 1_000_000_000_000_000;
+
+/// The compiled token program, as committed in the package's `build/`.
+pub fn program() -> Program {
+    aleo::parse(include_str!("../../../token_dsfl348dfl93w1/build/main.aleo")).expect("the build parses")
+}
+
+/// The token package directory.
+pub const PACKAGE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../token_dsfl348dfl93w1");
+
+/// A fresh temporary directory for one test, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("workshop-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<OsStr> for TempDir {
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_os_str()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A copy of the token package for one test to modify, in a directory named
+/// after the package inside a [`TempDir`]. Dereferences to the package root.
+pub struct PackageCopy {
+    root: PathBuf,
+    _dir: TempDir,
+}
+
+impl Deref for PackageCopy {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl AsRef<OsStr> for PackageCopy {
+    fn as_ref(&self) -> &OsStr {
+        self.root.as_os_str()
+    }
+}
+
+pub fn package_copy(name: &str) -> PackageCopy {
+    fn copy(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let target = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy(&entry.path(), &target);
+            } else {
+                std::fs::copy(entry.path(), target).unwrap();
+            }
+        }
+    }
+    let dir = TempDir::new(name);
+    let root = dir.join("token_dsfl348dfl93w1");
+    copy(Path::new(PACKAGE), &root);
+    PackageCopy { root, _dir: dir }
+}
+
+/// Parses each input the way `interpret` does on the command line.
+pub fn inputs(texts: &[&str]) -> Vec<Value> {
+    texts.iter().map(|text| Value::parse(text).expect("test inputs parse")).collect()
+}
+
+/// Runs `function` as `caller`.
+pub fn run(
+    interpreter: &mut Interpreter<'_>,
+    caller: &str,
+    function: &str,
+    texts: &[&str],
+) -> Result<Vec<Value>, InterpretError> {
+    interpreter.set_caller(caller);
+    interpreter.execute(function, inputs(texts))
+}
+
+fn address(address: &str) -> Plaintext {
+    Plaintext::Literal(Literal::Address(address.to_string()))
+}
+
+/// The integer stored for `owner` in `mapping`, or `None` without an entry.
+pub fn mapping_integer(interpreter: &Interpreter<'_>, mapping: &str, owner: &str) -> Option<u128> {
+    match interpreter.mapping_value(mapping, &address(owner))? {
+        Plaintext::Literal(Literal::Integer(integer)) => Some(integer.unsigned()),
+        other => panic!("`{mapping}` holds a non-integer value {other}"),
+    }
+}
+
+/// The public balance of `owner` in the `account` mapping.
+pub fn public_balance(interpreter: &Interpreter<'_>, owner: &str) -> Option<u128> {
+    mapping_integer(interpreter, "account", owner)
+}
+
+/// The total minted so far, from the `supply` mapping.
+pub fn minted(interpreter: &Interpreter<'_>) -> Option<u128> {
+    let key = Plaintext::Literal(Literal::Integer(Integer::from_u128(LiteralType::U8, 0).unwrap()));
+    match interpreter.mapping_value("supply", &key)? {
+        Plaintext::Literal(Literal::Integer(integer)) => Some(integer.unsigned()),
+        other => panic!("`supply` holds a non-integer value {other}"),
+    }
+}
+
+/// The sum of every public balance in the `account` mapping.
+pub fn total_public(interpreter: &Interpreter<'_>) -> u128 {
+    interpreter
+        .mapping_entries("account")
+        .iter()
+        .map(|(_, value)| match value {
+            Plaintext::Literal(Literal::Integer(integer)) => integer.unsigned(),
+            other => panic!("`account` holds a non-integer value {other}"),
+        })
+        .sum()
+}
+
+/// The `balance` of a `Token` record output.
+pub fn record_balance(value: &Value) -> u128 {
+    let Value::Record(record) = value else { panic!("expected a record, found {value}") };
+    match record.member("balance") {
+        Some(Plaintext::Literal(Literal::Integer(integer))) => integer.unsigned(),
+        _ => panic!("the record has no integer balance: {record}"),
+    }
+}
@@ -0,0 +1,141 @@
+//! Moving value between private `Token` records and the public `account`
+//! mapping, checking that no path creates or destroys tokens.
+
+mod common;
+
+use common::{minted, program, public_balance, record_balance, run, total_public, ALICE, BOB};
+use workshop::aleo::{Program, ValueType};
+use workshop::interpreter::{InterpretError, Interpreter, Value};
+
+/// The records a test holds, and the interpreter holding the mappings.
+struct Ledger<'a> {
+    interpreter: Interpreter<'a>,
+    records: Vec<Value>,
+}
+
+impl<'a> Ledger<'a> {
+    fn new(program: &'a Program) -> Self {
+        Self { interpreter: Interpreter::new(program, ALICE), records: Vec::new() }
+    }
+
+    /// Private plus public tokens, checked against the `supply` mapping.
+    fn supply(&self) -> u128 {
+        let held = self.records.iter().map(record_balance).sum::<u128>() + total_public(&self.interpreter);
+        assert_eq!(Some(held), minted(&self.interpreter));
+        held
+    }
+
+    /// Runs `function` spending the record at `spend`, if any, as its record
+    /// input; on success the record is spent and the outputs are kept.
+    fn run(
+        &mut self,
+        caller: &str,
+        function: &str,
+        spend: Option<usize>,
+        texts: &[&str],
+    ) -> Result<Vec<Value>, InterpretError> {
+        let mut inputs = common::inputs(texts);
+        if let Some(index) = spend {
+            let declaration = self.interpreter.program().function(function).unwrap();
+            let at = declaration.inputs.iter().position(|input| matches!(input.ty, ValueType::Record(_))).unwrap();
+            inputs.insert(at, self.records[index].clone());
+        }
+        self.interpreter.set_caller(caller);
+        let outputs = self.interpreter.execute(function, inputs)?;
+        if let Some(index) = spend {
+            self.records.remove(index);
+        }
+        self.records.extend(outputs.iter().cloned());
+        Ok(outputs)
+    }
+}
+
+#[test]
+fn private_to_public_returns_change_and_credits_the_mapping() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ALICE);
+    let token = run(&mut interpreter, ALICE, "mint", &["100u128"]).unwrap().remove(0);
+
+    interpreter.set_caller(ALICE);
+    let mut inputs = vec![token];
+    inputs.extend(common::inputs(&[BOB, "30u128"]));
+    let outputs = interpreter.execute("transfer_private_to_public", inputs).unwrap();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(record_balance(&outputs[0]), 70);
+    let Value::Record(change) = &outputs[0] else { unreachable!() };
+    assert_eq!(change.owner(), Some(ALICE));
+    assert_eq!(public_balance(&interpreter, BOB), Some(30));
+}
+
+#[test]
+fn public_to_private_debits_the_mapping_and_emits_a_record() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ALICE);
+    run(&mut interpreter, ALICE, "mint_public", &[ALICE, "100u128"]).unwrap();
+
+    let outputs = run(&mut interpreter, ALICE, "transfer_public_to_private", &[BOB, "60u128"]).unwrap();
+    assert_eq!(record_balance(&outputs[0]), 60);
+    let Value::Record(record) = &outputs[0] else { unreachable!() };
+    assert_eq!(record.owner(), Some(BOB));
+    assert_eq!(public_balance(&interpreter, ALICE), Some(40));
+}
+
+#[test]
+fn every_conversion_path_conserves_supply() {
+    let program = program();
+    let mut ledger = Ledger::new(&program);
+    ledger.run(ALICE, "mint", None, &["500u128"]).unwrap();
+    ledger.run(ALICE, "mint_public", None, &[BOB, "300u128"]).unwrap();
+    assert_eq!(ledger.supply(), 800);
+
+    // Alice's 500 record: 200 to Bob's public balance, 300 change.
+    ledger.run(ALICE, "transfer_private_to_public", Some(0), &[BOB, "200u128"]).unwrap();
+    assert_eq!(ledger.supply(), 800);
+    assert_eq!(public_balance(&ledger.interpreter, BOB), Some(500));
+
+    // Bob takes 450 of his public balance private, as a record for Alice.
+    ledger.run(BOB, "transfer_public_to_private", None, &[ALICE, "450u128"]).unwrap();
+    assert_eq!(ledger.supply(), 800);
+
+    // Private transfer of the 450 record, then public transfers.
+    let index = ledger.records.iter().position(|record| record_balance(record) == 450).unwrap();
+    ledger.run(ALICE, "transfer", Some(index), &[BOB, "50u128"]).unwrap();
+    assert_eq!(ledger.supply(), 800);
+    ledger.run(BOB, "transfer_public", None, &[ALICE, "50u128"]).unwrap();
+    assert_eq!(ledger.supply(), 800);
+
+    // Converting everything back to public and then to private again.
+    while let Some(index) = ledger.records.iter().position(|record| record_balance(record) > 0) {
+        let Value::Record(record) = &ledger.records[index] else { unreachable!() };
+        let owner = record.owner().unwrap().to_string();
+        let amount = format!("{}u128", record_balance(&ledger.records[index]));
+        ledger.run(&owner, "transfer_private_to_public", Some(index), &[&owner, &amount]).unwrap();
+        assert_eq!(ledger.supply(), 800);
+    }
+    assert_eq!(total_public(&ledger.interpreter), 800);
+    for owner in [ALICE, BOB] {
+        let amount = format!("{}u128", public_balance(&ledger.interpreter, owner).unwrap());
+        ledger.run(owner, "transfer_public_to_private", None, &[owner, &amount]).unwrap();
+        assert_eq!(ledger.supply(), 800);
+    }
+    assert_eq!(total_public(&ledger.interpreter), 0);
+}
+
+#[test]
+fn failed_conversions_change_nothing() {
+    let program = program();
+    let mut ledger = Ledger::new(&program);
+    ledger.run(ALICE, "mint", None, &["100u128"]).unwrap();
+    ledger.run(ALICE, "mint_public", None, &[ALICE, "100u128"]).unwrap();
+
+    let error = ledger.run(ALICE, "transfer_private_to_public", Some(0), &[BOB, "101u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    let error = ledger.run(ALICE, "transfer_public_to_private", None, &[BOB, "101u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    let error = ledger.run(BOB, "transfer_private_to_public", Some(0), &[BOB, "1u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Input { .. }), "{error}");
+
+    assert_eq!(ledger.records.len(), 1);
+    assert_eq!(ledger.supply(), 200);
+    assert_eq!(public_balance(&ledger.interpreter, BOB), None);
+}
@@ -0,0 +1,132 @@
+//! Loading the deploy key and assembling the `snarkos developer deploy` command.
+
+mod common;
+
+use std::path::Path;
+
+use common::{TempDir, PACKAGE};
+use workshop::deploy::{
+    load_private_key, parse_env_file, DeployError, Deployment, PrivateKey, ENV_FILE, PRIVATE_KEY_VAR,
+};
+use workshop::network::{NetworkError, NetworksConfig, BUILTIN_NETWORK, DEFAULT_FEE};
+use workshop::package::Package;
+
+/// A well-formed key made of `character` repeated after the prefix.
+fn key(character: char) -> String {
+    format!("APrivateKey1{}", character.to_string().repeat(47))
+}
+
+fn deployment(network: Option<&str>) -> Deployment {
+    let config = NetworksConfig::load(&Path::new(PACKAGE).join("networks.toml")).unwrap();
+    let (name, profile) = config.select(network).unwrap();
+    let private_key = PrivateKey::parse(&key('k'), "a test").unwrap();
+    Deployment::new(&Package::open(PACKAGE).unwrap(), name, profile.clone(), private_key)
+}
+
+fn arguments(deployment: &Deployment) -> Vec<String> {
+    deployment.command().get_args().map(|arg| arg.to_string_lossy().into_owned()).collect()
+}
+
+#[test]
+fn env_files_allow_comments_exports_and_quotes() {
+    let path = Path::new(ENV_FILE);
+    let text = "# the deploy key\n\nexport PRIVATE_KEY = \"APrivateKey1abc\"\nNAME='single quoted'\nEMPTY=\n\
+                MISMATCHED=\"half'\n";
+    let pairs = parse_env_file(path, text).unwrap();
+    let pairs: Vec<_> = pairs.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+    assert_eq!(
+        pairs,
+        [("PRIVATE_KEY", "APrivateKey1abc"), ("NAME", "single quoted"), ("EMPTY", ""), ("MISMATCHED", "\"half'")]
+    );
+
+    let error = parse_env_file(path, "# comment\nNAME=value\n\nnot a pair\n").unwrap_err();
+    assert!(matches!(error, DeployError::EnvSyntax { line: 4, .. }), "{error}");
+    assert_eq!(error.to_string(), "`.env` line 4: expected `NAME=value`");
+}
+
+// The only test that touches `PRIVATE_KEY`, so setting it cannot race
+// another test in this binary.
+#[test]
+fn the_environment_wins_over_the_env_file() {
+    let dir = TempDir::new("deploy-key");
+    let env_file = dir.join(ENV_FILE);
+    std::env::remove_var(PRIVATE_KEY_VAR);
+
+    let error = load_private_key(&env_file).unwrap_err();
+    assert!(matches!(error, DeployError::MissingKey { env_file: ref path } if *path == env_file), "{error}");
+    std::fs::write(&env_file, "OTHER=1\nPRIVATE_KEY=\n").unwrap();
+    assert!(matches!(load_private_key(&env_file), Err(DeployError::MissingKey { .. })));
+
+    std::fs::write(&env_file, format!("PRIVATE_KEY={}\nPRIVATE_KEY={}\n", key('a'), key('b'))).unwrap();
+    assert_eq!(load_private_key(&env_file).unwrap().expose(), key('b'));
+
+    std::env::set_var(PRIVATE_KEY_VAR, key('c'));
+    assert_eq!(load_private_key(&env_file).unwrap().expose(), key('c'));
+    std::env::set_var(PRIVATE_KEY_VAR, "APrivateKey1tooshort");
+    let error = load_private_key(&env_file).unwrap_err();
+    assert!(matches!(error, DeployError::MalformedKey { ref source_name } if source_name == "`$PRIVATE_KEY`"));
+    std::env::set_var(PRIVATE_KEY_VAR, " ");
+    assert_eq!(load_private_key(&env_file).unwrap().expose(), key('b'));
+    std::env::remove_var(PRIVATE_KEY_VAR);
+}
+
+#[test]
+fn profiles_are_selected_by_name_or_default() {
+    let path = Path::new("networks.toml");
+    let config = NetworksConfig::parse(
+        path,
+        "default = \"local\"\n\n[networks.local]\nquery = \"http://localhost:3030\"\n\
+         broadcast = \"http://localhost:3030/testnet3/transaction/broadcast\"\nfee = 5\n\n\
+         [networks.testnet3]\nquery = \"https://vm.aleo.org/api\"\n\
+         broadcast = \"https://vm.aleo.org/api/testnet3/transaction/broadcast\"\n",
+    )
+    .unwrap();
+
+    let (name, profile) = config.select(None).unwrap();
+    assert_eq!((name, profile.query.as_str(), profile.fee), ("local", "http://localhost:3030", 5));
+    let (name, profile) = config.select(Some("testnet3")).unwrap();
+    assert_eq!((name, profile.fee), ("testnet3", DEFAULT_FEE));
+
+    let error = config.select(Some("mainnet")).unwrap_err();
+    assert!(matches!(error, NetworkError::Unknown { ref name, .. } if name == "mainnet"), "{error}");
+    assert_eq!(error.to_string(), "no network named `mainnet`; known networks: local, testnet3");
+
+    assert_eq!(NetworksConfig::default().select(None).unwrap().0, BUILTIN_NETWORK);
+    assert!(matches!(NetworksConfig::parse(path, "default = 3\n"), Err(NetworkError::Parse { .. })));
+}
+
+#[test]
+fn dry_runs_and_fee_overrides_change_the_arguments() {
+    let mut deployment = deployment(Some("local"));
+    let broadcast = arguments(&deployment);
+    assert_eq!(
+        broadcast[..3],
+        ["developer".to_string(), "deploy".to_string(), "token_dsfl348dfl93w1.aleo".to_string()]
+    );
+    assert_eq!(
+        broadcast[broadcast.len() - 4..],
+        ["--fee", "1000000", "--broadcast", "http://localhost:3030/testnet3/transaction/broadcast"]
+    );
+
+    deployment.dry_run = true;
+    deployment.profile.fee = 2_500_000;
+    deployment.fee_record = Some("{ owner: aleo1.private }".to_string());
+    let dry_run = arguments(&deployment);
+    assert!(!dry_run.iter().any(|arg| arg == "--broadcast"), "{dry_run:?}");
+    assert_eq!(dry_run[dry_run.len() - 5..], ["--fee", "2500000", "--record", "{ owner: aleo1.private }", "--dry-run"]);
+    assert_eq!(dry_run[..broadcast.len() - 3], broadcast[..broadcast.len() - 3]);
+}
+
+#[test]
+fn the_displayed_command_redacts_the_key() {
+    let mut deployment = deployment(None);
+    assert_eq!(deployment.network, BUILTIN_NETWORK);
+    assert!(arguments(&deployment).contains(&key('k')));
+    deployment.fee_record = Some("{ owner: aleo1.private }".to_string());
+
+    let text = deployment.display_command();
+    assert!(!text.contains(deployment.private_key.expose()), "{text}");
+    assert!(text.starts_with("snarkos developer deploy token_dsfl348dfl93w1.aleo --private-key <redacted> "), "{text}");
+    assert!(text.contains(" --record \"{ owner: aleo1.private }\" "), "{text}");
+    assert_eq!(format!("{:?}", deployment.private_key), "PrivateKey(<redacted>)");
+}
@@ -0,0 +1,62 @@
+//! Checking that a package's artifacts agree on the program id.
+
+mod common;
+
+use std::fs;
+
+use common::package_copy;
+use workshop::doctor::{examine, Artifact, Status};
+
+#[test]
+fn a_copy_of_the_token_package_is_healthy() {
+    let report = examine(&package_copy("doctor-healthy"));
+    assert_eq!(report.expected.as_deref(), Some("token_dsfl348dfl93w1.aleo"));
+    assert!(report.is_healthy(), "{report}");
+    assert_eq!(report.findings.len(), 7);
+}
+
+#[test]
+fn a_changed_program_id_is_reported_as_a_mismatch() {
+    let root = package_copy("doctor-mismatch");
+    let build = root.join("build/main.aleo");
+    let source = fs::read_to_string(&build).unwrap();
+    fs::write(&build, source.replacen("program token_dsfl348dfl93w1.aleo;", "program token_other.aleo;", 1)).unwrap();
+
+    let report = examine(&root);
+    assert!(!report.is_healthy());
+    let problems: Vec<_> = report.problems().collect();
+    assert_eq!(problems.len(), 1, "{report}");
+    let finding = problems[0];
+    assert_eq!(finding.artifact, Artifact::BuildProgram);
+    assert_eq!(finding.found.as_deref(), Some("token_other.aleo"));
+    assert_eq!(finding.line, Some(1));
+    assert_eq!(finding.status, Status::Mismatch { fix: "rebuild with `leo build`".to_string() });
+
+    let table = report.to_string();
+    let row = table.lines().find(|line| line.starts_with("build/main.aleo")).unwrap();
+    assert!(row.contains("token_other.aleo") && row.contains("MISMATCH"), "{table}");
+    assert!(table.contains("  - build/main.aleo: rebuild with `leo build`"), "{table}");
+}
+
+#[test]
+fn a_suffixed_appname_and_a_missing_input_file_are_reported() {
+    let root = package_copy("doctor-script");
+    let script = root.join("deploy.sh");
+    let text = fs::read_to_string(&script).unwrap();
+    fs::write(&script, text.replacen("APPNAME=\"token_dsfl348dfl93w1\"", "APPNAME=\"token_dsfl348dfl93w1.aleo\"", 1))
+        .unwrap();
+    fs::remove_file(root.join("inputs/token_dsfl348dfl93w1.in")).unwrap();
+
+    let report = examine(&root);
+    let problems: Vec<_> = report.problems().map(|finding| (finding.artifact, finding.status.clone())).collect();
+    assert_eq!(
+        problems,
+        [
+            (Artifact::InputFile, Status::Missing { fix: "create `inputs/token_dsfl348dfl93w1.in`".to_string() }),
+            (
+                Artifact::DeployScript,
+                Status::Mismatch { fix: "set `APPNAME=\"token_dsfl348dfl93w1\"` (without `.aleo`)".to_string() }
+            ),
+        ]
+    );
+}
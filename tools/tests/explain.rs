@@ -0,0 +1,115 @@
+//! Recognizing failed `leo run` and `snarkos developer deploy` output and
+//! pointing at the fix in the token package.
+
+mod common;
+
+use std::path::Path;
+
+use common::{package_copy, PACKAGE};
+use workshop::diagnostic::Position;
+use workshop::explain::{locate, recognize, Failure};
+use workshop::package::Package;
+
+fn edit_input(dir: &Path, from: &str, to: &str) {
+    let path = dir.join("inputs/token_dsfl348dfl93w1.in");
+    let source = std::fs::read_to_string(&path).unwrap();
+    assert!(source.contains(from), "{from}");
+    std::fs::write(&path, source.replacen(from, to, 1)).unwrap();
+}
+
+#[test]
+fn the_catalog_recognizes_each_failure() {
+    let output = "\x1b[1;31mError\x1b[0m [ECLI0377032]: Function 'mint' in the program 'token_dsfl348dfl93w1.aleo' expects 1 inputs, but 0 inputs were found.\n\
+        Error [EPAR0370005]: expected ; -- found 'amount'\n    --> inputs/token_dsfl348dfl93w1.in:3:1\n\
+        Error: Fee record does not have enough balance to pay the fee\n\
+        Error: Program 'token_dsfl348dfl93w1.aleo' already exists\n\
+        Failed to evaluate instruction (sub r2.balance r1 into r3;): Integer underflow on subtraction of 100u128 and 1000u128\n\
+        Failed to evaluate instruction (sub r2.balance r1 into r3;): Integer underflow on subtraction of 100u128 and 1000u128\n";
+    let failures: Vec<_> = recognize(output).into_iter().map(|found| (found.line, found.failure)).collect();
+    assert_eq!(
+        failures,
+        [
+            (1, Failure::InputCount { function: "mint".to_string(), expected: 1, found: 0 }),
+            (
+                2,
+                Failure::Syntax {
+                    message: "expected ; -- found 'amount'".to_string(),
+                    file: "inputs/token_dsfl348dfl93w1.in".to_string(),
+                    position: Some(Position::new(3, 1)),
+                }
+            ),
+            (4, Failure::InsufficientFee),
+            (5, Failure::ProgramExists { program: "token_dsfl348dfl93w1.aleo".to_string() }),
+            (6, Failure::Arithmetic { instruction: "sub r2.balance r1 into r3".to_string(), underflow: true }),
+        ]
+    );
+    assert!(recognize("Leo ✅ Finished 'token_dsfl348dfl93w1.aleo/mint'\n").is_empty());
+}
+
+#[test]
+fn a_missing_semicolon_is_found_in_the_input_section() {
+    let dir = package_copy("explain-semicolon");
+    edit_input(&dir, "amount: u128 = 100u128;\n\n[transfer]", "amount: u128 = 100u128\n\n[transfer]");
+    let package = Package::open(&dir).unwrap();
+
+    let failure = Failure::InputCount { function: "mint".to_string(), expected: 1, found: 0 };
+    let locations = locate(&failure, &package);
+    assert_eq!(locations.len(), 1, "{locations:?}");
+    assert_eq!(locations[0].path, package.input_path());
+    assert!(locations[0].diagnostic.message.contains(';'), "{:?}", locations[0].diagnostic);
+    assert_eq!(locations[0].diagnostic.span.start.line, 3);
+}
+
+#[test]
+fn a_section_with_too_few_inputs_is_pointed_at() {
+    let dir = package_copy("explain-count");
+    edit_input(&dir, "amount: u128 = 100u128;\n\n[transfer]", "\n[transfer]");
+    let package = Package::open(&dir).unwrap();
+
+    let failure = Failure::InputCount { function: "mint".to_string(), expected: 1, found: 0 };
+    let locations = locate(&failure, &package);
+    assert_eq!(locations.len(), 1, "{locations:?}");
+    assert_eq!(locations[0].diagnostic.message, "`[mint]` has 0 input(s); the function takes 1");
+    assert_eq!(locations[0].diagnostic.span.start, Position::new(2, 2));
+
+    let failure = Failure::InputCount { function: "missing".to_string(), expected: 2, found: 0 };
+    let help = locate(&failure, &package)[0].diagnostic.help.clone().unwrap();
+    assert!(help.contains("`[missing]`"), "{help}");
+}
+
+#[test]
+fn fee_failures_point_at_the_name_and_the_fee() {
+    let package = Package::open(PACKAGE).unwrap();
+    let locations = locate(&Failure::InsufficientFee, &package);
+    let files: Vec<_> = locations.iter().map(|location| location.path.file_name().unwrap().to_owned()).collect();
+    assert_eq!(files, ["program.json", "deploy.sh", "networks.toml"]);
+    assert!(locations[0].diagnostic.help.as_ref().unwrap().contains("rename"));
+    // `deploy.sh` and the `testnet3` profile pay one credit, below the estimate.
+    assert_eq!(locations[1].diagnostic.message, "`deploy.sh` pays 1000000 microcredits");
+    assert!(locations[2].diagnostic.message.contains("`testnet3`"), "{:?}", locations[2].diagnostic);
+
+    let failure = Failure::ProgramExists { program: "token_dsfl348dfl93w1.aleo".to_string() };
+    let locations = locate(&failure, &package);
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].path, package.root().join("program.json"));
+}
+
+#[test]
+fn an_underflow_is_replayed_from_the_input_file() {
+    let failure = Failure::Arithmetic { instruction: "sub r2.balance r1 into r3".to_string(), underflow: true };
+
+    let package = Package::open(PACKAGE).unwrap();
+    let locations = locate(&failure, &package);
+    assert_eq!(locations.len(), 1, "{locations:?}");
+    assert_eq!(locations[0].path, package.build_program_path());
+    assert!(locations[0].diagnostic.message.contains("halts here"), "{:?}", locations[0].diagnostic);
+    let line = locations[0].diagnostic.span.start.line;
+
+    let dir = package_copy("explain-underflow");
+    edit_input(&dir, "transfer_amount: u128 = 10u128;", "transfer_amount: u128 = 1000u128;");
+    let package = Package::open(&dir).unwrap();
+    let locations = locate(&failure, &package);
+    assert_eq!(locations.len(), 1, "{locations:?}");
+    assert_eq!(locations[0].diagnostic.span.start.line, line);
+    assert!(locations[0].diagnostic.message.contains("1000"), "{:?}", locations[0].diagnostic);
+}
@@ -0,0 +1,66 @@
+//! Deployment fee estimates from compiled programs.
+
+mod common;
+
+use workshop::aleo;
+use workshop::fee::{
+    FeeEstimate, CONSTRAINTS_PER_FUNCTION, CONSTRAINTS_PER_INSTRUCTION, CONSTRAINTS_PER_RECORD, KEY_BYTES_PER_FUNCTION,
+    MICROCREDITS_PER_CREDIT, STORAGE_COST_PER_BYTE, SYNTHESIS_COST_PER_CONSTRAINT,
+};
+
+const SOURCE: &str = "program coins.aleo;\n\nrecord Coin:\n    owner as address.private;\n    amount as u64.private;\n\n\nfunction split:\n    input r0 as Coin.record;\n    input r1 as u64.private;\n    sub r0.amount r1 into r2;\n    cast self.caller r1 into r3 as Coin.record;\n    cast self.caller r2 into r4 as Coin.record;\n    output r3 as Coin.record;\n    output r4 as Coin.record;\n\n\nfunction double:\n    input r0 as u64.public;\n    add r0 r0 into r1;\n    output r1 as u64.public;\n";
+
+fn estimate(source: &str) -> FeeEstimate {
+    FeeEstimate::new(&aleo::parse(source).unwrap(), source)
+}
+
+#[test]
+fn the_estimate_counts_what_the_program_is_charged_for() {
+    let estimate = estimate(SOURCE);
+    assert_eq!(estimate.name, "coins");
+    assert_eq!(estimate.program_bytes, SOURCE.len() as u64);
+    assert_eq!(estimate.functions, 2);
+    assert_eq!(estimate.records, 3);
+    assert_eq!(estimate.instructions, 4);
+
+    assert_eq!(estimate.storage_bytes(), SOURCE.len() as u64 + 2 * KEY_BYTES_PER_FUNCTION);
+    assert_eq!(estimate.storage_cost(), estimate.storage_bytes() * STORAGE_COST_PER_BYTE);
+    let constraints = 2 * CONSTRAINTS_PER_FUNCTION + 3 * CONSTRAINTS_PER_RECORD + 4 * CONSTRAINTS_PER_INSTRUCTION;
+    assert_eq!(estimate.constraints(), constraints);
+    assert_eq!(estimate.synthesis_cost(), constraints * SYNTHESIS_COST_PER_CONSTRAINT);
+    assert_eq!(estimate.total(), estimate.storage_cost() + estimate.synthesis_cost() + estimate.namespace_cost());
+}
+
+#[test]
+fn short_names_pay_for_their_namespace() {
+    let cost = |name: &str| FeeEstimate { name: name.to_string(), ..estimate(SOURCE) }.namespace_cost();
+    assert_eq!(cost("token_dsfl348dfl93w1"), MICROCREDITS_PER_CREDIT);
+    assert_eq!(cost("tenletters"), MICROCREDITS_PER_CREDIT);
+    assert_eq!(cost("nineabcde"), 10 * MICROCREDITS_PER_CREDIT);
+    assert_eq!(cost("coins"), 100_000 * MICROCREDITS_PER_CREDIT);
+    assert_eq!(cost("a"), 1_000_000_000 * MICROCREDITS_PER_CREDIT);
+}
+
+#[test]
+fn the_suggested_fee_adds_the_margin_in_whole_credits() {
+    let estimate = FeeEstimate { name: "token_dsfl348dfl93w1".to_string(), ..estimate(SOURCE) };
+    let total = estimate.total();
+
+    let exact = estimate.suggested_fee(0);
+    assert!(exact >= total && exact - total < MICROCREDITS_PER_CREDIT, "{exact} for {total}");
+    assert_eq!(exact % MICROCREDITS_PER_CREDIT, 0);
+    let padded = estimate.suggested_fee(20);
+    assert!(padded >= total + total / 5, "{padded} for {total}");
+    assert_eq!(padded % MICROCREDITS_PER_CREDIT, 0);
+}
+
+#[test]
+fn the_token_costs_more_than_deploy_sh_pays() {
+    let source = include_str!("../../token_dsfl348dfl93w1/build/main.aleo");
+    let estimate = estimate(source);
+    let program = common::program();
+    assert_eq!(estimate.functions, program.functions().count() as u64);
+    assert_eq!(estimate.namespace_cost(), MICROCREDITS_PER_CREDIT);
+    // `deploy.sh` and the `testnet3` profile pay one credit.
+    assert!(estimate.total() > MICROCREDITS_PER_CREDIT);
+}
@@ -0,0 +1,109 @@
+//! Parsing input files and checking them against the token program.
+
+mod common;
+
+use workshop::diagnostic::{Diagnostic, Position};
+use workshop::inputs::{self, Value};
+use workshop::leo;
+
+const SOURCE: &str = include_str!("../../token_dsfl348dfl93w1/src/main.leo");
+const INPUT_FILE: &str = include_str!("../../token_dsfl348dfl93w1/inputs/token_dsfl348dfl93w1.in");
+const NONCE: &str = "705976341404673007283367164533130687910559696873293749990007850040286847435group";
+
+/// The parser's and the checker's errors for `source`, in that order.
+fn errors(source: &str) -> Vec<Diagnostic> {
+    let (file, mut diagnostics) = inputs::parse(source);
+    diagnostics.extend(inputs::check(&file, &common::program(), &leo::transitions(SOURCE)));
+    diagnostics.retain(Diagnostic::is_error);
+    diagnostics
+}
+
+fn messages(source: &str) -> Vec<String> {
+    errors(source).into_iter().map(|diagnostic| diagnostic.message).collect()
+}
+
+#[test]
+fn the_package_input_file_is_clean() {
+    let (file, diagnostics) = inputs::parse(INPUT_FILE);
+    assert_eq!(diagnostics, []);
+    assert_eq!(messages(INPUT_FILE), Vec::<String>::new());
+
+    let transfer = file.section("transfer").unwrap();
+    let names: Vec<_> = transfer.inputs.iter().map(|input| input.name.name.as_str()).collect();
+    assert_eq!(names, ["receiver", "transfer_amount", "input"]);
+    let Value::Struct(token) = &transfer.inputs[2].value else { panic!("the record input is a struct literal") };
+    assert_eq!(token.name.name, "Token");
+    assert_eq!(token.members.len(), 3);
+}
+
+#[test]
+fn a_missing_semicolon_is_reported_after_the_value() {
+    let errors = errors("[mint]\namount: u128 = 100u128\n");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].message, "missing `;` after the value of `amount`");
+    assert_eq!(errors[0].span.start.line, 2);
+
+    let record = format!(
+        "[burn]\ninput: Token = Token {{\n  owner: {},\n  balance: 1u128,\n  _nonce: {NONCE}\n}}\n",
+        common::ALICE
+    );
+    assert!(
+        messages(&record).contains(&"missing `;` after the value of `input`".to_string()),
+        "{:?}",
+        messages(&record)
+    );
+}
+
+#[test]
+fn types_and_literals_are_checked_against_the_function() {
+    let wrong_type = messages("[mint]\namount: u64 = 100u64;\n");
+    assert!(wrong_type.iter().any(|message| message.contains("u128")), "{wrong_type:?}");
+
+    let out_of_range = messages("[mint]\namount: u128 = 340282366920938463463374607431768211456u128;\n");
+    assert_eq!(out_of_range.len(), 1, "{out_of_range:?}");
+    assert!(out_of_range[0].contains("does not fit in `u128`"), "{out_of_range:?}");
+
+    let bad_address = messages("[mint_public]\nreceiver: address = aleo1notanaddress;\namount: u128 = 1u128;\n");
+    assert!(bad_address.iter().any(|message| message.starts_with("invalid address")), "{bad_address:?}");
+}
+
+#[test]
+fn a_record_without_its_nonce_is_incomplete() {
+    let source = format!(
+        "[burn]\ninput: Token = Token {{\n  owner: {},\n  balance: 1u128\n}};\namount: u128 = 1u128;\n",
+        common::ALICE
+    );
+    let errors = errors(&source);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].message, "`Token` literal is missing `_nonce`");
+    assert!(errors[0].help.as_deref().unwrap().contains("leo run"));
+}
+
+#[test]
+fn inputs_must_be_in_parameter_order() {
+    let source = format!("[mint_public]\namount: u128 = 1u128;\nreceiver: address = {};\n", common::ALICE);
+    let errors = errors(&source);
+    let misplaced = errors
+        .iter()
+        .find(|diagnostic| {
+            diagnostic.message == "input `amount` is parameter 2 of `mint_public`, but is declared at position 1"
+        })
+        .unwrap_or_else(|| panic!("{errors:?}"));
+    assert_eq!(misplaced.span.start, Position::new(2, 1));
+    assert_eq!(misplaced.help.as_deref(), Some("parameter 1 is `receiver: address`"));
+}
+
+#[test]
+fn sections_and_counts_must_match_the_program() {
+    let unknown = messages("[mintt]\namount: u128 = 1u128;\n");
+    assert_eq!(unknown.len(), 1, "{unknown:?}");
+    assert!(unknown[0].contains("has no function named `mintt`"), "{unknown:?}");
+
+    let missing = errors(&format!("[mint_public]\nreceiver: address = {};\n", common::ALICE));
+    assert_eq!(missing.len(), 1, "{missing:?}");
+    assert_eq!(missing[0].message, "function `mint_public` expects 2 input(s), but 1 are declared");
+    assert_eq!(missing[0].help.as_deref(), Some("add `amount: u128`"));
+
+    let twice = messages("[mint]\namount: u128 = 1u128;\n\n[mint]\namount: u128 = 2u128;\n");
+    assert_eq!(twice, ["section `[mint]` is declared more than once"]);
+}
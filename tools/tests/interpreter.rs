@@ -0,0 +1,131 @@
+//! Integer arithmetic, casts and value parsing in the interpreter.
+
+use workshop::aleo::{self, LiteralType};
+use workshop::interpreter::{Integer, InterpretError, Interpreter, Literal, Plaintext, Value, ZERO_ADDRESS};
+
+/// The type suffix of a literal such as `10u8`.
+fn ty(literal: &str) -> String {
+    match Value::parse(literal).unwrap() {
+        Value::Plaintext(Plaintext::Literal(literal)) => literal.ty().to_string(),
+        value => panic!("{value} is not a literal"),
+    }
+}
+
+/// Runs a one-instruction function on `operands` and returns its output,
+/// which is declared as `output`.
+fn run(instruction: &str, operands: &[&str], output: &str) -> Result<String, InterpretError> {
+    let registers: Vec<_> = (0..operands.len()).map(|index| format!("r{index}")).collect();
+    let mut source = "program ops.aleo;\n\nfunction run:\n".to_string();
+    for (register, operand) in registers.iter().zip(operands) {
+        source += &format!("    input {register} as {}.private;\n", ty(operand));
+    }
+    let result = format!("r{}", operands.len());
+    source += &format!("    {instruction} {} into {result}", registers.join(" "));
+    if instruction.starts_with("cast") {
+        source += &format!(" as {output}");
+    }
+    source += &format!(";\n    output {result} as {output}.private;\n");
+
+    let program = aleo::parse(&source).unwrap();
+    let inputs = operands.iter().map(|operand| Value::parse(operand).unwrap()).collect();
+    let outputs = Interpreter::new(&program, ZERO_ADDRESS).execute("run", inputs)?;
+    Ok(outputs[0].to_string())
+}
+
+fn binary(op: &str, a: &str, b: &str) -> Result<String, InterpretError> {
+    run(op, &[a, b], &ty(a))
+}
+
+fn arithmetic_error(result: Result<String, InterpretError>) -> String {
+    match result {
+        Err(InterpretError::Arithmetic { message, .. }) => message,
+        other => panic!("expected an arithmetic halt, found {other:?}"),
+    }
+}
+
+fn halt(result: Result<String, InterpretError>) -> String {
+    match result {
+        Err(InterpretError::Halt { message, .. }) => message,
+        other => panic!("expected a halt, found {other:?}"),
+    }
+}
+
+#[test]
+fn checked_operations_halt_on_overflow_and_underflow() {
+    assert_eq!(binary("add", "200u8", "55u8").unwrap(), "255u8");
+    assert_eq!(arithmetic_error(binary("add", "200u8", "56u8")), "`add 200u8 56u8` overflows u8");
+    assert_eq!(arithmetic_error(binary("sub", "1u128", "2u128")), "`sub 1u128 2u128` underflows u128");
+    assert_eq!(arithmetic_error(binary("mul", "-128i8", "-1i8")), "`mul -128i8 -1i8` overflows i8");
+    assert_eq!(arithmetic_error(binary("add", "-100i8", "-29i8")), "`add -100i8 -29i8` underflows i8");
+    assert_eq!(arithmetic_error(binary("div", "-128i8", "-1i8")), "`div -128i8 -1i8` overflows i8");
+    assert_eq!(arithmetic_error(binary("div", "1u32", "0u32")), "`div 1u32 0u32` divides by zero");
+    assert_eq!(arithmetic_error(binary("pow", "2u8", "8u8")), "`pow 2u8 8u8` overflows u8");
+    assert_eq!(
+        arithmetic_error(binary("shl", "1u16", "16u8")),
+        "`shl 1u16 16u8` shifts by more than the 16 bits of u16"
+    );
+    assert_eq!(arithmetic_error(run("neg", &["-128i8"], "i8")), "`neg -128i8` overflows i8");
+    assert_eq!(arithmetic_error(run("abs", &["-128i8"], "i8")), "`abs -128i8` overflows i8");
+    assert_eq!(binary("sub", "-100i8", "28i8").unwrap(), "-128i8");
+}
+
+#[test]
+fn wrapped_operations_keep_the_low_bits() {
+    assert_eq!(binary("add.w", "200u8", "56u8").unwrap(), "0u8");
+    assert_eq!(binary("sub.w", "0u128", "1u128").unwrap(), u128::MAX.to_string() + "u128");
+    assert_eq!(binary("mul.w", "127i8", "2i8").unwrap(), "-2i8");
+    assert_eq!(binary("div.w", "-128i8", "-1i8").unwrap(), "-128i8");
+    assert_eq!(binary("pow.w", "2u8", "9u8").unwrap(), "0u8");
+    assert_eq!(binary("shl.w", "1u16", "17u8").unwrap(), "2u16");
+    assert_eq!(binary("shr", "-8i32", "1u8").unwrap(), "-4i32");
+    assert_eq!(run("abs.w", &["-128i8"], "i8").unwrap(), "-128i8");
+    assert_eq!(arithmetic_error(binary("rem.w", "1u8", "0u8")), "`rem.w 1u8 0u8` divides by zero");
+}
+
+#[test]
+fn operands_must_share_a_type() {
+    assert_eq!(halt(binary("add", "1u8", "1u16")), "`add` needs operands of the same type, found u8 and u16");
+    assert_eq!(run("lt", &["-1i8", "0i8"], "boolean").unwrap(), "true");
+    assert_eq!(run("gte", &["255u8", "0u8"], "boolean").unwrap(), "true");
+    assert_eq!(halt(binary("mod", "1i8", "1i8")), "`mod` is not defined for i8");
+}
+
+#[test]
+fn casts_halt_unless_lossy() {
+    assert_eq!(run("cast", &["255u128"], "u8").unwrap(), "255u8");
+    assert_eq!(halt(run("cast", &["256u128"], "u8")), "cannot cast 256u128 to u8 without losing bits");
+    assert_eq!(halt(run("cast", &["-1i64"], "u64")), "cannot cast -1i64 to u64 without losing bits");
+    assert_eq!(run("cast", &["-1i64"], "i8").unwrap(), "-1i8");
+    assert_eq!(run("cast.lossy", &["256u128"], "u8").unwrap(), "0u8");
+    assert_eq!(run("cast.lossy", &["-1i64"], "u64").unwrap(), u64::MAX.to_string() + "u64");
+    assert_eq!(run("cast.lossy", &["200u8"], "i8").unwrap(), "-56i8");
+    assert_eq!(run("cast", &["true"], "u8").unwrap(), "1u8");
+    assert_eq!(run("cast", &["1u8"], "boolean").unwrap(), "true");
+    assert_eq!(halt(run("cast", &["2u8"], "boolean")), "cannot cast 2u8 to boolean");
+    assert_eq!(run("cast.lossy", &["2u8"], "boolean").unwrap(), "false");
+}
+
+#[test]
+fn values_parse_from_their_printed_form() {
+    let integer = |ty, value| Literal::Integer(Integer::from_i128(ty, value).unwrap());
+    assert_eq!(Value::parse("10u32").unwrap(), integer(LiteralType::U32, 10).into());
+    assert_eq!(Value::parse("-5i16").unwrap(), integer(LiteralType::I16, -5).into());
+    assert_eq!(Value::parse("1_000u64").unwrap(), integer(LiteralType::U64, 1000).into());
+    assert_eq!(Value::parse("true").unwrap(), Literal::Boolean(true).into());
+    assert!(Value::parse("256u8").unwrap_err().contains("out of range"));
+    assert!(Value::parse("10u32 11u32").unwrap_err().contains("after the value"));
+
+    let Value::Plaintext(pair) = Value::parse("{ a: 1u8, b: { c: false } }").unwrap() else {
+        panic!("a struct is plaintext")
+    };
+    assert_eq!(pair.member("a"), Some(&integer(LiteralType::U8, 1).into()));
+    assert!(matches!(pair.member("b"), Some(Plaintext::Struct(_))));
+
+    let text = "{\n  owner: aleo1rgg7jdpcka5wxggltt8vs6c5t6dw7q60r79xrn2qz9jpqefexuzsc2lkzs.private,\n  \
+                balance: 5u128.public,\n  _nonce: 0group.public\n}";
+    let Value::Record(record) = Value::parse(text).unwrap() else { panic!("a `_nonce` makes a record") };
+    assert_eq!(record.owner(), Some("aleo1rgg7jdpcka5wxggltt8vs6c5t6dw7q60r79xrn2qz9jpqefexuzsc2lkzs"));
+    assert_eq!(record.member("balance"), Some(&integer(LiteralType::U128, 5).into()));
+    assert_eq!(Value::parse(&record.to_string()).unwrap(), Value::Record(record));
+    assert_eq!(Value::parse("{ a: 1u8, _nonce: 1u8 }").unwrap_err(), "`_nonce` must be a group literal");
+}
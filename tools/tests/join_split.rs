@@ -0,0 +1,149 @@
+//! `join` and `split`, and covering a transfer with [`wallet::select`].
+
+mod common;
+
+use common::{program, record_balance, run, ADMIN, BOB};
+use workshop::interpreter::{InterpretError, Interpreter, Record, Value};
+use workshop::wallet::{self, SelectError};
+
+/// Runs `function` as `caller` with `records` followed by `texts`.
+fn spend(
+    interpreter: &mut Interpreter<'_>,
+    caller: &str,
+    function: &str,
+    records: Vec<Value>,
+    texts: &[&str],
+) -> Result<Vec<Value>, InterpretError> {
+    interpreter.set_caller(caller);
+    let mut inputs = records;
+    inputs.extend(common::inputs(texts));
+    interpreter.execute(function, inputs)
+}
+
+/// Mints one record per amount to the admin.
+fn mint(interpreter: &mut Interpreter<'_>, amounts: &[u128]) -> Vec<Record> {
+    amounts
+        .iter()
+        .map(|amount| match run(interpreter, ADMIN, "mint", &[&format!("{amount}u128")]).unwrap().remove(0) {
+            Value::Record(record) => record,
+            other => panic!("expected a record, found {other}"),
+        })
+        .collect()
+}
+
+#[test]
+fn join_merges_two_records() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let records = mint(&mut interpreter, &[30, 12]);
+
+    let joined = spend(&mut interpreter, ADMIN, "join", records.into_iter().map(Value::Record).collect(), &[]).unwrap();
+    assert_eq!(joined.len(), 1);
+    assert_eq!(record_balance(&joined[0]), 42);
+}
+
+#[test]
+fn split_divides_a_record() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let record = mint(&mut interpreter, &[100]).remove(0);
+
+    let parts = spend(&mut interpreter, ADMIN, "split", vec![Value::Record(record.clone())], &["25u128"]).unwrap();
+    assert_eq!(parts.iter().map(record_balance).collect::<Vec<_>>(), [25, 75]);
+
+    let error = spend(&mut interpreter, ADMIN, "split", vec![Value::Record(record)], &["101u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+}
+
+#[test]
+fn select_prefers_the_smallest_single_record() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let records = mint(&mut interpreter, &[50, 20, 35, 5]);
+
+    let selection = wallet::select(&records, "balance", 30).unwrap();
+    assert_eq!(selection.indices, [2]);
+    assert_eq!(selection.joins(), 0);
+    assert_eq!(selection.change(30), 5);
+}
+
+#[test]
+fn select_joins_the_fewest_records_and_the_smallest_last_one() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let records = mint(&mut interpreter, &[10, 40, 25, 30, 12]);
+
+    // 40 + 30 = 70 is short of 75, so three records are needed; 40 + 30 +
+    // 10 leaves 5 of change where 40 + 30 + 25 would leave 20.
+    let selection = wallet::select(&records, "balance", 75).unwrap();
+    assert_eq!(selection.indices, [1, 3, 0]);
+    assert_eq!(selection.total, 80);
+    assert_eq!(selection.joins(), 2);
+
+    // Only the last pick is swapped: 60 + 50 is chosen over the exact 50 + 50.
+    let records = mint(&mut interpreter, &[60, 55, 50, 50]);
+    let selection = wallet::select(&records, "balance", 100).unwrap();
+    assert_eq!(selection.indices, [0, 2]);
+    assert_eq!(selection.change(100), 10);
+}
+
+#[test]
+fn select_reports_insufficient_records() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let records = mint(&mut interpreter, &[10, 20]);
+
+    assert_eq!(
+        wallet::select(&records, "balance", 31),
+        Err(SelectError::Insufficient { available: 30, requested: 31 })
+    );
+    assert_eq!(wallet::select(&[], "balance", 0), Err(SelectError::Insufficient { available: 0, requested: 0 }));
+    assert_eq!(
+        wallet::select(&records, "amount", 1),
+        Err(SelectError::NoAmount { index: 0, member: "amount".to_string() })
+    );
+}
+
+#[test]
+fn select_does_not_overflow_on_huge_records() {
+    // Past the supply cap, so these are built rather than minted.
+    let half = u128::MAX / 2 + 1;
+    let record = |amount: u128| match Value::parse(&format!(
+        "{{ owner: {ADMIN}.private, balance: {amount}u128.private, _nonce: 0group.public }}"
+    ))
+    .unwrap()
+    {
+        Value::Record(record) => record,
+        other => panic!("expected a record, found {other}"),
+    };
+    let records = [record(half), record(half), record(5)];
+
+    let selection = wallet::select(&records, "balance", half).unwrap();
+    assert_eq!((selection.indices, selection.total), (vec![0], half));
+    let selection = wallet::select(&records, "balance", half + 3).unwrap();
+    assert_eq!((selection.indices, selection.total), (vec![0, 2], half + 5));
+    assert_eq!(wallet::select(&records, "balance", u128::MAX), Err(SelectError::Overflow { requested: u128::MAX }));
+    assert_eq!(wallet::select(&records[..2], "balance", half + 6), Err(SelectError::Overflow { requested: half + 6 }));
+}
+
+#[test]
+fn a_selection_joined_in_order_covers_the_transfer() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let records = mint(&mut interpreter, &[15, 40, 8, 22]);
+
+    let selection = wallet::select(&records, "balance", 70).unwrap();
+    let mut chosen = selection.indices.iter().map(|&index| Value::Record(records[index].clone()));
+    let mut joined = chosen.next().unwrap();
+    for record in chosen {
+        joined = spend(&mut interpreter, ADMIN, "join", vec![joined, record], &[]).unwrap().remove(0);
+    }
+    assert_eq!(record_balance(&joined), selection.total);
+
+    interpreter.set_caller(ADMIN);
+    let mut inputs = common::inputs(&[BOB, "70u128"]);
+    inputs.push(joined);
+    let outputs = interpreter.execute("transfer", inputs).unwrap();
+    assert_eq!(record_balance(&outputs[0]), 70);
+    assert_eq!(record_balance(&outputs[1]), selection.change(70));
+}
@@ -0,0 +1,71 @@
+//! Storing private keys encrypted and unlocking them again.
+
+mod common;
+
+use common::{TempDir, ALICE, BOB};
+use workshop::address::Address;
+use workshop::deploy::{DeployError, PrivateKey};
+use workshop::keystore::{check_private_key, generate_private_key, Keystore, KeystoreError};
+
+#[test]
+fn an_added_key_unlocks_with_its_passphrase_only() {
+    let dir = TempDir::new("keystore-unlock");
+    let keystore = Keystore::open(dir.join("keystore"));
+    assert_eq!(keystore.accounts().unwrap(), []);
+
+    let key = generate_private_key();
+    check_private_key(&key).unwrap();
+    let account = keystore.add("workshop", &key, None, "correct horse").unwrap();
+    assert_eq!(account.address, None);
+    let stored = std::fs::read_to_string(&account.path).unwrap();
+    assert!(!stored.contains(key.expose()), "the key is stored in the clear");
+
+    assert_eq!(keystore.unlock("workshop", "correct horse").unwrap().expose(), key.expose());
+    let error = keystore.unlock("workshop", "battery staple").unwrap_err();
+    assert!(matches!(error, KeystoreError::WrongPassphrase(ref name) if name == "workshop"), "{error}");
+
+    assert!(matches!(keystore.add("workshop", &key, None, "again"), Err(KeystoreError::Exists(_))));
+    assert!(matches!(keystore.add("work shop", &key, None, "pass"), Err(KeystoreError::InvalidName(_))));
+    assert!(matches!(keystore.add("other", &key, None, ""), Err(KeystoreError::EmptyPassphrase)));
+    let error = keystore.unlock("missing", "pass").unwrap_err();
+    assert!(error.to_string().ends_with("; known accounts: workshop"), "{error}");
+
+    keystore.remove("workshop").unwrap();
+    assert_eq!(keystore.accounts().unwrap(), []);
+}
+
+#[test]
+fn addresses_are_stored_and_can_be_set_later() {
+    let dir = TempDir::new("keystore-address");
+    let keystore = Keystore::open(dir.join("keystore"));
+    let alice = Address::parse(ALICE).unwrap();
+    keystore.add("imported", &generate_private_key(), Some(alice.clone()), "pass").unwrap();
+    let key = generate_private_key();
+    keystore.add("generated", &key, None, "pass").unwrap();
+
+    let bob = Address::parse(BOB).unwrap();
+    assert_eq!(keystore.set_address("generated", bob.clone()).unwrap().address, Some(bob.clone()));
+    let addresses: Vec<_> =
+        keystore.accounts().unwrap().into_iter().map(|account| (account.name, account.address)).collect();
+    assert_eq!(addresses, [("generated".to_string(), Some(bob)), ("imported".to_string(), Some(alice))]);
+    assert_eq!(keystore.unlock("generated", "pass").unwrap().expose(), key.expose());
+    assert!(matches!(
+        keystore.set_address("missing", Address::parse(BOB).unwrap()),
+        Err(KeystoreError::Unknown { .. })
+    ));
+}
+
+#[test]
+fn imported_keys_must_decode_to_a_private_key() {
+    let key = generate_private_key();
+    check_private_key(&key).unwrap();
+    assert_eq!(key.expose().len(), 59);
+
+    // Right shape, but the base58 bytes do not start with the key prefix.
+    let shaped = PrivateKey::parse(&format!("APrivateKey1{}", "z".repeat(47)), "a test").unwrap();
+    assert!(matches!(check_private_key(&shaped), Err(DeployError::MalformedKey { .. })));
+    // `0` is not a base58 character.
+    let zero = PrivateKey::parse(&format!("APrivateKey1{}", "0".repeat(47)), "a test").unwrap();
+    assert!(matches!(check_private_key(&zero), Err(DeployError::MalformedKey { .. })));
+    assert!(PrivateKey::parse("APrivateKey1tooshort", "a test").is_err());
+}
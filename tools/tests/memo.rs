@@ -0,0 +1,59 @@
+//! `transfer_with_memo` and the helpers packing memos into fields.
+
+mod common;
+
+use common::{program, record_balance, run, ADMIN, BOB};
+use workshop::interpreter::{Interpreter, Value};
+use workshop::memo::{decode_memo, encode_memo, record_memo, MemoError, MEMO_CAPACITY};
+
+#[test]
+fn memos_round_trip_through_fields() {
+    for text in ["", "a", "March rent", "café ☕", "🦀🦀🦀🦀🦀🦀🦀", &"z".repeat(MEMO_CAPACITY)] {
+        let field = encode_memo(text).unwrap();
+        assert!(field.ends_with("field"), "{field}");
+        assert_eq!(decode_memo(&field).as_deref(), Ok(text), "{field}");
+    }
+    assert_eq!(encode_memo("").unwrap(), "0field");
+    assert_eq!(encode_memo("A").unwrap(), "65field");
+    assert_eq!(decode_memo("65field.private").as_deref(), Ok("A"));
+}
+
+#[test]
+fn encode_memo_rejects_what_a_field_cannot_hold() {
+    assert_eq!(encode_memo(&"z".repeat(MEMO_CAPACITY + 1)), Err(MemoError::TooLong(MEMO_CAPACITY + 1)));
+    // 4 bytes per crab: 8 of them no longer fit.
+    assert_eq!(encode_memo(&"🦀".repeat(8)), Err(MemoError::TooLong(32)));
+    assert_eq!(encode_memo("a\0b"), Err(MemoError::Nul));
+}
+
+#[test]
+fn decode_memo_rejects_fields_that_are_not_text() {
+    assert_eq!(decode_memo("12u128"), Err(MemoError::NotField("12u128".to_string())));
+    assert_eq!(decode_memo("field"), Err(MemoError::NotField("field".to_string())));
+    // 0xff is never valid UTF-8.
+    assert_eq!(decode_memo("255field"), Err(MemoError::NotText("255field".to_string())));
+    // 2^248 is a field element, but wider than any memo.
+    let wide = "452312848583266388373324160190187140051835877600158453279131187530910662656field";
+    assert_eq!(decode_memo(wide), Err(MemoError::NotText(wide.to_string())));
+}
+
+#[test]
+fn transfer_with_memo_gives_the_receiver_a_note() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let record = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap().remove(0);
+
+    let memo = encode_memo("March rent").unwrap();
+    let mut inputs = common::inputs(&[BOB, "30u128"]);
+    inputs.push(record);
+    inputs.extend(common::inputs(&[&memo]));
+    let outputs = interpreter.execute("transfer_with_memo", inputs).unwrap();
+
+    assert_eq!(outputs.iter().take(2).map(record_balance).collect::<Vec<_>>(), [30, 70]);
+    let Value::Record(note) = &outputs[2] else { panic!("expected a record, found {}", outputs[2]) };
+    assert_eq!(note.member("owner").map(ToString::to_string), Some(BOB.to_string()));
+    assert_eq!(record_memo(note), Some(Ok("March rent".to_string())));
+
+    let Value::Record(token) = &outputs[0] else { panic!("expected a record, found {}", outputs[0]) };
+    assert_eq!(record_memo(token), None);
+}
@@ -0,0 +1,182 @@
+//! The mock node's query and broadcast endpoints, directly and over HTTP.
+
+mod common;
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use common::{TempDir, ALICE};
+use workshop::address::{encode_bech32m, Address};
+use workshop::mock_node::{MockNode, DEFAULT_CREDITS, MAX_BODY_LENGTH, STATE_FILE, TRANSACTIONS_DIR};
+
+const TOKEN: &str = include_str!("../../token_dsfl348dfl93w1/build/main.aleo");
+
+/// An empty directory for one test's node.
+fn node_dir(name: &str) -> TempDir {
+    TempDir::new(&format!("mock-node-{name}"))
+}
+
+fn deployment(id: &str, program: &str) -> String {
+    serde_json::json!({ "type": "deploy", "id": id, "deployment": { "edition": 0, "program": program } }).to_string()
+}
+
+fn get(node: &mut MockNode, path: &str) -> (u16, String) {
+    let response = node.handle("GET", path, "");
+    (response.status, response.body)
+}
+
+fn broadcast(node: &mut MockNode, body: &str) -> (u16, String) {
+    let response = node.handle("POST", "/testnet3/transaction/broadcast", body);
+    (response.status, response.body)
+}
+
+#[test]
+fn bech32m_encoding_round_trips_addresses() {
+    let address = Address::parse(ALICE).unwrap();
+    assert_eq!(encode_bech32m("aleo", &address.to_bytes()), ALICE);
+}
+
+#[test]
+fn an_empty_node_starts_at_height_zero() {
+    let dir = node_dir("empty");
+    let mut node = MockNode::open(&dir).unwrap();
+
+    assert_eq!(get(&mut node, "/testnet3/latest/height"), (200, "0".to_string()));
+    let (status, root) = get(&mut node, "/testnet3/latest/stateRoot");
+    assert_eq!(status, 200);
+    assert_eq!(root, format!("\"{}\"", node.state_root()));
+    assert!(node.state_root().starts_with("ar1"), "{root}");
+    assert_eq!(get(&mut node, "/testnet3/stateRoot/latest").1, root);
+    assert_eq!(get(&mut node, "/testnet3/program/token_dsfl348dfl93w1.aleo").0, 404);
+    assert_eq!(get(&mut node, "/testnet3/nothing").0, 404);
+    assert_eq!(get(&mut node, "/mainnet/latest/height").0, 404);
+}
+
+#[test]
+fn a_deployment_adds_the_program_in_a_new_block() {
+    let dir = node_dir("deploy");
+    let mut node = MockNode::open(&dir).unwrap();
+    let root = node.state_root();
+
+    assert_eq!(broadcast(&mut node, &deployment("at1dep", TOKEN)), (200, "\"at1dep\"".to_string()));
+    assert_eq!(get(&mut node, "/testnet3/latest/height"), (200, "1".to_string()));
+    assert_ne!(node.state_root(), root);
+
+    let (status, source) = get(&mut node, "/testnet3/program/token_dsfl348dfl93w1.aleo");
+    assert_eq!(status, 200);
+    assert_eq!(serde_json::from_str::<String>(&source).unwrap(), TOKEN);
+    let (status, names) = get(&mut node, "/testnet3/program/token_dsfl348dfl93w1.aleo/mappings");
+    assert_eq!(status, 200);
+    assert_eq!(names, r#"["account","supply","allowance","paused","frozen"]"#);
+
+    let (status, stored) = get(&mut node, "/testnet3/transaction/at1dep");
+    assert_eq!((status, stored), (200, deployment("at1dep", TOKEN)));
+    assert!(dir.join(TRANSACTIONS_DIR).join("at1dep.json").is_file());
+}
+
+#[test]
+fn broadcasts_are_checked_before_they_are_accepted() {
+    let dir = node_dir("reject");
+    let mut node = MockNode::open(&dir).unwrap();
+    broadcast(&mut node, &deployment("at1ahead", TOKEN));
+
+    assert_eq!(broadcast(&mut node, "not json").0, 400);
+    assert_eq!(broadcast(&mut node, r#"{"type":"execute"}"#).0, 400);
+    assert_eq!(broadcast(&mut node, r#"{"type":"mint","id":"at1strange"}"#).0, 400);
+    assert_eq!(broadcast(&mut node, &deployment("at1ahead", "program other.aleo;\n")).0, 400);
+    let (status, message) = broadcast(&mut node, &deployment("at1later", TOKEN));
+    assert_eq!(status, 400);
+    assert!(message.contains("already exists"), "{message}");
+    assert_eq!(node.handle("GET", "/testnet3/transaction/broadcast", "").status, 405);
+    assert_eq!(node.state().height, 1);
+
+    // The id becomes a file name, so only bech32 characters may follow `at1`.
+    for id in ["at1/../x", "at1", "at1Run", "at1bio", "xat1run"] {
+        let body = serde_json::json!({ "type": "execute", "id": id }).to_string();
+        assert_eq!(broadcast(&mut node, &body).0, 400, "{id}");
+    }
+    assert!(!dir.join(TRANSACTIONS_DIR).join("x.json").exists());
+    assert_eq!(get(&mut node, "/testnet3/transaction/..").0, 404);
+    assert_eq!(node.state().height, 1);
+
+    assert_eq!(broadcast(&mut node, r#"{"type":"execute","id":"at1run"}"#).0, 200);
+    assert_eq!(node.state().height, 2);
+}
+
+#[test]
+fn mapping_values_come_from_the_state_file() {
+    let dir = node_dir("mappings");
+    let mut node = MockNode::open(&dir).unwrap();
+    node.add_program(TOKEN).unwrap();
+    // Adding the same program again, as a restart with `--program` does, is fine.
+    node.add_program(TOKEN).unwrap();
+
+    let path = dir.join(STATE_FILE);
+    let mut state: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    state["mappings"]["token_dsfl348dfl93w1.aleo"]["account"][ALICE] = "100u128".into();
+    state["mappings"]["credits.aleo"]["account"][ALICE] = "5u64".into();
+    std::fs::write(&path, state.to_string()).unwrap();
+    let mut node = MockNode::open(&dir).unwrap();
+
+    let account = format!("/testnet3/program/token_dsfl348dfl93w1.aleo/mapping/account/{ALICE}");
+    assert_eq!(get(&mut node, &account), (200, "\"100u128\"".to_string()));
+    let supply = "/testnet3/program/token_dsfl348dfl93w1.aleo/mapping/supply/0u8";
+    assert_eq!(get(&mut node, supply), (200, "null".to_string()));
+    assert_eq!(get(&mut node, "/testnet3/program/token_dsfl348dfl93w1.aleo/mapping/missing/0u8").0, 404);
+    assert_eq!(get(&mut node, "/testnet3/program/other.aleo/mapping/account/0u8").0, 404);
+
+    let credits = format!("/testnet3/program/credits.aleo/mapping/account/{ALICE}");
+    assert_eq!(get(&mut node, &credits), (200, "\"5u64\"".to_string()));
+    let unfunded = "/testnet3/program/credits.aleo/mapping/account/aleo1unseen";
+    assert_eq!(get(&mut node, unfunded), (200, format!("\"{DEFAULT_CREDITS}u64\"")));
+}
+
+#[test]
+fn accepted_transactions_survive_a_restart() {
+    let dir = node_dir("restart");
+    let mut node = MockNode::open(&dir).unwrap();
+    broadcast(&mut node, &deployment("at1dep", TOKEN));
+    let root = node.state_root();
+
+    let mut node = MockNode::open(&dir).unwrap();
+    assert_eq!(node.state().height, 1);
+    assert_eq!(node.state_root(), root);
+    assert_eq!(node.state().transactions, ["at1dep"]);
+    assert_eq!(get(&mut node, "/testnet3/program/token_dsfl348dfl93w1.aleo").0, 200);
+}
+
+#[test]
+fn the_node_answers_over_http() {
+    let dir = node_dir("http");
+    let mut node = MockNode::open(&dir).unwrap();
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || node.serve(&listener));
+
+    let request = |text: String| {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.write_all(text.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let body = deployment("at1http", TOKEN);
+    let response = request(format!(
+        "POST /testnet3/transaction/broadcast HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    ));
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.ends_with("\r\n\r\n\"at1http\""), "{response}");
+
+    let response = request("GET /testnet3/latest/height HTTP/1.1\r\nHost: localhost\r\n\r\n".to_string());
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.contains("Content-Length: 1\r\n"), "{response}");
+    assert!(response.ends_with("\r\n\r\n1"), "{response}");
+
+    let broadcast = "POST /testnet3/transaction/broadcast HTTP/1.1\r\nHost: localhost\r\n";
+    let response = request(format!("{broadcast}Content-Length: {}\r\n\r\n", MAX_BODY_LENGTH + 1));
+    assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"), "{response}");
+    let response = request(format!("{broadcast}Content-Length: -1\r\n\r\n"));
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"), "{response}");
+}
@@ -0,0 +1,144 @@
+//! `pause`, `unpause`, `freeze` and `unfreeze`, and the checks every public
+//! transfer's and `burn_public`'s finalize block makes against the `paused`
+//! and `frozen` mappings.
+
+mod common;
+
+use common::{minted, program, public_balance, record_balance, run, ADMIN, ALICE, BOB, CAROL};
+use workshop::aleo::Program;
+use workshop::interpreter::{InterpretError, Interpreter, Literal, Plaintext, Value};
+
+/// An interpreter where ALICE and BOB hold 100 publicly and ALICE lets BOB
+/// spend 50 of hers.
+fn funded(program: &Program) -> Interpreter<'_> {
+    let mut interpreter = Interpreter::new(program, ADMIN);
+    run(&mut interpreter, ADMIN, "mint_public", &[ALICE, "100u128"]).unwrap();
+    run(&mut interpreter, ADMIN, "mint_public", &[BOB, "100u128"]).unwrap();
+    run(&mut interpreter, ALICE, "approve_public", &[BOB, "50u128"]).unwrap();
+    interpreter
+}
+
+fn is_frozen(interpreter: &Interpreter<'_>, owner: &str) -> bool {
+    let key = Plaintext::Literal(Literal::Address(owner.to_string()));
+    matches!(interpreter.mapping_value("frozen", &key), Some(Plaintext::Literal(Literal::Boolean(true))))
+}
+
+fn assert_halts(result: Result<Vec<Value>, InterpretError>) {
+    let error = result.unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+}
+
+#[test]
+fn only_the_admin_can_pause_and_freeze() {
+    let program = program();
+    let mut interpreter = funded(&program);
+
+    assert_halts(run(&mut interpreter, BOB, "pause", &[]));
+    assert_halts(run(&mut interpreter, BOB, "unpause", &[]));
+    assert_halts(run(&mut interpreter, BOB, "freeze", &[ALICE]));
+    assert_halts(run(&mut interpreter, BOB, "unfreeze", &[BOB]));
+    assert!(!is_frozen(&interpreter, ALICE));
+
+    run(&mut interpreter, BOB, "transfer_public", &[CAROL, "10u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, CAROL), Some(10));
+}
+
+#[test]
+fn pause_halts_every_public_transfer_until_unpause() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    let record = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap().remove(0);
+
+    run(&mut interpreter, ADMIN, "pause", &[]).unwrap();
+    assert_halts(run(&mut interpreter, ALICE, "transfer_public", &[CAROL, "10u128"]));
+    assert_halts(run(&mut interpreter, ALICE, "transfer_public_to_private", &[CAROL, "10u128"]));
+    assert_halts(run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, CAROL, "10u128"]));
+    interpreter.set_caller(ADMIN);
+    let mut inputs = vec![record.clone()];
+    inputs.extend(common::inputs(&[CAROL, "10u128"]));
+    assert_halts(interpreter.execute("transfer_private_to_public", inputs.clone()));
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+    assert_eq!(public_balance(&interpreter, CAROL), None);
+
+    run(&mut interpreter, ADMIN, "unpause", &[]).unwrap();
+    run(&mut interpreter, ALICE, "transfer_public", &[CAROL, "10u128"]).unwrap();
+    run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, CAROL, "10u128"]).unwrap();
+    interpreter.set_caller(ADMIN);
+    let change = interpreter.execute("transfer_private_to_public", inputs).unwrap();
+    assert_eq!(record_balance(&change[0]), 90);
+    assert_eq!(public_balance(&interpreter, ALICE), Some(80));
+    assert_eq!(public_balance(&interpreter, CAROL), Some(30));
+}
+
+#[test]
+fn frozen_account_cannot_move_its_public_balance() {
+    let program = program();
+    let mut interpreter = funded(&program);
+
+    run(&mut interpreter, ADMIN, "freeze", &[BOB]).unwrap();
+    assert!(is_frozen(&interpreter, BOB));
+    assert_halts(run(&mut interpreter, BOB, "transfer_public", &[CAROL, "10u128"]));
+    assert_halts(run(&mut interpreter, BOB, "transfer_public_to_private", &[BOB, "10u128"]));
+    assert_eq!(public_balance(&interpreter, BOB), Some(100));
+
+    // Others still transact, including paying the frozen account.
+    run(&mut interpreter, ALICE, "transfer_public", &[BOB, "10u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, BOB), Some(110));
+
+    run(&mut interpreter, ADMIN, "unfreeze", &[BOB]).unwrap();
+    assert!(!is_frozen(&interpreter, BOB));
+    run(&mut interpreter, BOB, "transfer_public", &[CAROL, "10u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, BOB), Some(100));
+    assert_eq!(public_balance(&interpreter, CAROL), Some(10));
+}
+
+#[test]
+fn a_frozen_or_paused_public_balance_cannot_be_burned() {
+    let program = program();
+    let mut interpreter = funded(&program);
+
+    run(&mut interpreter, ADMIN, "freeze", &[BOB]).unwrap();
+    assert_halts(run(&mut interpreter, BOB, "burn_public", &["10u128"]));
+    assert_eq!(public_balance(&interpreter, BOB), Some(100));
+    assert_eq!(minted(&interpreter), Some(200));
+    run(&mut interpreter, ALICE, "burn_public", &["10u128"]).unwrap();
+
+    run(&mut interpreter, ADMIN, "unfreeze", &[BOB]).unwrap();
+    run(&mut interpreter, ADMIN, "pause", &[]).unwrap();
+    assert_halts(run(&mut interpreter, BOB, "burn_public", &["10u128"]));
+    run(&mut interpreter, ADMIN, "unpause", &[]).unwrap();
+    run(&mut interpreter, BOB, "burn_public", &["10u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, ALICE), Some(90));
+    assert_eq!(public_balance(&interpreter, BOB), Some(90));
+    assert_eq!(minted(&interpreter), Some(180));
+}
+
+#[test]
+fn frozen_approver_or_spender_halts_transfer_from_public() {
+    let program = program();
+    let mut interpreter = funded(&program);
+
+    run(&mut interpreter, ADMIN, "freeze", &[ALICE]).unwrap();
+    assert_halts(run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, CAROL, "10u128"]));
+    run(&mut interpreter, ADMIN, "unfreeze", &[ALICE]).unwrap();
+
+    run(&mut interpreter, ADMIN, "freeze", &[BOB]).unwrap();
+    assert_halts(run(&mut interpreter, BOB, "transfer_from_public", &[ALICE, CAROL, "10u128"]));
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+    assert_eq!(public_balance(&interpreter, CAROL), None);
+}
+
+#[test]
+fn freezing_does_not_reach_private_records() {
+    let program = program();
+    let mut interpreter = funded(&program);
+    let record = run(&mut interpreter, ADMIN, "mint", &["100u128"]).unwrap().remove(0);
+
+    // `transfer` has no finalize block, so neither switch can stop it.
+    run(&mut interpreter, ADMIN, "freeze", &[ADMIN]).unwrap();
+    run(&mut interpreter, ADMIN, "pause", &[]).unwrap();
+    let mut inputs = common::inputs(&[BOB, "30u128"]);
+    inputs.push(record);
+    let outputs = interpreter.execute("transfer", inputs).unwrap();
+    assert_eq!(outputs.iter().map(record_balance).collect::<Vec<_>>(), [30, 70]);
+}
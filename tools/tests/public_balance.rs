@@ -0,0 +1,69 @@
+//! `mint_public` and `transfer_public` run through the interpreter, checking
+//! the `account` mapping their finalize blocks leave behind.
+
+mod common;
+
+use common::{program, public_balance, run, ALICE, BOB};
+use workshop::interpreter::{InterpretError, Interpreter};
+
+#[test]
+fn mint_public_credits_the_receiver() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ALICE);
+    assert_eq!(public_balance(&interpreter, BOB), None);
+
+    let outputs = run(&mut interpreter, ALICE, "mint_public", &[BOB, "100u128"]).unwrap();
+    assert!(outputs.is_empty());
+    assert_eq!(public_balance(&interpreter, BOB), Some(100));
+    assert_eq!(public_balance(&interpreter, ALICE), None);
+
+    run(&mut interpreter, ALICE, "mint_public", &[BOB, "25u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, BOB), Some(125));
+}
+
+#[test]
+fn transfer_public_moves_balance_from_the_caller() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ALICE);
+    run(&mut interpreter, ALICE, "mint_public", &[ALICE, "100u128"]).unwrap();
+
+    run(&mut interpreter, ALICE, "transfer_public", &[BOB, "40u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, ALICE), Some(60));
+    assert_eq!(public_balance(&interpreter, BOB), Some(40));
+
+    run(&mut interpreter, BOB, "transfer_public", &[ALICE, "40u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+    assert_eq!(public_balance(&interpreter, BOB), Some(0));
+}
+
+#[test]
+fn transfer_public_to_self_keeps_the_balance() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ALICE);
+    run(&mut interpreter, ALICE, "mint_public", &[ALICE, "100u128"]).unwrap();
+
+    run(&mut interpreter, ALICE, "transfer_public", &[ALICE, "100u128"]).unwrap();
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+}
+
+#[test]
+fn transfer_public_beyond_the_balance_halts_and_rolls_back() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ALICE);
+    run(&mut interpreter, ALICE, "mint_public", &[ALICE, "100u128"]).unwrap();
+
+    let error = run(&mut interpreter, ALICE, "transfer_public", &[BOB, "101u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert_eq!(public_balance(&interpreter, ALICE), Some(100));
+    assert_eq!(public_balance(&interpreter, BOB), None);
+}
+
+#[test]
+fn transfer_public_without_an_account_halts() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, BOB);
+
+    let error = run(&mut interpreter, BOB, "transfer_public", &[ALICE, "1u128"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Arithmetic { .. }), "{error}");
+    assert!(interpreter.mapping_entries("account").is_empty());
+}
@@ -0,0 +1,118 @@
+//! Turning a record printed by `leo run` into an input file assignment.
+
+mod common;
+
+use common::{program, ALICE};
+use workshop::inputs::{self, record, record::RecordError};
+use workshop::interpreter::Value;
+use workshop::leo;
+
+const SOURCE: &str = include_str!("../../token_dsfl348dfl93w1/src/main.leo");
+const INPUT_FILE: &str = include_str!("../../token_dsfl348dfl93w1/inputs/token_dsfl348dfl93w1.in");
+
+/// What `leo run mint 100u128` prints.
+fn leo_run_output() -> String {
+    format!(
+        "       Leo ✅ Compiled 'main.leo' into Aleo instructions\n\n➡️  Output\n\n • {{\n  owner: {ALICE}.private,\n  \
+         balance: 100u128.private,\n  _nonce: 1234group.public\n}}\n\n       Leo ✅ Finished \
+         'token_dsfl348dfl93w1.aleo/mint'\n"
+    )
+}
+
+/// `source` split after each blank line, i.e. into its sections.
+fn sections(source: &str) -> Vec<&str> {
+    source.split_inclusive("\n\n").collect()
+}
+
+#[test]
+fn a_record_is_found_in_leo_run_output() {
+    let record = record::find_record(&leo_run_output()).unwrap();
+    assert_eq!(record.owner(), Some(ALICE));
+    assert_eq!(record.nonce, "1234group");
+    assert_eq!(record.member("balance").unwrap().to_string(), "100u128");
+
+    assert_eq!(record::find_record("Leo ✅ Finished"), Err(RecordError::NotFound));
+    assert_eq!(record::find_record("output: { amount: 1u8 }"), Err(RecordError::NotARecord));
+    assert_eq!(record::find_record("{ amount: 1u8"), Err(RecordError::NotFound));
+    assert!(matches!(record::find_record("{ amount: 1x8, _nonce: 0group }"), Err(RecordError::Parse(_))));
+}
+
+#[test]
+fn the_target_is_resolved_from_the_function_and_its_parameters() {
+    let program = program();
+    let transitions = leo::transitions(SOURCE);
+
+    let error = record::target(&program, &transitions, None, None).unwrap_err();
+    assert!(matches!(error, RecordError::Ambiguous { flag: "--function", .. }), "{error}");
+    assert!(error.to_string().contains("`transfer`, `transfer_with_memo`"), "{error}");
+
+    let target = record::target(&program, &transitions, Some("transfer"), None).unwrap();
+    assert_eq!(
+        target,
+        record::Target { function: "transfer".to_string(), name: "input".to_string(), record: "Token".to_string() }
+    );
+    let unnamed = record::target(&program, &[], Some("transfer"), None).unwrap();
+    assert_eq!(unnamed.name, "r2");
+    assert_eq!(record::target(&program, &transitions, Some("claim"), None).unwrap().record, "VestingToken");
+
+    let error = record::target(&program, &transitions, Some("join"), None).unwrap_err();
+    assert_eq!(
+        error,
+        RecordError::Ambiguous { candidates: "`join` takes records `a`, `b`".to_string(), flag: "--name" }
+    );
+    assert_eq!(record::target(&program, &transitions, Some("join"), Some("b")).unwrap().name, "b");
+    assert_eq!(
+        record::target(&program, &transitions, Some("join"), Some("c")),
+        Err(RecordError::UnknownInput { function: "join".to_string(), name: "c".to_string() })
+    );
+    assert_eq!(
+        record::target(&program, &transitions, Some("mint"), None),
+        Err(RecordError::NoRecordInput { function: "mint".to_string() })
+    );
+    assert_eq!(
+        record::target(&program, &transitions, Some("mintt"), None),
+        Err(RecordError::UnknownFunction("mintt".to_string()))
+    );
+}
+
+#[test]
+fn writing_replaces_only_the_chosen_input() {
+    let program = program();
+    let target = record::target(&program, &leo::transitions(SOURCE), Some("transfer"), None).unwrap();
+    let found = record::find_record(&leo_run_output()).unwrap();
+    let literal = record::record_literal(&program, &target.record, &found).unwrap();
+    assert_eq!(literal, format!("Token {{\n  owner: {ALICE},\n  balance: 100u128,\n  _nonce: 1234group\n}}"));
+    let assignment = record::assignment(&target, &literal);
+
+    let (file, _) = inputs::parse(INPUT_FILE);
+    let updated = record::set_input(INPUT_FILE, &file, &target.function, &target.name, &assignment);
+    let (before, after) = (sections(INPUT_FILE), sections(&updated));
+    assert_eq!(before.len(), after.len());
+    for (before, after) in before.iter().zip(&after) {
+        if before.starts_with("[transfer]\n") {
+            assert_eq!(after.replace(&assignment, ""), before[..before.find("input:").unwrap()].to_string() + "\n\n");
+        } else {
+            assert_eq!(before, after);
+        }
+    }
+
+    let (file, diagnostics) = inputs::parse(&updated);
+    assert_eq!(diagnostics, []);
+    let input = &file.section("transfer").unwrap().inputs[2];
+    assert_eq!(Value::from_input(&input.value).unwrap(), Value::Record(found));
+}
+
+#[test]
+fn a_missing_input_or_section_is_added() {
+    let source = "[mint]\namount: u128 = 100u128;\n";
+    let (file, _) = inputs::parse(source);
+    assert_eq!(
+        record::set_input(source, &file, "mint", "memo", "memo: field = 0field;"),
+        "[mint]\namount: u128 = 100u128;\nmemo: field = 0field;\n"
+    );
+    assert_eq!(
+        record::set_input(source, &file, "burn", "input", "input: Token = Token {};"),
+        "[mint]\namount: u128 = 100u128;\n\n[burn]\ninput: Token = Token {};\n"
+    );
+    assert_eq!(record::set_input("", &inputs::parse("").0, "burn", "input", "x;"), "[burn]\nx;\n");
+}
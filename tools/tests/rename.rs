@@ -0,0 +1,92 @@
+//! Program name validation and renaming a copy of the token package.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::package_copy;
+use workshop::package::{validate_program_name, NameError, Package, MAX_NAME_LENGTH};
+use workshop::rename::{normalize_name, plan, RenameError};
+
+/// Every file a rename touches, with its contents.
+fn snapshot(root: &Path) -> Vec<(String, String)> {
+    ["program.json", "build/program.json", "src/main.leo", "build/main.aleo", "deploy.sh", "README.md"]
+        .iter()
+        .map(|path| (path.to_string(), fs::read_to_string(root.join(path)).unwrap()))
+        .collect()
+}
+
+#[test]
+fn program_names_follow_the_identifier_rules() {
+    assert_eq!(validate_program_name("token_dsfl348dfl93w1"), Ok(()));
+    assert_eq!(validate_program_name(""), Err(NameError::Empty));
+    let long = "a".repeat(MAX_NAME_LENGTH + 1);
+    assert_eq!(validate_program_name(&long), Err(NameError::TooLong(long.clone())));
+    assert_eq!(validate_program_name(&long[1..]), Ok(()));
+    assert_eq!(validate_program_name("1token"), Err(NameError::Start("1token".to_string())));
+    assert_eq!(validate_program_name("_token"), Err(NameError::Start("_token".to_string())));
+    assert_eq!(
+        validate_program_name("my-token"),
+        Err(NameError::Character { name: "my-token".to_string(), character: '-' })
+    );
+    assert!(matches!(validate_program_name("Token"), Err(NameError::Start(_))));
+    assert_eq!(validate_program_name("record"), Err(NameError::Reserved("record".to_string())));
+    assert_eq!(validate_program_name("u128"), Err(NameError::Reserved("u128".to_string())));
+
+    assert_eq!(normalize_name("coins.aleo"), Ok("coins"));
+    assert_eq!(normalize_name("coins"), Ok("coins"));
+}
+
+#[test]
+fn a_rename_updates_every_artifact_and_moves_the_package() {
+    let root = package_copy("rename-apply");
+    let package = Package::open(&root).unwrap();
+    assert!(matches!(plan(&package, "token_dsfl348dfl93w1.aleo"), Err(RenameError::Unchanged(_))));
+    assert!(matches!(plan(&package, "bad-name"), Err(RenameError::Name(_))));
+
+    let plan = plan(&package, "coins_workshop.aleo").unwrap();
+    assert_eq!(plan.new_name, "coins_workshop");
+    assert!(plan.root_move.is_some());
+    let new_root = plan.apply().unwrap();
+    assert_eq!(new_root.file_name().unwrap(), "coins_workshop");
+    assert!(!root.exists());
+
+    let package = Package::open(&new_root).unwrap();
+    assert_eq!(package.program_id(), "coins_workshop.aleo");
+    assert!(package.input_path().ends_with("inputs/coins_workshop.in"));
+    assert!(package.input_path().is_file());
+    for (path, contents) in snapshot(&new_root) {
+        assert!(!contents.contains("token_dsfl348dfl93w1"), "`{path}` still has the old name");
+    }
+    assert!(fs::read_to_string(package.source_path()).unwrap().contains("program coins_workshop.aleo {"));
+    assert!(fs::read_to_string(package.deploy_script_path()).unwrap().contains("APPNAME=\"coins_workshop\""));
+}
+
+#[test]
+fn a_failed_rename_is_rolled_back() {
+    let root = package_copy("rename-rollback");
+    let before = snapshot(&root);
+    let package = Package::open(&root).unwrap();
+    let plan = plan(&package, "coins_workshop").unwrap();
+
+    // Occupy the new package directory after planning, so the last step fails.
+    let (_, new_root) = plan.root_move.clone().unwrap();
+    fs::create_dir_all(&new_root).unwrap();
+    fs::write(new_root.join("keep"), "").unwrap();
+
+    let error = plan.apply().unwrap_err();
+    assert!(matches!(error, RenameError::Apply { action: "move", .. }), "{error}");
+    assert!(error.to_string().contains("rolled back"), "{error}");
+    assert_eq!(snapshot(&root), before);
+    assert!(root.join("inputs/token_dsfl348dfl93w1.in").is_file());
+    assert!(!root.join("inputs/coins_workshop.in").exists());
+}
+
+#[test]
+fn a_taken_package_directory_is_refused_up_front() {
+    let root = package_copy("rename-taken");
+    fs::create_dir(root.with_file_name("coins_workshop")).unwrap();
+    let package = Package::open(&root).unwrap();
+    assert!(matches!(plan(&package, "coins_workshop"), Err(RenameError::Exists { .. })));
+}
@@ -0,0 +1,104 @@
+//! `vest_mint` and `claim`, run at simulated block heights around the unlock.
+
+mod common;
+
+use common::{minted, program, record_balance, run, ADMIN, BOB, MAX_SUPPLY};
+use workshop::interpreter::{InterpretError, Interpreter, Literal, Plaintext, Value};
+
+const UNLOCK_HEIGHT: u32 = 1_000;
+
+/// Vests `amount` for BOB until [`UNLOCK_HEIGHT`].
+fn vest(interpreter: &mut Interpreter<'_>, amount: u128) -> Value {
+    let texts = [BOB, &format!("{amount}u128"), &format!("{UNLOCK_HEIGHT}u32")];
+    run(interpreter, ADMIN, "vest_mint", &texts).unwrap().remove(0)
+}
+
+/// Runs `claim` as BOB at `height`.
+fn claim(interpreter: &mut Interpreter<'_>, vesting: &Value, height: u32) -> Result<Vec<Value>, InterpretError> {
+    interpreter.set_block_height(height);
+    interpreter.set_caller(BOB);
+    interpreter.execute("claim", vec![vesting.clone()])
+}
+
+fn owner(value: &Value) -> String {
+    let Value::Record(record) = value else { panic!("expected a record, found {value}") };
+    match record.member("owner") {
+        Some(Plaintext::Literal(Literal::Address(owner))) => owner.clone(),
+        _ => panic!("the record has no owner: {record}"),
+    }
+}
+
+#[test]
+fn vest_mint_locks_tokens_for_the_receiver() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+
+    let vesting = vest(&mut interpreter, 500);
+    let Value::Record(record) = &vesting else { panic!("expected a record, found {vesting}") };
+    assert_eq!(owner(&vesting), BOB);
+    assert_eq!(record_balance(&vesting), 500);
+    assert_eq!(record.member("unlock_height").map(ToString::to_string), Some(format!("{UNLOCK_HEIGHT}u32")));
+    assert_eq!(minted(&interpreter), Some(500));
+}
+
+#[test]
+fn only_the_admin_can_vest_mint() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+
+    let error = run(&mut interpreter, BOB, "vest_mint", &[BOB, "500u128", "0u32"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    assert_eq!(minted(&interpreter), None);
+}
+
+#[test]
+fn vest_mint_respects_the_supply_cap() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    vest(&mut interpreter, MAX_SUPPLY);
+
+    let error = run(&mut interpreter, ADMIN, "vest_mint", &[BOB, "1u128", "0u32"]).unwrap_err();
+    assert!(matches!(error, InterpretError::Halt { .. }), "{error}");
+    assert_eq!(minted(&interpreter), Some(MAX_SUPPLY));
+}
+
+#[test]
+fn claim_before_the_unlock_height_halts() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let vesting = vest(&mut interpreter, 500);
+
+    for height in [0, UNLOCK_HEIGHT / 2, UNLOCK_HEIGHT - 1] {
+        let error = claim(&mut interpreter, &vesting, height).unwrap_err();
+        assert!(matches!(error, InterpretError::Halt { .. }), "at {height}: {error}");
+    }
+}
+
+#[test]
+fn claim_from_the_unlock_height_returns_a_token() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let vesting = vest(&mut interpreter, 500);
+
+    for height in [UNLOCK_HEIGHT, UNLOCK_HEIGHT + 1, u32::MAX] {
+        let outputs = claim(&mut interpreter, &vesting, height).unwrap();
+        assert_eq!(outputs.len(), 1);
+        let Value::Record(token) = &outputs[0] else { panic!("expected a record, found {}", outputs[0]) };
+        assert!(token.member("unlock_height").is_none(), "{token}");
+        assert_eq!(owner(&outputs[0]), BOB);
+        assert_eq!(record_balance(&outputs[0]), 500);
+    }
+    assert_eq!(minted(&interpreter), Some(500));
+}
+
+#[test]
+fn claim_needs_the_vesting_record_owner() {
+    let program = program();
+    let mut interpreter = Interpreter::new(&program, ADMIN);
+    let vesting = vest(&mut interpreter, 500);
+
+    interpreter.set_block_height(UNLOCK_HEIGHT);
+    interpreter.set_caller(ADMIN);
+    let error = interpreter.execute("claim", vec![vesting]).unwrap_err();
+    assert!(matches!(error, InterpretError::Input { .. }), "{error}");
+}